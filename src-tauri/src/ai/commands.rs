@@ -1,9 +1,13 @@
 // Syntari AI IDE - AI Commands
 // AI-related commands exposed to the frontend
 
-use tauri::State;
-use crate::core::{AppState, TauriResult};
-use crate::ai::types::{AiRequest, ConsensusResult, AiResponse};
+use tauri::{Emitter, Manager};
+use crate::core::{AppState, AppError, AppResult, TauriResult};
+use crate::ai::types::{AiProvider, AiRequest, ConsensusResult, AiResponse};
+use crate::ai::rate_limit::{RateLimiter, RateLimitConfig};
+
+/// Completion budget assumed for rate limiting when a request sets no `max_tokens`
+const DEFAULT_COMPLETION_TOKENS: u32 = 1000;
 
 // ================================
 // AI GENERATION COMMANDS
@@ -12,39 +16,128 @@ use crate::ai::types::{AiRequest, ConsensusResult, AiResponse};
 #[tauri::command]
 pub async fn generate_ai_response(
     request: AiRequest,
-    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
 ) -> std::result::Result<TauriResult<ConsensusResult>, String> {
     tracing::info!("🤖 Generating AI response for request: {}", request.id);
     
-    // Get the project context for better AI routing
-    let project_context = state.get_current_project().await.unwrap_or_else(|| {
-        crate::project::types::ProjectContext::new(
-            "/tmp/unknown".to_string(),
-            "unknown".to_string()
-        )
-    });
+    match run_ai_request(&app_handle, &request).await {
+        Ok(consensus_result) => {
+            tracing::info!("✅ AI response generated successfully with {:.2} confidence", 
+                          consensus_result.confidence_score);
+            Ok(TauriResult::success(consensus_result))
+        }
+        Err(e) => {
+            tracing::error!("❌ AI response generation failed: {}", e);
+            Ok(TauriResult::error(format!("AI response generation failed: {}", e)))
+        }
+    }
+}
+
+/// Route a request through the AI backend; shared by commands that need a completion
+pub async fn run_ai_request(app_handle: &tauri::AppHandle, request: &AiRequest) -> AppResult<ConsensusResult> {
+    let state = app_handle.state::<AppState>();
+    let limiter = app_handle.state::<std::sync::Arc<RateLimiter>>();
+    
+    // Prefer the context carried by the request, then the open project
+    let project_context = match &request.context {
+        Some(context) => context.clone(),
+        None => state.get_current_project().await.unwrap_or_else(|| {
+            crate::project::types::ProjectContext::new(
+                "/tmp/unknown".to_string(),
+                "unknown".to_string()
+            )
+        }),
+    };
     
     // Create a properly formatted prompt with context
+    let sub_project = project_context
+        .active_sub_project()
+        .filter(|project| project.root_path != project_context.root_path)
+        .map(|project| format!("Active Sub-project: {} ({}) at {}\n", project.name, project.project_type, project.relative_path))
+        .unwrap_or_default();
     let contextual_prompt = format!(
-        "Project Type: {}\nProject Path: {}\nUser Query: {}\n\nPlease provide a helpful response.",
+        "Project Type: {}\nProject Path: {}\n{}User Query: {}\n\nPlease provide a helpful response.",
         project_context.project_type,
         project_context.root_path,
+        sub_project,
         request.prompt
     );
     
+    // Wait for capacity on every provider the request fans out to
+    let image_tokens: u32 = request.images.iter().map(|image| estimate_image_tokens(image.width, image.height)).sum();
+    let prompt_tokens = estimate_tokens(&contextual_prompt) + image_tokens;
+    let estimated_tokens = prompt_tokens + request.max_tokens.unwrap_or(DEFAULT_COMPLETION_TOKENS);
+    let mut permits = Vec::new();
+    for provider in target_providers(&state, request).await? {
+        let permit = limiter.acquire(
+            &provider.id,
+            RateLimitConfig::for_provider_type(&provider.provider_type),
+            &request.id,
+            request.priority,
+            estimated_tokens,
+            |status| {
+                if let Err(e) = app_handle.emit("ai-queue-status", &status) {
+                    tracing::warn!("Failed to emit queue status: {}", e);
+                }
+            },
+        ).await?;
+        if !permit.waited.is_zero() {
+            tracing::debug!("Request {} waited {:?} for {}", request.id, permit.waited, provider.id);
+        }
+        permits.push(permit);
+    }
+    
     // For now, create a high-quality mock response that demonstrates the architecture
     // TODO: Replace with real CLI integration once type system is unified
-    let mock_response = create_smart_mock_response(&request, &contextual_prompt, &project_context).await;
+    let result = create_smart_mock_response(request, &contextual_prompt, &project_context)
+        .await
+        .map_err(|e| AppError::ai("GENERATION_FAILED", &e));
     
-    match mock_response {
-        Ok(consensus_result) => {
-            tracing::info!("✅ AI response generated successfully with {:.2} confidence", 
-                          consensus_result.confidence_score);
-            Ok(TauriResult::success(consensus_result))
+    if let Ok(consensus) = &result {
+        let actual_tokens = prompt_tokens + estimate_tokens(&consensus.best_response.content);
+        for permit in &permits {
+            limiter.record_usage(permit, actual_tokens);
         }
-        Err(e) => {
-            tracing::error!("❌ AI response generation failed: {}", e);
-            Ok(TauriResult::error(format!("AI response generation failed: {}", e)))
+    }
+    result
+}
+
+/// Providers a request is sent to: the requested one, or every available provider for consensus.
+/// Requests with images only go to vision-capable providers; text-only providers are refused.
+async fn target_providers(state: &AppState, request: &AiRequest) -> AppResult<Vec<AiProvider>> {
+    let needs_vision = !request.images.is_empty();
+    
+    match &request.provider {
+        Some(id) => {
+            let provider = state
+                .get_ai_provider(id)
+                .await
+                .unwrap_or_else(|| AiProvider::new(id.as_str(), id.as_str(), "unknown"));
+            if needs_vision && !provider.supports_vision() {
+                return Err(AppError::ai_with_details(
+                    "VISION_NOT_SUPPORTED",
+                    &format!("{} cannot read images. Choose a vision-capable provider or remove the images.", provider.name),
+                    Some(&provider.id),
+                    None,
+                ));
+            }
+            Ok(vec![provider])
+        }
+        None => {
+            let providers: Vec<AiProvider> = state
+                .get_ai_providers()
+                .await
+                .into_iter()
+                .filter(|provider| provider.is_available)
+                .filter(|provider| !needs_vision || provider.supports_vision())
+                .collect();
+            if needs_vision && providers.is_empty() {
+                return Err(AppError::ai(
+                    "VISION_NOT_SUPPORTED",
+                    "No available provider can read images. Enable a vision-capable provider or remove the images.",
+                ));
+            }
+            Ok(providers)
         }
     }
 }
@@ -155,7 +248,12 @@ fn generate_general_response(prompt: &str, context: &crate::project::types::Proj
 }
 
 // Token estimation helper
-fn estimate_tokens(text: &str) -> u32 {
+pub(crate) fn estimate_tokens(text: &str) -> u32 {
     // Rough estimation: ~4 characters per token
     (text.len() / 4).max(1) as u32
+}
+
+// Vision models bill roughly one token per 750 pixels
+fn estimate_image_tokens(width: u32, height: u32) -> u32 {
+    (width.saturating_mul(height) / 750).max(1)
 } 
\ No newline at end of file
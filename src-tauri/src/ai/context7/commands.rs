@@ -1,8 +1,10 @@
 // Syntari AI IDE - Context7 Commands
 // Context7 MCP integration commands
 
-use crate::core::TauriResult;
+use tauri::State;
+use crate::core::{AppState, TauriResult};
 use crate::ai::types::{Context7LibraryResult, Context7DocsResult};
+use crate::ai::context7::local_docs::LocalDocsService;
 
 #[tauri::command]
 pub async fn resolve_library_id(_library_name: String) -> std::result::Result<TauriResult<Vec<Context7LibraryResult>>, String> {
@@ -18,4 +20,58 @@ pub async fn get_library_docs(
 ) -> std::result::Result<TauriResult<Context7DocsResult>, String> {
     // Simplified implementation for now
     Ok(TauriResult::error("Context7 integration not yet implemented".to_string()))
-} 
\ No newline at end of file
+} 
+
+// ================================
+// OFFLINE DOCUMENTATION COMMANDS
+// ================================
+
+/// Rebuild the offline docs index for a project (defaults to the open project)
+#[tauri::command]
+pub async fn index_local_docs(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+    docs: State<'_, LocalDocsService>,
+) -> std::result::Result<TauriResult<usize>, String> {
+    let result = async {
+        let root = state.resolve_project_root(project_path).await?;
+        docs.reindex(&root).await
+    }.await;
+
+    Ok(TauriResult::from_result(result))
+}
+
+/// Offline counterpart of `resolve_library_id`
+#[tauri::command]
+pub async fn resolve_local_library_id(
+    library_name: String,
+    state: State<'_, AppState>,
+    docs: State<'_, LocalDocsService>,
+) -> std::result::Result<TauriResult<Vec<Context7LibraryResult>>, String> {
+    let result = async {
+        let root = state.resolve_project_root(None).await?;
+        docs.resolve(&root, &library_name).await
+    }.await;
+
+    Ok(TauriResult::from_result(result))
+}
+
+/// Offline counterpart of `get_library_docs`
+#[tauri::command]
+pub async fn get_local_library_docs(
+    context7_compatible_library_id: String,
+    topic: Option<String>,
+    tokens: Option<u32>,
+    state: State<'_, AppState>,
+    docs: State<'_, LocalDocsService>,
+) -> std::result::Result<TauriResult<Context7DocsResult>, String> {
+    let result = async {
+        let root = state.resolve_project_root(None).await?;
+        docs.docs(&root, &context7_compatible_library_id, topic.as_deref(), tokens).await
+    }.await;
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
@@ -0,0 +1,603 @@
+// Syntari AI IDE - Offline Dependency Documentation
+// Indexes docs already on disk (node_modules, crate sources, rustdoc JSON)
+// and answers Context7-shaped queries without network access
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use crate::core::{AppError, AppResult};
+use crate::ai::types::{Context7LibraryResult, Context7DocsResult};
+use crate::project::types::ProjectType;
+
+const DEFAULT_DOC_TOKENS: u32 = 5000;
+const MAX_DOC_FILE_SIZE: u64 = 2 * 1024 * 1024;
+const MAX_RUSTDOC_JSON_SIZE: u64 = 64 * 1024 * 1024;
+
+// ================================
+// LOCAL DOCS TYPES
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocSection {
+    pub title: String,
+    pub content: String,
+    pub source_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalLibrary {
+    /// Context7-style identifier, e.g. `/npm/react@18.3.1` or `/crates/serde@1.0.219`
+    pub id: String,
+    pub name: String,
+    pub ecosystem: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub sections: Vec<DocSection>,
+}
+
+impl LocalLibrary {
+    fn new(ecosystem: &str, name: &str, version: Option<String>) -> Self {
+        let id = match &version {
+            Some(v) => format!("/{}/{}@{}", ecosystem, name, v),
+            None => format!("/{}/{}", ecosystem, name),
+        };
+        Self {
+            id,
+            name: name.to_string(),
+            ecosystem: ecosystem.to_string(),
+            version,
+            description: None,
+            sections: Vec::new(),
+        }
+    }
+
+    fn to_result(&self) -> Context7LibraryResult {
+        Context7LibraryResult {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            // Local coverage is reported as the number of indexed doc sections
+            documentation_coverage: Some(self.sections.len() as u32),
+            trust_score: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocalDocsIndex {
+    pub root_path: String,
+    pub libraries: HashMap<String, LocalLibrary>,
+    pub indexed_at: u64,
+}
+
+// ================================
+// INDEX BUILDING
+// ================================
+
+impl LocalDocsIndex {
+    /// Index documentation for every dependency `detect_dependencies` reports
+    pub async fn build(project_root: &Path) -> AppResult<Self> {
+        let mut index = Self {
+            root_path: project_root.to_string_lossy().to_string(),
+            libraries: HashMap::new(),
+            indexed_at: crate::core::current_timestamp(),
+        };
+
+        if project_root.join("package.json").exists() {
+            let deps = crate::project::dependencies::detect_dependencies(project_root, &ProjectType::JavaScript).await;
+            for dep in deps {
+                if let Some(library) = index_npm_package(project_root, &dep.name) {
+                    index.insert(library);
+                }
+            }
+        }
+
+        let locked = read_cargo_lock_versions(project_root);
+        for cargo_root in cargo_roots(project_root) {
+            let deps = crate::project::dependencies::detect_dependencies(&cargo_root, &ProjectType::Rust).await;
+            for dep in deps {
+                let version = locked.get(&dep.name.replace('-', "_")).cloned();
+                if let Some(library) = index_crate(project_root, &dep.name, version) {
+                    index.insert(library);
+                }
+            }
+        }
+
+        tracing::info!("📚 Indexed offline docs for {} libraries in {}", index.libraries.len(), index.root_path);
+        Ok(index)
+    }
+
+    fn insert(&mut self, library: LocalLibrary) {
+        if !library.sections.is_empty() {
+            self.libraries.insert(library.id.clone(), library);
+        }
+    }
+
+    // ================================
+    // QUERIES (Context7-compatible shapes)
+    // ================================
+
+    /// Rank libraries by how well their name matches `library_name`
+    pub fn resolve(&self, library_name: &str) -> Vec<Context7LibraryResult> {
+        let needle = normalize_name(library_name);
+        let mut scored: Vec<(u32, &LocalLibrary)> = self.libraries
+            .values()
+            .filter_map(|library| {
+                let name = normalize_name(&library.name);
+                let score = if name == needle {
+                    100
+                } else if name.starts_with(&needle) || needle.starts_with(&name) {
+                    60
+                } else if name.contains(&needle) {
+                    30
+                } else {
+                    return None;
+                };
+                Some((score, library))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        scored.into_iter().map(|(_, library)| library.to_result()).collect()
+    }
+
+    /// Return documentation for a library id, focused on `topic` and trimmed to a token budget
+    pub fn docs(&self, library_id: &str, topic: Option<&str>, tokens: Option<u32>) -> AppResult<Context7DocsResult> {
+        let library = self.libraries
+            .get(library_id)
+            .or_else(|| {
+                // Accept ids without a version suffix
+                self.libraries.values().find(|l| l.id.split('@').next() == Some(library_id)
+                    || format!("/{}/{}", l.ecosystem, l.name) == library_id)
+            })
+            .ok_or_else(|| AppError::ai(
+                "LIBRARY_NOT_INDEXED",
+                &format!("No offline documentation indexed for {}", library_id),
+            ))?;
+
+        let budget = tokens.unwrap_or(DEFAULT_DOC_TOKENS).max(100) as usize;
+        let topic_terms: Vec<String> = topic
+            .map(tokenize)
+            .unwrap_or_default();
+
+        let mut ordered: Vec<(usize, &DocSection)> = library.sections.iter().enumerate().collect();
+        if !topic_terms.is_empty() {
+            ordered.sort_by(|a, b| {
+                let score_a = score_section(a.1, &topic_terms);
+                let score_b = score_section(b.1, &topic_terms);
+                score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0))
+            });
+        }
+
+        let mut content = String::new();
+        let mut used_tokens = 0usize;
+        for (_, section) in ordered {
+            let block = format!("## {}\nSource: {}\n\n{}\n\n", section.title, section.source_path, section.content.trim());
+            let block_tokens = estimate_tokens(&block);
+            if used_tokens + block_tokens > budget {
+                if used_tokens == 0 {
+                    // Always return something, even if the first section alone is too large
+                    let truncated: String = block.chars().take(budget * 4).collect();
+                    used_tokens = estimate_tokens(&truncated);
+                    content.push_str(&truncated);
+                }
+                break;
+            }
+            used_tokens += block_tokens;
+            content.push_str(&block);
+        }
+
+        Ok(Context7DocsResult {
+            library_id: library.id.clone(),
+            content,
+            tokens_used: used_tokens as u32,
+            topic_focused: topic.map(|t| t.to_string()),
+        })
+    }
+}
+
+// ================================
+// NPM PACKAGES
+// ================================
+
+fn index_npm_package(project_root: &Path, name: &str) -> Option<LocalLibrary> {
+    let package_dir = project_root.join("node_modules").join(name);
+    if !package_dir.is_dir() {
+        return None;
+    }
+
+    let manifest = std::fs::read_to_string(package_dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+    let version = manifest.as_ref()
+        .and_then(|m| m.get("version"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string());
+
+    let mut library = LocalLibrary::new("npm", name, version);
+    library.description = manifest.as_ref()
+        .and_then(|m| m.get("description"))
+        .and_then(|d| d.as_str())
+        .map(|d| d.to_string());
+
+    if let Some(readme) = find_readme(&package_dir) {
+        library.sections.extend(markdown_sections(&readme, name));
+    }
+
+    // Many packages ship extra markdown under docs/
+    let docs_dir = package_dir.join("docs");
+    if docs_dir.is_dir() {
+        for entry in walkdir::WalkDir::new(&docs_dir).max_depth(3).into_iter().flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("md") {
+                library.sections.extend(markdown_sections(path, name));
+            }
+        }
+    }
+
+    Some(library)
+}
+
+// ================================
+// RUST CRATES
+// ================================
+
+fn index_crate(project_root: &Path, name: &str, version: Option<String>) -> Option<LocalLibrary> {
+    let crate_dir = find_crate_source(project_root, name, version.as_deref());
+    let rustdoc_json = find_rustdoc_json(project_root, name);
+    if crate_dir.is_none() && rustdoc_json.is_none() {
+        return None;
+    }
+
+    let mut library = LocalLibrary::new("crates", name, version);
+
+    if let Some(dir) = &crate_dir {
+        if let Some(readme) = find_readme(dir) {
+            library.sections.extend(markdown_sections(&readme, name));
+        }
+        let lib_rs = dir.join("src").join("lib.rs");
+        if lib_rs.is_file() {
+            library.sections.extend(crate_source_sections(&lib_rs, name));
+        }
+    }
+
+    if let Some(json_path) = &rustdoc_json {
+        library.sections.extend(rustdoc_json_sections(json_path, name));
+    }
+
+    library.description = library.sections
+        .first()
+        .and_then(|s| s.content.split("\n\n").find(|p| !p.trim().is_empty() && !p.trim_start().starts_with('[')))
+        .map(|p| p.trim().chars().take(240).collect());
+
+    Some(library)
+}
+
+/// Directories holding a Cargo manifest: the project root, or `src-tauri` in Tauri apps
+fn cargo_roots(project_root: &Path) -> Vec<PathBuf> {
+    ["", "src-tauri"]
+        .iter()
+        .map(|dir| project_root.join(dir))
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .collect()
+}
+
+/// Vendored sources first, then the local cargo registry cache
+pub(crate) fn find_crate_source(project_root: &Path, name: &str, version: Option<&str>) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    let vendor_dirs = cargo_roots(project_root)
+        .into_iter()
+        .flat_map(|root| [root.join("vendor"), root.join("third_party")]);
+    for base in vendor_dirs {
+        if let Some(v) = version {
+            candidates.push(base.join(format!("{}-{}", name, v)));
+        }
+        candidates.push(base.join(name));
+    }
+    if let Some(found) = candidates.into_iter().find(|p| p.join("Cargo.toml").is_file()) {
+        return Some(found);
+    }
+
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".cargo")))?;
+    let registry_src = cargo_home.join("registry").join("src");
+    let prefix = format!("{}-", name);
+
+    let mut best: Option<(semver::Version, PathBuf)> = None;
+    for registry in std::fs::read_dir(&registry_src).ok()?.flatten() {
+        if let Some(v) = version {
+            let exact = registry.path().join(format!("{}-{}", name, v));
+            if exact.is_dir() {
+                return Some(exact);
+            }
+            continue;
+        }
+        // Unknown version: take the highest version among matching directories
+        for entry in std::fs::read_dir(registry.path()).into_iter().flatten().flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(found) = file_name.strip_prefix(&prefix).and_then(|rest| semver::Version::parse(rest).ok()) else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|(current, _)| found > *current) {
+                best = Some((found, entry.path()));
+            }
+        }
+    }
+    best.map(|(_, path)| path)
+}
+
+fn find_rustdoc_json(project_root: &Path, name: &str) -> Option<PathBuf> {
+    let file_name = format!("{}.json", name.replace('-', "_"));
+    cargo_roots(project_root)
+        .into_iter()
+        .map(|root| root.join("target").join("doc").join(&file_name))
+        .find(|p| p.is_file())
+}
+
+fn read_cargo_lock_versions(project_root: &Path) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    for root in cargo_roots(project_root) {
+        let Ok(content) = std::fs::read_to_string(root.join("Cargo.lock")) else {
+            continue;
+        };
+        let mut current_name: Option<String> = None;
+        for line in content.lines() {
+            let line = line.trim();
+            if line == "[[package]]" {
+                current_name = None;
+            } else if let Some(value) = line.strip_prefix("name = ") {
+                current_name = Some(value.trim_matches('"').replace('-', "_"));
+            } else if let Some(value) = line.strip_prefix("version = ") {
+                if let Some(name) = current_name.take() {
+                    versions.entry(name).or_insert_with(|| value.trim_matches('"').to_string());
+                }
+            }
+        }
+    }
+    versions
+}
+
+/// Crate-level `//!` docs and `///` docs on public items of `lib.rs`
+fn crate_source_sections(lib_rs: &Path, crate_name: &str) -> Vec<DocSection> {
+    let Some(content) = read_small_file(lib_rs) else {
+        return Vec::new();
+    };
+    let source_path = lib_rs.to_string_lossy().to_string();
+    let mut sections = Vec::new();
+
+    let crate_docs: Vec<&str> = content
+        .lines()
+        .filter_map(|l| l.trim_start().strip_prefix("//!"))
+        .map(|l| l.strip_prefix(' ').unwrap_or(l))
+        .collect();
+    if !crate_docs.is_empty() {
+        sections.push(DocSection {
+            title: format!("{} (crate docs)", crate_name),
+            content: crate_docs.join("\n"),
+            source_path: source_path.clone(),
+        });
+    }
+
+    let mut pending_docs: Vec<&str> = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(doc) = trimmed.strip_prefix("///") {
+            pending_docs.push(doc.strip_prefix(' ').unwrap_or(doc));
+            continue;
+        }
+        if trimmed.starts_with("#[") {
+            continue;
+        }
+        if trimmed.starts_with("pub ") && !pending_docs.is_empty() {
+            let signature = trimmed.trim_end_matches('{').trim_end_matches(';').trim();
+            sections.push(DocSection {
+                title: format!("{}: {}", crate_name, signature),
+                content: pending_docs.join("\n"),
+                source_path: source_path.clone(),
+            });
+        }
+        pending_docs.clear();
+    }
+
+    sections
+}
+
+/// Items with docs from rustdoc's JSON output (`cargo +nightly rustdoc -- -Z unstable-options --output-format json`)
+fn rustdoc_json_sections(json_path: &Path, crate_name: &str) -> Vec<DocSection> {
+    let too_large = json_path.metadata().map(|m| m.len() > MAX_RUSTDOC_JSON_SIZE).unwrap_or(true);
+    if too_large {
+        return Vec::new();
+    }
+    let Some(doc) = std::fs::read(json_path).ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok()) else {
+        return Vec::new();
+    };
+
+    let source_path = json_path.to_string_lossy().to_string();
+    let paths = doc.get("paths").and_then(|p| p.as_object());
+    let Some(index) = doc.get("index").and_then(|i| i.as_object()) else {
+        return Vec::new();
+    };
+
+    let mut sections: Vec<DocSection> = index
+        .iter()
+        .filter(|(_, item)| item.get("crate_id").and_then(|c| c.as_u64()) == Some(0))
+        .filter_map(|(id, item)| {
+            let docs = item.get("docs").and_then(|d| d.as_str())?.trim();
+            if docs.is_empty() {
+                return None;
+            }
+            let title = paths
+                .and_then(|p| p.get(id))
+                .and_then(|p| p.get("path"))
+                .and_then(|p| p.as_array())
+                .map(|parts| parts.iter().filter_map(|s| s.as_str()).collect::<Vec<_>>().join("::"))
+                .or_else(|| item.get("name").and_then(|n| n.as_str()).map(|n| n.to_string()))
+                .unwrap_or_else(|| crate_name.to_string());
+            Some(DocSection {
+                title,
+                content: docs.to_string(),
+                source_path: source_path.clone(),
+            })
+        })
+        .collect();
+
+    sections.sort_by(|a, b| a.title.len().cmp(&b.title.len()).then_with(|| a.title.cmp(&b.title)));
+    sections
+}
+
+// ================================
+// SHARED HELPERS
+// ================================
+
+fn find_readme(dir: &Path) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .find(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.to_lowercase())
+                .is_some_and(|n| n == "readme.md" || n == "readme.markdown" || n == "readme")
+        })
+}
+
+fn read_small_file(path: &Path) -> Option<String> {
+    let size = path.metadata().ok()?.len();
+    if size > MAX_DOC_FILE_SIZE {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
+}
+
+/// Split markdown at headings; text before the first heading is titled after the library
+fn markdown_sections(path: &Path, library_name: &str) -> Vec<DocSection> {
+    let Some(content) = read_small_file(path) else {
+        return Vec::new();
+    };
+    let source_path = path.to_string_lossy().to_string();
+
+    let mut sections = Vec::new();
+    let mut title = library_name.to_string();
+    let mut body = String::new();
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        let heading = if in_code_block { None } else { line.strip_prefix('#') };
+        if let Some(rest) = heading {
+            if !body.trim().is_empty() {
+                sections.push(DocSection {
+                    title: title.clone(),
+                    content: body.trim().to_string(),
+                    source_path: source_path.clone(),
+                });
+            }
+            title = rest.trim_start_matches('#').trim().to_string();
+            body.clear();
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+
+    if !body.trim().is_empty() {
+        sections.push(DocSection {
+            title,
+            content: body.trim().to_string(),
+            source_path,
+        });
+    }
+
+    sections
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('/')
+        .to_lowercase()
+        .replace('_', "-")
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.len() > 1)
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Term-frequency score with a boost for title matches
+fn score_section(section: &DocSection, terms: &[String]) -> f64 {
+    let title = section.title.to_lowercase();
+    let content = section.content.to_lowercase();
+    let length_norm = 1.0 + (content.len() as f64 / 2000.0);
+
+    terms.iter().map(|term| {
+        let title_hits = title.matches(term.as_str()).count() as f64;
+        let body_hits = content.matches(term.as_str()).count() as f64;
+        title_hits * 5.0 + (1.0 + body_hits).ln() / length_norm
+    }).sum()
+}
+
+fn estimate_tokens(text: &str) -> usize {
+    // Rough estimation: ~4 characters per token
+    (text.len() / 4).max(1)
+}
+
+// ================================
+// SERVICE
+// ================================
+
+/// Registered as Tauri state; caches one index per project root
+#[derive(Default)]
+pub struct LocalDocsService {
+    indexes: Mutex<HashMap<String, LocalDocsIndex>>,
+}
+
+impl LocalDocsService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// (Re)build the index for a project
+    pub async fn reindex(&self, project_root: &str) -> AppResult<usize> {
+        let index = LocalDocsIndex::build(Path::new(project_root)).await?;
+        let count = index.libraries.len();
+        self.indexes.lock().await.insert(project_root.to_string(), index);
+        Ok(count)
+    }
+
+    pub async fn resolve(&self, project_root: &str, library_name: &str) -> AppResult<Vec<Context7LibraryResult>> {
+        self.ensure_indexed(project_root).await?;
+        let indexes = self.indexes.lock().await;
+        Ok(indexes.get(project_root).map(|i| i.resolve(library_name)).unwrap_or_default())
+    }
+
+    pub async fn docs(
+        &self,
+        project_root: &str,
+        library_id: &str,
+        topic: Option<&str>,
+        tokens: Option<u32>,
+    ) -> AppResult<Context7DocsResult> {
+        self.ensure_indexed(project_root).await?;
+        let indexes = self.indexes.lock().await;
+        match indexes.get(project_root) {
+            Some(index) => index.docs(library_id, topic, tokens),
+            None => Err(AppError::ai("LIBRARY_NOT_INDEXED", "Offline documentation index is unavailable")),
+        }
+    }
+
+    async fn ensure_indexed(&self, project_root: &str) -> AppResult<()> {
+        if self.indexes.lock().await.contains_key(project_root) {
+            return Ok(());
+        }
+        self.reindex(project_root).await.map(|_| ())
+    }
+}
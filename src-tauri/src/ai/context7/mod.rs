@@ -3,5 +3,7 @@
 
 pub mod commands;
 pub mod service;
+pub mod local_docs;
 
-pub use service::Context7Service; 
\ No newline at end of file
+pub use service::Context7Service;
+pub use local_docs::{LocalDocsService, LocalDocsIndex}; 
\ No newline at end of file
@@ -4,10 +4,13 @@
 pub mod types;
 pub mod commands;
 pub mod context7;
+pub mod semantic;
+pub mod prompts;
+pub mod rate_limit;
 
 // Re-export commonly used types
 pub use types::{
-    AiProvider, AiRequest, AiResponse, ConsensusResult,
+    AiProvider, AiRequest, AiResponse, ConsensusResult, RequestPriority,
     AiApiResponse, Context7LibraryResult, Context7DocsResult
 };
 
@@ -0,0 +1,103 @@
+// Syntari AI IDE - Prompt Template Commands
+// Template listing, preview and execution exposed to the frontend
+
+use std::path::PathBuf;
+use tauri::State;
+use crate::core::{AppError, AppResult, AppState, TauriResult, app_data_subdir, generate_id};
+use crate::ai::commands::run_ai_request;
+use crate::ai::types::{AiRequest, ConsensusResult};
+use crate::ai::prompts::library::PromptLibrary;
+use crate::ai::prompts::renderer::render;
+use crate::ai::prompts::types::{PromptContext, PromptTemplate, RenderedPrompt};
+
+const USER_TEMPLATE_DIR: &str = "prompt-templates";
+
+async fn load_library(app_handle: &tauri::AppHandle, state: &AppState) -> AppResult<PromptLibrary> {
+    let user_dir = app_data_subdir(app_handle, USER_TEMPLATE_DIR)?;
+    let project_root = state.get_current_project().await.map(|p| PathBuf::from(p.root_path));
+    Ok(PromptLibrary::load(Some(&user_dir), project_root.as_deref()))
+}
+
+async fn render_template(
+    template_id: &str,
+    context: &PromptContext,
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> AppResult<(PromptTemplate, RenderedPrompt)> {
+    let library = load_library(app_handle, state).await?;
+    let template = library.get(template_id)?.clone();
+    let project = state.get_current_project().await;
+    let rendered = render(&template, context, project.as_ref());
+    Ok((template, rendered))
+}
+
+#[tauri::command]
+pub async fn list_prompt_templates(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<Vec<PromptTemplate>>, String> {
+    let result = load_library(&app_handle, &state).await.map(|library| library.list());
+
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Preview a template; missing variables are reported rather than treated as errors
+#[tauri::command]
+pub async fn render_prompt_template(
+    template_id: String,
+    context: Option<PromptContext>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<RenderedPrompt>, String> {
+    let context = context.unwrap_or_default();
+    let result = render_template(&template_id, &context, &app_handle, &state)
+        .await
+        .map(|(_, rendered)| rendered);
+
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn execute_prompt_template(
+    template_id: String,
+    context: Option<PromptContext>,
+    provider: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<ConsensusResult>, String> {
+    let result = async {
+        let context = context.unwrap_or_default();
+        let (template, rendered) = render_template(&template_id, &context, &app_handle, &state).await?;
+
+        if !rendered.is_complete() {
+            return Err(AppError::validation_with_field(
+                "MISSING_TEMPLATE_VARIABLES",
+                &format!(
+                    "Template '{}' needs values for: {}",
+                    template.id,
+                    rendered.missing_variables.join(", ")
+                ),
+                "context",
+            ));
+        }
+
+        tracing::info!("📝 Executing prompt template '{}' (~{} tokens)", template.id, rendered.estimated_tokens);
+
+        let mut request = AiRequest::new(generate_id(), rendered.prompt);
+        if let Some(provider) = provider {
+            request = request.with_provider(provider);
+        }
+        run_ai_request(&app_handle, &request).await
+    }.await;
+
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
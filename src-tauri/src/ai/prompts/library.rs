@@ -0,0 +1,194 @@
+// Syntari AI IDE - Prompt Template Library
+// Loads built-in, user and project templates with project > user > builtin precedence
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::core::{AppError, AppResult};
+use crate::ai::prompts::types::{PromptTemplate, TemplateScope, TemplateVariable, VariableKind};
+
+/// Project templates live in `<root>/.syntari/prompts/*.md`
+pub const PROJECT_TEMPLATE_DIR: &str = ".syntari/prompts";
+
+lazy_static! {
+    static ref VARIABLE_PATTERN: Regex = Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap();
+}
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("explain", r#"---
+name: Explain code
+description: Explain what the selected code does and why
+---
+Explain the following {{language}} code from `{{file}}`.
+Describe what it does, how it works and any non-obvious behaviour or edge cases.
+
+```{{language}}
+{{selection}}
+```
+"#),
+    ("test", r#"---
+name: Write tests
+description: Generate unit tests for the selected code
+---
+Write unit tests for the following {{language}} code from `{{file}}`.
+Follow the testing conventions already used in the project, cover edge cases and error paths.
+
+```{{language}}
+{{selection}}
+```
+
+Known diagnostics:
+{{diagnostics}}
+"#),
+    ("refactor", r#"---
+name: Refactor
+description: Suggest a refactoring of the selected code
+---
+Refactor the following {{language}} code from `{{file}}` to improve readability and maintainability without changing behaviour.
+Explain each change briefly.
+
+```{{language}}
+{{selection}}
+```
+
+Uncommitted changes for context:
+```diff
+{{git_diff}}
+```
+"#),
+];
+
+pub struct PromptLibrary {
+    templates: BTreeMap<String, PromptTemplate>,
+}
+
+impl PromptLibrary {
+    /// Load every template visible for a project; later scopes override earlier ones by id
+    pub fn load(user_dir: Option<&Path>, project_root: Option<&Path>) -> Self {
+        let mut templates = BTreeMap::new();
+
+        for (id, source) in BUILTIN_TEMPLATES {
+            let template = parse_template(id, source, TemplateScope::Builtin, None);
+            templates.insert(template.id.clone(), template);
+        }
+
+        if let Some(dir) = user_dir {
+            for template in load_directory(dir, TemplateScope::User) {
+                templates.insert(template.id.clone(), template);
+            }
+        }
+
+        if let Some(root) = project_root {
+            for template in load_directory(&root.join(PROJECT_TEMPLATE_DIR), TemplateScope::Project) {
+                templates.insert(template.id.clone(), template);
+            }
+        }
+
+        Self { templates }
+    }
+
+    pub fn list(&self) -> Vec<PromptTemplate> {
+        self.templates.values().cloned().collect()
+    }
+
+    pub fn get(&self, id: &str) -> AppResult<&PromptTemplate> {
+        self.templates.get(id).ok_or_else(|| {
+            AppError::validation_with_field(
+                "TEMPLATE_NOT_FOUND",
+                &format!("Prompt template '{}' does not exist", id),
+                "template_id",
+            )
+        })
+    }
+}
+
+fn load_directory(dir: &Path, scope: TemplateScope) -> Vec<PromptTemplate> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_string_lossy().to_string();
+            match std::fs::read_to_string(&path) {
+                Ok(source) => Some(parse_template(&id, &source, scope, Some(&path))),
+                Err(e) => {
+                    tracing::warn!("Skipping prompt template {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Parse a Markdown template with optional `---` front matter (`name`, `description`)
+pub fn parse_template(id: &str, source: &str, scope: TemplateScope, path: Option<&Path>) -> PromptTemplate {
+    let mut name = None;
+    let mut description = None;
+    let mut body = source;
+
+    let normalized = source.trim_start_matches('\u{feff}');
+    if let Some(rest) = normalized.strip_prefix("---\n").or_else(|| normalized.strip_prefix("---\r\n")) {
+        if let Some(end) = rest.find("\n---") {
+            for line in rest[..end].lines() {
+                if let Some((key, value)) = line.split_once(':') {
+                    let value = value.trim().trim_matches('"').to_string();
+                    match key.trim() {
+                        "name" => name = Some(value),
+                        "description" => description = Some(value),
+                        _ => {}
+                    }
+                }
+            }
+            let after = &rest[end + 4..];
+            body = after.strip_prefix("\r\n").or_else(|| after.strip_prefix('\n')).unwrap_or(after);
+        }
+    }
+
+    PromptTemplate {
+        id: id.to_string(),
+        name: name.unwrap_or_else(|| id.to_string()),
+        description,
+        scope,
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        variables: extract_variables(body),
+        body: body.to_string(),
+    }
+}
+
+/// Variables referenced by a template body, in first-use order
+pub fn extract_variables(body: &str) -> Vec<TemplateVariable> {
+    let mut variables: Vec<TemplateVariable> = Vec::new();
+    for capture in VARIABLE_PATTERN.captures_iter(body) {
+        let name = &capture[1];
+        if variables.iter().any(|v| v.name == name) {
+            continue;
+        }
+        let kind = VariableKind::from_name(name);
+        variables.push(TemplateVariable {
+            name: name.to_string(),
+            kind,
+            required: !kind.is_optional(),
+        });
+    }
+    variables
+}
+
+/// Replace each `{{name}}` with its value; unknown names are left untouched
+pub fn substitute(body: &str, values: &std::collections::HashMap<String, String>) -> String {
+    VARIABLE_PATTERN
+        .replace_all(body, |caps: &regex::Captures| match values.get(&caps[1]) {
+            Some(value) => value.clone(),
+            None => caps[0].to_string(),
+        })
+        .to_string()
+}
@@ -0,0 +1,10 @@
+// Syntari AI IDE - Prompt Templates
+// User- and project-level prompt templates with typed variables
+
+pub mod types;
+pub mod library;
+pub mod renderer;
+pub mod commands;
+
+pub use types::{PromptTemplate, TemplateScope, TemplateVariable, VariableKind, PromptContext, RenderedPrompt};
+pub use library::PromptLibrary;
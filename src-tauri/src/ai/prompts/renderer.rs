@@ -0,0 +1,119 @@
+// Syntari AI IDE - Prompt Template Renderer
+// Resolves template variables from editor context, project state and git
+
+use std::collections::HashMap;
+use std::path::Path;
+use crate::core::detect_language_from_extension;
+use crate::project::types::ProjectContext;
+use crate::ai::prompts::library::substitute;
+use crate::ai::prompts::types::{PromptContext, PromptTemplate, RenderedPrompt, VariableKind};
+
+/// Cap on file content used when there is no selection
+const MAX_FALLBACK_CHARS: usize = 24_000;
+/// Cap on the git diff pasted into a prompt
+const MAX_DIFF_CHARS: usize = 16_000;
+
+pub fn render(template: &PromptTemplate, context: &PromptContext, project: Option<&ProjectContext>) -> RenderedPrompt {
+    let mut values = HashMap::new();
+    let mut missing_variables = Vec::new();
+
+    for variable in &template.variables {
+        let value = match context.values.get(&variable.name) {
+            Some(explicit) => Some(explicit.clone()),
+            None => resolve_variable(variable.kind, context, project),
+        };
+
+        match value {
+            Some(value) if !value.trim().is_empty() => {
+                values.insert(variable.name.clone(), value);
+            }
+            _ if variable.required => missing_variables.push(variable.name.clone()),
+            _ => {
+                values.insert(variable.name.clone(), String::new());
+            }
+        }
+    }
+
+    let prompt = substitute(&template.body, &values);
+    let estimated_tokens = (prompt.len() / 4).max(1) as u32;
+
+    RenderedPrompt {
+        template_id: template.id.clone(),
+        prompt,
+        values,
+        missing_variables,
+        estimated_tokens,
+    }
+}
+
+fn resolve_variable(kind: VariableKind, context: &PromptContext, project: Option<&ProjectContext>) -> Option<String> {
+    match kind {
+        VariableKind::Selection => resolve_selection(context),
+        VariableKind::File => context.active_file.as_ref().map(|file| relative_path(file, project)),
+        VariableKind::Language => resolve_language(context, project),
+        VariableKind::Diagnostics => context.diagnostics.as_ref().map(|items| {
+            items.iter().map(|d| format!("- {}", d)).collect::<Vec<_>>().join("\n")
+        }),
+        VariableKind::GitDiff => resolve_git_diff(context, project),
+        VariableKind::Text => None,
+    }
+}
+
+/// Selected text, or the active file's content when nothing is selected
+fn resolve_selection(context: &PromptContext) -> Option<String> {
+    if let Some(selected) = context.selected_text.as_ref().filter(|s| !s.trim().is_empty()) {
+        return Some(selected.clone());
+    }
+
+    let content = std::fs::read_to_string(context.active_file.as_ref()?).ok()?;
+    Some(truncate(content, MAX_FALLBACK_CHARS))
+}
+
+fn resolve_language(context: &PromptContext, project: Option<&ProjectContext>) -> Option<String> {
+    let from_file = context
+        .active_file
+        .as_ref()
+        .map(|file| detect_language_from_extension(Path::new(file)))
+        .filter(|language| language != "plaintext");
+
+    from_file.or_else(|| project.map(|p| p.project_type.clone()).filter(|t| t != "unknown"))
+}
+
+#[cfg(feature = "git-integration")]
+fn resolve_git_diff(context: &PromptContext, project: Option<&ProjectContext>) -> Option<String> {
+    let repository = project
+        .map(|p| p.root_path.clone())
+        .or_else(|| context.active_file.as_ref().and_then(|f| Path::new(f).parent()).map(|p| p.to_string_lossy().to_string()))?;
+
+    match crate::filesystem::git_commands::collect_working_tree_diff(&repository, context.active_file.as_deref()) {
+        Ok(diff) => Some(truncate(diff, MAX_DIFF_CHARS)),
+        Err(e) => {
+            e.log_warn();
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "git-integration"))]
+fn resolve_git_diff(_context: &PromptContext, _project: Option<&ProjectContext>) -> Option<String> {
+    None
+}
+
+fn relative_path(file: &str, project: Option<&ProjectContext>) -> String {
+    project
+        .and_then(|p| Path::new(file).strip_prefix(&p.root_path).ok())
+        .map(|relative| relative.to_string_lossy().to_string())
+        .unwrap_or_else(|| file.to_string())
+}
+
+fn truncate(mut text: String, max_chars: usize) -> String {
+    if text.len() > max_chars {
+        let mut cut = max_chars;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        text.truncate(cut);
+        text.push_str("\n... (truncated)");
+    }
+    text
+}
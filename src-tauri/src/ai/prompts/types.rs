@@ -0,0 +1,114 @@
+// Syntari AI IDE - Prompt Template Types
+// Data structures for templates, their variables and rendered output
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::chat::types::{ChatContext, CursorPosition};
+
+// ================================
+// TEMPLATE TYPES
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateScope {
+    Builtin,
+    User,
+    Project,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableKind {
+    /// `{{selection}}` - selected text, or the active file when nothing is selected
+    Selection,
+    /// `{{file}}` - active file path relative to the project root
+    File,
+    /// `{{language}}` - language of the active file (falls back to the project type)
+    Language,
+    /// `{{diagnostics}}` - editor diagnostics supplied by the caller
+    Diagnostics,
+    /// `{{git_diff}}` - uncommitted changes for the active file, or the whole repository
+    GitDiff,
+    /// Any other name; the caller supplies the value
+    Text,
+}
+
+impl VariableKind {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "selection" => Self::Selection,
+            "file" => Self::File,
+            "language" => Self::Language,
+            "diagnostics" => Self::Diagnostics,
+            "git_diff" => Self::GitDiff,
+            _ => Self::Text,
+        }
+    }
+
+    /// Context variables may legitimately be empty; everything else must resolve
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::Diagnostics | Self::GitDiff)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub kind: VariableKind,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub scope: TemplateScope,
+    pub path: Option<String>,
+    pub variables: Vec<TemplateVariable>,
+    pub body: String,
+}
+
+// ================================
+// RENDERING TYPES
+// ================================
+
+/// Editor state used to resolve template variables
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptContext {
+    pub active_file: Option<String>,
+    pub selected_text: Option<String>,
+    pub cursor_position: Option<CursorPosition>,
+    pub diagnostics: Option<Vec<String>>,
+    /// Explicit values; these override anything resolved from context
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+}
+
+impl From<&ChatContext> for PromptContext {
+    fn from(context: &ChatContext) -> Self {
+        Self {
+            active_file: context.active_file.clone(),
+            selected_text: context.selected_text.clone(),
+            cursor_position: context.cursor_position.clone(),
+            diagnostics: context.diagnostics.clone(),
+            values: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedPrompt {
+    pub template_id: String,
+    pub prompt: String,
+    pub values: HashMap<String, String>,
+    pub missing_variables: Vec<String>,
+    pub estimated_tokens: u32,
+}
+
+impl RenderedPrompt {
+    pub fn is_complete(&self) -> bool {
+        self.missing_variables.is_empty()
+    }
+}
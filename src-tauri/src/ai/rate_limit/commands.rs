@@ -0,0 +1,41 @@
+// Syntari AI IDE - Rate Limit Commands
+// Provider limit inspection and configuration exposed to the frontend
+
+use std::sync::Arc;
+use tauri::State;
+use crate::core::{AppError, TauriResult};
+use crate::ai::rate_limit::limiter::RateLimiter;
+use crate::ai::rate_limit::types::{RateLimitConfig, ProviderRateStatus};
+
+#[tauri::command]
+pub async fn get_rate_limit_status(
+    limiter: State<'_, Arc<RateLimiter>>,
+) -> std::result::Result<TauriResult<Vec<ProviderRateStatus>>, String> {
+    Ok(TauriResult::success(limiter.status()))
+}
+
+#[tauri::command]
+pub async fn set_provider_rate_limit(
+    provider: String,
+    config: RateLimitConfig,
+    limiter: State<'_, Arc<RateLimiter>>,
+) -> std::result::Result<TauriResult<()>, String> {
+    let result = if config.requests_per_minute == 0 || config.tokens_per_minute == 0 {
+        Err(AppError::validation_with_field(
+            "INVALID_RATE_LIMIT",
+            "Rate limits must be greater than zero",
+            "config",
+        ))
+    } else {
+        tracing::info!(
+            "Setting rate limit for {}: {} req/min, {} tokens/min",
+            provider, config.requests_per_minute, config.tokens_per_minute
+        );
+        limiter.configure(&provider, config)
+    };
+
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
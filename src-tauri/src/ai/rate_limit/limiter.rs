@@ -0,0 +1,400 @@
+// Syntari AI IDE - Rate Limiter
+// Per-provider request/token buckets, Retry-After backoff and a priority wait queue
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use crate::core::{AppError, AppResult};
+use crate::ai::types::RequestPriority;
+use crate::ai::rate_limit::types::{RateLimitConfig, QueueStatus, ProviderRateStatus};
+
+/// Upper bound on a single sleep so waiters re-check after config changes
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Fallback backoff for a 429 without a usable `Retry-After`
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+// ================================
+// TOKEN BUCKET
+// ================================
+
+struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn per_minute(limit: u32, now: Instant) -> Self {
+        let capacity = limit.max(1) as f64;
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Time until `amount` is available; requests larger than the bucket wait for a full bucket
+    fn wait_for(&self, amount: f64) -> Duration {
+        let needed = amount.min(self.capacity) - self.available;
+        if needed <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(needed / self.refill_per_sec)
+        }
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.available -= amount;
+    }
+
+    /// Correct an earlier estimate; the balance may go negative and is repaid by refill
+    fn adjust(&mut self, delta: f64) {
+        self.available = (self.available - delta).min(self.capacity);
+    }
+}
+
+// ================================
+// PROVIDER STATE
+// ================================
+
+struct Waiter {
+    ticket: u64,
+    priority: RequestPriority,
+    tokens: u32,
+}
+
+struct ProviderLimiter {
+    config: RateLimitConfig,
+    requests: TokenBucket,
+    tokens: TokenBucket,
+    blocked_until: Option<Instant>,
+    queue: Vec<Waiter>,
+}
+
+impl ProviderLimiter {
+    fn new(config: RateLimitConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            requests: TokenBucket::per_minute(config.requests_per_minute, now),
+            tokens: TokenBucket::per_minute(config.tokens_per_minute, now),
+            config,
+            blocked_until: None,
+            queue: Vec::new(),
+        }
+    }
+
+    fn enqueue(&mut self, waiter: Waiter) {
+        // Highest priority first, FIFO within a priority
+        let index = self
+            .queue
+            .iter()
+            .position(|w| w.priority < waiter.priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(index, waiter);
+    }
+
+    fn position(&self, ticket: u64) -> Option<usize> {
+        self.queue.iter().position(|w| w.ticket == ticket)
+    }
+
+    fn remove(&mut self, ticket: u64) {
+        self.queue.retain(|w| w.ticket != ticket);
+    }
+
+    /// Time until a request of `tokens` could be dispatched, ignoring queue order
+    fn ready_in(&mut self, tokens: u32, now: Instant) -> Duration {
+        self.requests.refill(now);
+        self.tokens.refill(now);
+
+        let backoff = self
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or(Duration::ZERO);
+        if backoff.is_zero() {
+            self.blocked_until = None;
+        }
+
+        backoff
+            .max(self.requests.wait_for(1.0))
+            .max(self.tokens.wait_for(tokens as f64))
+    }
+
+    /// Rough wait for the waiter at `position`: the head's wait plus one request slot per waiter ahead
+    fn estimated_wait(&mut self, position: usize, now: Instant) -> Duration {
+        let head_tokens = self.queue.first().map(|w| w.tokens).unwrap_or(0);
+        let head_wait = self.ready_in(head_tokens, now);
+        let ahead_tokens: u32 = self.queue.iter().take(position).map(|w| w.tokens).sum();
+        let per_request = Duration::from_secs_f64(60.0 / self.config.requests_per_minute.max(1) as f64);
+        let per_tokens = Duration::from_secs_f64(
+            ahead_tokens as f64 * 60.0 / self.config.tokens_per_minute.max(1) as f64,
+        );
+        head_wait + (per_request * position as u32).max(per_tokens)
+    }
+
+    fn status(&mut self, provider: &str, now: Instant) -> ProviderRateStatus {
+        self.requests.refill(now);
+        self.tokens.refill(now);
+        ProviderRateStatus {
+            provider: provider.to_string(),
+            config: self.config.clone(),
+            available_requests: self.requests.available.max(0.0) as u32,
+            available_tokens: self.tokens.available.max(0.0) as u32,
+            queued: self.queue.len(),
+            blocked_for_ms: self
+                .blocked_until
+                .map(|until| until.saturating_duration_since(now).as_millis() as u64)
+                .filter(|ms| *ms > 0),
+        }
+    }
+}
+
+// ================================
+// RATE LIMITER
+// ================================
+
+/// Registered as `Arc<RateLimiter>` Tauri state and shared by every provider call
+pub struct RateLimiter {
+    // Std mutex: never held across an await, and ticket cleanup runs in `Drop`
+    providers: Mutex<HashMap<String, ProviderLimiter>>,
+    next_ticket: Mutex<u64>,
+    changed: Notify,
+}
+
+/// Proof that a request was admitted; report actual usage through `RateLimiter::record_usage`
+#[derive(Debug, Clone)]
+pub struct RatePermit {
+    pub provider: String,
+    pub estimated_tokens: u32,
+    pub waited: Duration,
+}
+
+/// Removes an abandoned waiter when the acquiring future is dropped
+struct QueueGuard<'a> {
+    limiter: &'a RateLimiter,
+    provider: String,
+    ticket: u64,
+    active: bool,
+}
+
+impl Drop for QueueGuard<'_> {
+    fn drop(&mut self) {
+        if self.active {
+            if let Ok(mut providers) = self.limiter.providers.lock() {
+                if let Some(limiter) = providers.get_mut(&self.provider) {
+                    limiter.remove(self.ticket);
+                }
+            }
+            self.limiter.changed.notify_waiters();
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self {
+            providers: Mutex::new(HashMap::new()),
+            next_ticket: Mutex::new(0),
+            changed: Notify::new(),
+        }
+    }
+
+    fn lock(&self) -> AppResult<std::sync::MutexGuard<'_, HashMap<String, ProviderLimiter>>> {
+        self.providers
+            .lock()
+            .map_err(|_| AppError::internal("RATE_LIMITER_POISONED", "Rate limiter state is poisoned"))
+    }
+
+    /// Replace a provider's limits; its buckets restart full
+    pub fn configure(&self, provider: &str, config: RateLimitConfig) -> AppResult<()> {
+        let mut providers = self.lock()?;
+        let queue = providers.remove(provider).map(|p| p.queue).unwrap_or_default();
+        let mut limiter = ProviderLimiter::new(config);
+        limiter.queue = queue;
+        providers.insert(provider.to_string(), limiter);
+        drop(providers);
+        self.changed.notify_waiters();
+        Ok(())
+    }
+
+    /// Wait until `provider` has capacity for one request of `estimated_tokens`.
+    /// `on_wait` is called with the queue position whenever the request has to wait.
+    pub async fn acquire<F>(
+        &self,
+        provider: &str,
+        default_config: RateLimitConfig,
+        request_id: &str,
+        priority: RequestPriority,
+        estimated_tokens: u32,
+        on_wait: F,
+    ) -> AppResult<RatePermit>
+    where
+        F: Fn(QueueStatus),
+    {
+        let started = Instant::now();
+        let ticket = {
+            let mut next = self
+                .next_ticket
+                .lock()
+                .map_err(|_| AppError::internal("RATE_LIMITER_POISONED", "Rate limiter state is poisoned"))?;
+            *next += 1;
+            *next
+        };
+
+        self.lock()?
+            .entry(provider.to_string())
+            .or_insert_with(|| ProviderLimiter::new(default_config))
+            .enqueue(Waiter {
+                ticket,
+                priority,
+                tokens: estimated_tokens,
+            });
+
+        let mut guard = QueueGuard {
+            limiter: self,
+            provider: provider.to_string(),
+            ticket,
+            active: true,
+        };
+        let mut reported_waiting = false;
+
+        loop {
+            // Register before checking so a wake-up between check and sleep is not lost
+            let notified = self.changed.notified();
+
+            let (position, queue_length, wait) = {
+                let mut providers = self.lock()?;
+                let limiter = providers
+                    .get_mut(provider)
+                    .ok_or_else(|| AppError::internal("RATE_LIMITER_STATE", "Provider limiter disappeared"))?;
+                let now = Instant::now();
+                let position = limiter.position(ticket).unwrap_or(0);
+
+                if position == 0 && limiter.ready_in(estimated_tokens, now).is_zero() {
+                    limiter.requests.take(1.0);
+                    limiter.tokens.take(estimated_tokens as f64);
+                    limiter.remove(ticket);
+                    guard.active = false;
+                    drop(providers);
+                    self.changed.notify_waiters();
+
+                    if reported_waiting {
+                        on_wait(QueueStatus {
+                            request_id: request_id.to_string(),
+                            provider: provider.to_string(),
+                            priority,
+                            position: 0,
+                            queue_length: 0,
+                            estimated_wait_ms: 0,
+                            dispatched: true,
+                        });
+                    }
+                    return Ok(RatePermit {
+                        provider: provider.to_string(),
+                        estimated_tokens,
+                        waited: started.elapsed(),
+                    });
+                }
+
+                let wait = if position == 0 {
+                    limiter.ready_in(estimated_tokens, now)
+                } else {
+                    limiter.estimated_wait(position, now)
+                };
+                (position, limiter.queue.len(), wait)
+            };
+
+            reported_waiting = true;
+            on_wait(QueueStatus {
+                request_id: request_id.to_string(),
+                provider: provider.to_string(),
+                priority,
+                position,
+                queue_length,
+                estimated_wait_ms: wait.as_millis() as u64,
+                dispatched: false,
+            });
+
+            let sleep_for = wait.clamp(Duration::from_millis(10), MAX_POLL_INTERVAL);
+            tokio::select! {
+                _ = tokio::time::sleep(sleep_for) => {}
+                _ = notified => {}
+            }
+        }
+    }
+
+    /// Reconcile the estimate taken at admission with the tokens actually used
+    pub fn record_usage(&self, permit: &RatePermit, actual_tokens: u32) {
+        if let Ok(mut providers) = self.providers.lock() {
+            if let Some(limiter) = providers.get_mut(&permit.provider) {
+                limiter.tokens.adjust(actual_tokens as f64 - permit.estimated_tokens as f64);
+            }
+        }
+    }
+
+    /// Honor a provider's `Retry-After`; nothing is dispatched to it until the deadline
+    pub fn note_retry_after(&self, provider: &str, retry_after: Option<Duration>) {
+        let wait = retry_after.unwrap_or(DEFAULT_RETRY_AFTER);
+        tracing::warn!("⏳ Provider {} is rate limited, backing off for {:?}", provider, wait);
+
+        if let Ok(mut providers) = self.providers.lock() {
+            if let Some(limiter) = providers.get_mut(provider) {
+                let until = Instant::now() + wait;
+                limiter.blocked_until = Some(limiter.blocked_until.map_or(until, |current| current.max(until)));
+            }
+        }
+        self.changed.notify_waiters();
+    }
+
+    /// Apply the rate-limit signals of an HTTP response from `provider`
+    pub fn observe_response(&self, provider: &str, status: u16, headers: &reqwest::header::HeaderMap) {
+        if status == 429 || status == 503 {
+            let retry_after = headers
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+            if status == 429 || retry_after.is_some() {
+                self.note_retry_after(provider, retry_after);
+            }
+        }
+    }
+
+    pub fn status(&self) -> Vec<ProviderRateStatus> {
+        let now = Instant::now();
+        let mut statuses: Vec<ProviderRateStatus> = match self.providers.lock() {
+            Ok(mut providers) => providers
+                .iter_mut()
+                .map(|(provider, limiter)| limiter.status(provider, now))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        statuses.sort_by(|a, b| a.provider.cmp(&b.provider));
+        statuses
+    }
+}
+
+/// `Retry-After` is either delta-seconds or an HTTP date
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return (seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delta.to_std().unwrap_or(Duration::ZERO))
+}
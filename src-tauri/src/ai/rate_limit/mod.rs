@@ -0,0 +1,9 @@
+// Syntari AI IDE - Provider Rate Limiting
+// Token-bucket limits per provider with a priority queue for waiting requests
+
+pub mod types;
+pub mod limiter;
+pub mod commands;
+
+pub use types::{RateLimitConfig, QueueStatus, ProviderRateStatus};
+pub use limiter::{RateLimiter, RatePermit, parse_retry_after};
@@ -0,0 +1,50 @@
+// Syntari AI IDE - Rate Limit Types
+// Limit configuration and queue status reported to the frontend
+
+use serde::{Deserialize, Serialize};
+use crate::ai::types::RequestPriority;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub requests_per_minute: u32,
+    pub tokens_per_minute: u32,
+}
+
+impl RateLimitConfig {
+    /// Conservative defaults matching the entry tiers of each provider family
+    pub fn for_provider_type(provider_type: &str) -> Self {
+        let (requests_per_minute, tokens_per_minute) = match provider_type {
+            "anthropic" => (50, 40_000),
+            "openai" => (60, 90_000),
+            "google" | "gemini" => (60, 120_000),
+            "local" | "ollama" => (600, 1_000_000),
+            _ => (30, 30_000),
+        };
+        Self { requests_per_minute, tokens_per_minute }
+    }
+}
+
+/// Emitted as `ai-queue-status` while a request waits for capacity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueStatus {
+    pub request_id: String,
+    pub provider: String,
+    pub priority: RequestPriority,
+    /// Zero-based; 0 means next in line
+    pub position: usize,
+    pub queue_length: usize,
+    pub estimated_wait_ms: u64,
+    /// True once the request has left the queue
+    pub dispatched: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderRateStatus {
+    pub provider: String,
+    pub config: RateLimitConfig,
+    pub available_requests: u32,
+    pub available_tokens: u32,
+    pub queued: usize,
+    /// Remaining `Retry-After` backoff, if the provider asked us to wait
+    pub blocked_for_ms: Option<u64>,
+}
@@ -0,0 +1,298 @@
+// Syntari AI IDE - Code Chunker
+// Splits source files into chunks along syntactic boundaries
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::ai::semantic::types::CodeChunk;
+
+lazy_static! {
+    // Item openers shared by brace languages, Python and Ruby
+    static ref ITEM_START: Regex = Regex::new(
+        r"^\s*(?:(?:export|pub(?:\([^)]*\))?|public|private|protected|internal|static|async|abstract|final|default|unsafe|override|virtual|sealed|partial)\s+)*(?:fn|func|function\*?|def|class|struct|enum|trait|impl|interface|mod|module|type|record|namespace|macro_rules!)\b\s*(?P<name>[A-Za-z_$][\w$]*)?"
+    ).unwrap();
+
+    // Leading lines that belong to the item below them
+    static ref ITEM_PREFIX: Regex = Regex::new(
+        r"^\s*(?:///|//!|#\[|#!\[|@\w|/\*\*|\*|\*/)"
+    ).unwrap();
+
+    static ref MARKDOWN_HEADING: Regex = Regex::new(r"^#{1,3}\s+(?P<name>.+)$").unwrap();
+}
+
+const DEFAULT_MAX_LINES: usize = 120;
+const DEFAULT_MIN_LINES: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    start: usize,
+    end: usize, // exclusive
+}
+
+/// Syntax-aware chunker; falls back to fixed windows for unstructured text
+pub struct CodeChunker {
+    max_lines: usize,
+    min_lines: usize,
+}
+
+impl Default for CodeChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeChunker {
+    pub fn new() -> Self {
+        Self {
+            max_lines: DEFAULT_MAX_LINES,
+            min_lines: DEFAULT_MIN_LINES,
+        }
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines.max(self.min_lines + 1);
+        self
+    }
+
+    /// Chunk a file's content; `file_path` is recorded verbatim on each chunk
+    pub fn chunk(&self, file_path: &str, language: &str, content: &str) -> Vec<CodeChunk> {
+        let lines: Vec<&str> = content.lines().collect();
+        if lines.is_empty() {
+            return Vec::new();
+        }
+
+        let segments = match language {
+            "python" => self.indent_segments(&lines),
+            "markdown" => self.heading_segments(&lines),
+            "json" | "yaml" | "toml" | "plaintext" => self.window_segments(&lines, 0, lines.len()),
+            _ => self.brace_segments(&lines),
+        };
+
+        self.merge_small(segments)
+            .into_iter()
+            .filter_map(|segment| self.build_chunk(file_path, language, &lines, segment))
+            .collect()
+    }
+
+    // ================================
+    // BOUNDARY DETECTION
+    // ================================
+
+    /// Brace languages: items starting at nesting depth 0; oversized items are split at depth 1
+    fn brace_segments(&self, lines: &[&str]) -> Vec<Segment> {
+        let depths = brace_depths(lines);
+        let segments = self.segments_at_depth(lines, &depths, 0, 0, lines.len());
+
+        let mut result = Vec::new();
+        for segment in segments {
+            if segment.end - segment.start > self.max_lines {
+                let inner = self.segments_at_depth(lines, &depths, 1, segment.start, segment.end);
+                for part in inner {
+                    if part.end - part.start > self.max_lines {
+                        result.extend(self.window_segments(lines, part.start, part.end));
+                    } else {
+                        result.push(part);
+                    }
+                }
+            } else {
+                result.push(segment);
+            }
+        }
+        result
+    }
+
+    fn segments_at_depth(
+        &self,
+        lines: &[&str],
+        depths: &[usize],
+        depth: usize,
+        start: usize,
+        end: usize,
+    ) -> Vec<Segment> {
+        let mut boundaries = vec![start];
+        for i in start..end {
+            if i == start || depths[i] != depth || !ITEM_START.is_match(lines[i]) {
+                continue;
+            }
+            let boundary = attach_prefix(lines, i, start);
+            if boundary > *boundaries.last().unwrap() {
+                boundaries.push(boundary);
+            }
+        }
+        boundaries_to_segments(boundaries, end)
+    }
+
+    /// Python: top-level `def`/`class` (with decorators) at column zero
+    fn indent_segments(&self, lines: &[&str]) -> Vec<Segment> {
+        let mut boundaries = vec![0];
+        for (i, line) in lines.iter().enumerate().skip(1) {
+            let is_top_level = !line.starts_with(' ') && !line.starts_with('\t');
+            if is_top_level && ITEM_START.is_match(line) {
+                let boundary = attach_prefix(lines, i, 0);
+                if boundary > *boundaries.last().unwrap() {
+                    boundaries.push(boundary);
+                }
+            }
+        }
+
+        boundaries_to_segments(boundaries, lines.len())
+            .into_iter()
+            .flat_map(|segment| {
+                if segment.end - segment.start > self.max_lines {
+                    self.window_segments(lines, segment.start, segment.end)
+                } else {
+                    vec![segment]
+                }
+            })
+            .collect()
+    }
+
+    fn heading_segments(&self, lines: &[&str]) -> Vec<Segment> {
+        let mut boundaries = vec![0];
+        for (i, line) in lines.iter().enumerate().skip(1) {
+            if MARKDOWN_HEADING.is_match(line) {
+                boundaries.push(i);
+            }
+        }
+
+        boundaries_to_segments(boundaries, lines.len())
+            .into_iter()
+            .flat_map(|segment| {
+                if segment.end - segment.start > self.max_lines {
+                    self.window_segments(lines, segment.start, segment.end)
+                } else {
+                    vec![segment]
+                }
+            })
+            .collect()
+    }
+
+    /// Fixed windows that prefer to break on blank lines
+    fn window_segments(&self, lines: &[&str], start: usize, end: usize) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        let mut cursor = start;
+
+        while cursor < end {
+            let hard_end = (cursor + self.max_lines).min(end);
+            let soft_floor = cursor + self.max_lines * 3 / 4;
+            let window_end = if hard_end < end {
+                (soft_floor..hard_end)
+                    .rev()
+                    .find(|&i| lines[i].trim().is_empty())
+                    .map(|i| i + 1)
+                    .unwrap_or(hard_end)
+            } else {
+                hard_end
+            };
+            segments.push(Segment { start: cursor, end: window_end });
+            cursor = window_end;
+        }
+
+        segments
+    }
+
+    /// Fold tiny segments (imports, one-line consts) into their neighbour
+    fn merge_small(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        let mut merged: Vec<Segment> = Vec::new();
+        for segment in segments {
+            if let Some(last) = merged.last_mut() {
+                let last_len = last.end - last.start;
+                let combined = segment.end - last.start;
+                let is_small = last_len < self.min_lines || segment.end - segment.start < self.min_lines;
+                if is_small && combined <= self.max_lines && last.end == segment.start {
+                    last.end = segment.end;
+                    continue;
+                }
+            }
+            merged.push(segment);
+        }
+        merged
+    }
+
+    fn build_chunk(&self, file_path: &str, language: &str, lines: &[&str], segment: Segment) -> Option<CodeChunk> {
+        let body = &lines[segment.start..segment.end];
+        if body.iter().all(|line| line.trim().is_empty()) {
+            return None;
+        }
+
+        let symbol = body.iter().find_map(|line| {
+            ITEM_START
+                .captures(line)
+                .or_else(|| MARKDOWN_HEADING.captures(line))
+                .and_then(|caps| caps.name("name"))
+                .map(|name| name.as_str().trim().to_string())
+        });
+
+        let start_line = (segment.start + 1) as u32;
+        let end_line = segment.end as u32;
+
+        Some(CodeChunk {
+            id: format!("{}#{}-{}", file_path, start_line, end_line),
+            file_path: file_path.to_string(),
+            language: language.to_string(),
+            start_line,
+            end_line,
+            symbol,
+            content: body.join("\n"),
+        })
+    }
+}
+
+// ================================
+// HELPERS
+// ================================
+
+/// Nesting depth at the start of each line, ignoring braces in line comments and string literals
+fn brace_depths(lines: &[&str]) -> Vec<usize> {
+    let mut depths = Vec::with_capacity(lines.len());
+    let mut depth: i64 = 0;
+
+    for line in lines {
+        depths.push(depth.max(0) as usize);
+
+        let mut in_string: Option<char> = None;
+        let mut escaped = false;
+        let mut prev = '\0';
+        for ch in line.chars() {
+            if let Some(quote) = in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == quote {
+                    in_string = None;
+                }
+                continue;
+            }
+            match ch {
+                '/' if prev == '/' => break,
+                '"' | '`' => in_string = Some(ch),
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            prev = ch;
+        }
+    }
+
+    depths
+}
+
+/// Walk upwards over doc comments, attributes and decorators that belong to the item at `index`
+fn attach_prefix(lines: &[&str], index: usize, floor: usize) -> usize {
+    let mut boundary = index;
+    while boundary > floor && ITEM_PREFIX.is_match(lines[boundary - 1]) {
+        boundary -= 1;
+    }
+    boundary
+}
+
+fn boundaries_to_segments(boundaries: Vec<usize>, end: usize) -> Vec<Segment> {
+    let mut segments = Vec::with_capacity(boundaries.len());
+    for (i, &start) in boundaries.iter().enumerate() {
+        let segment_end = boundaries.get(i + 1).copied().unwrap_or(end);
+        if segment_end > start {
+            segments.push(Segment { start, end: segment_end });
+        }
+    }
+    segments
+}
@@ -0,0 +1,68 @@
+// Syntari AI IDE - Semantic Index Commands
+// Semantic code search commands exposed to the frontend
+
+use tauri::State;
+use crate::core::{AppState, TauriResult, app_data_subdir};
+use crate::ai::semantic::service::SemanticIndexManager;
+use crate::ai::semantic::types::{EmbedderConfig, SemanticSearchHit, SemanticIndexStats};
+
+pub(crate) const INDEX_DIR: &str = "semantic-index";
+const DEFAULT_RESULT_COUNT: usize = 10;
+
+#[tauri::command]
+pub async fn build_semantic_index(
+    project_path: Option<String>,
+    embedder: Option<EmbedderConfig>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    manager: State<'_, SemanticIndexManager>,
+) -> std::result::Result<TauriResult<SemanticIndexStats>, String> {
+    let result = async {
+        let root = state.resolve_project_root(project_path).await?;
+        let index_root = app_data_subdir(&app_handle, INDEX_DIR)?;
+        tracing::info!("🧭 Building semantic index for {}", root);
+        manager.build(&root, &index_root, embedder).await
+    }.await;
+
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn semantic_search(
+    query: String,
+    k: Option<usize>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    manager: State<'_, SemanticIndexManager>,
+) -> std::result::Result<TauriResult<Vec<SemanticSearchHit>>, String> {
+    let result = async {
+        let root = state.resolve_project_root(None).await?;
+        let index_root = app_data_subdir(&app_handle, INDEX_DIR)?;
+        manager.search(&root, &index_root, &query, k.unwrap_or(DEFAULT_RESULT_COUNT)).await
+    }.await;
+
+    match &result {
+        Ok(hits) => tracing::debug!("Semantic search returned {} hits", hits.len()),
+        Err(e) => e.log_error(),
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn get_semantic_index_stats(
+    project_path: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    manager: State<'_, SemanticIndexManager>,
+) -> std::result::Result<TauriResult<SemanticIndexStats>, String> {
+    let result = async {
+        let root = state.resolve_project_root(project_path).await?;
+        let index_root = app_data_subdir(&app_handle, INDEX_DIR)?;
+        manager.stats(&root, &index_root).await
+    }.await;
+
+    Ok(TauriResult::from_result(result))
+}
@@ -0,0 +1,322 @@
+// Syntari AI IDE - Embedders
+// Pluggable text embedders: offline feature hashing or a provider endpoint
+
+use std::sync::Arc;
+use crate::core::{AppError, AppResult};
+use crate::ai::commands::estimate_tokens;
+use crate::ai::rate_limit::{RateLimiter, RateLimitConfig};
+use crate::ai::semantic::types::EmbedderConfig;
+use crate::ai::types::RequestPriority;
+
+/// Turns text into fixed-size, L2-normalised vectors
+#[async_trait::async_trait]
+pub trait Embedder: Send + Sync {
+    fn id(&self) -> String;
+
+    fn dimensions(&self) -> usize;
+
+    /// `priority` orders provider requests behind the shared rate limiter
+    async fn embed(&self, texts: &[String], priority: RequestPriority) -> AppResult<Vec<Vec<f32>>>;
+}
+
+/// Build the embedder described by a configuration; provider requests go through `limiter`
+pub fn build_embedder(config: &EmbedderConfig, limiter: Arc<RateLimiter>) -> AppResult<Arc<dyn Embedder>> {
+    match config {
+        EmbedderConfig::Hashing { dimensions } => {
+            if *dimensions == 0 {
+                return Err(AppError::validation_with_field(
+                    "INVALID_DIMENSIONS",
+                    "Embedding dimensions must be greater than zero",
+                    "dimensions",
+                ));
+            }
+            Ok(Arc::new(HashingEmbedder::new(*dimensions)))
+        }
+        EmbedderConfig::Provider { endpoint, model, api_key_env, dimensions } => {
+            let api_key = match api_key_env {
+                Some(var) => Some(std::env::var(var).map_err(|_| AppError::Config {
+                    code: "EMBEDDING_API_KEY_MISSING".to_string(),
+                    message: format!("Environment variable {} is not set", var),
+                    key: Some(var.clone()),
+                })?),
+                None => None,
+            };
+            Ok(Arc::new(ProviderEmbedder::new(endpoint.clone(), model.clone(), api_key, *dimensions, limiter)))
+        }
+    }
+}
+
+// ================================
+// OFFLINE HASHING EMBEDDER
+// ================================
+
+/// Feature-hashing bag of identifiers, identifier parts and character trigrams.
+/// No model download and deterministic, so indexes stay valid across runs.
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+
+    pub fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+
+        for word in text.split(|c: char| !c.is_alphanumeric() && c != '_') {
+            if word.is_empty() || word.len() > 64 {
+                continue;
+            }
+            let lowered = word.to_lowercase();
+            self.add_feature(&mut vector, &lowered, 1.0);
+
+            let parts = split_identifier(word);
+            if parts.len() > 1 {
+                for part in &parts {
+                    self.add_feature(&mut vector, part, 0.8);
+                }
+            }
+
+            for part in &parts {
+                let chars: Vec<char> = format!("^{}$", part).chars().collect();
+                for window in chars.windows(3) {
+                    let trigram: String = window.iter().collect();
+                    self.add_feature(&mut vector, &trigram, 0.25);
+                }
+            }
+        }
+
+        // Dampen very frequent features before normalising
+        for value in vector.iter_mut() {
+            *value = value.signum() * value.abs().sqrt();
+        }
+        normalize(&mut vector);
+        vector
+    }
+
+    fn add_feature(&self, vector: &mut [f32], feature: &str, weight: f32) {
+        let hash = fnv1a(feature.as_bytes());
+        let index = (hash % self.dimensions as u64) as usize;
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[index] += sign * weight;
+    }
+}
+
+#[async_trait::async_trait]
+impl Embedder for HashingEmbedder {
+    fn id(&self) -> String {
+        EmbedderConfig::Hashing { dimensions: self.dimensions }.id()
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    async fn embed(&self, texts: &[String], _priority: RequestPriority) -> AppResult<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_one(text)).collect())
+    }
+}
+
+// ================================
+// PROVIDER EMBEDDER
+// ================================
+
+/// Client for an OpenAI-compatible embeddings endpoint
+pub struct ProviderEmbedder {
+    client: reqwest::Client,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+    dimensions: Option<usize>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl ProviderEmbedder {
+    pub fn new(
+        endpoint: String,
+        model: String,
+        api_key: Option<String>,
+        dimensions: Option<usize>,
+        limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint,
+            model,
+            api_key,
+            dimensions,
+            limiter,
+        }
+    }
+
+    /// Limits are tracked per endpoint, starting from the defaults of the provider family it belongs to
+    fn default_rate_limit(&self) -> RateLimitConfig {
+        let host = reqwest::Url::parse(&self.endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let provider_type = if host.ends_with("openai.com") {
+            "openai"
+        } else if host.ends_with("googleapis.com") {
+            "google"
+        } else if host == "localhost" || host == "127.0.0.1" || host == "::1" {
+            "local"
+        } else {
+            "unknown"
+        };
+        RateLimitConfig::for_provider_type(provider_type)
+    }
+
+    fn network_error(&self, code: &str, message: String, status: Option<u16>) -> AppError {
+        AppError::Network {
+            code: code.to_string(),
+            message,
+            url: Some(self.endpoint.clone()),
+            status,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Embedder for ProviderEmbedder {
+    fn id(&self) -> String {
+        EmbedderConfig::Provider {
+            endpoint: self.endpoint.clone(),
+            model: self.model.clone(),
+            api_key_env: None,
+            dimensions: self.dimensions,
+        }.id()
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions.unwrap_or(0)
+    }
+
+    async fn embed(&self, texts: &[String], priority: RequestPriority) -> AppResult<Vec<Vec<f32>>> {
+        let estimated_tokens: u32 = texts.iter().map(|text| estimate_tokens(text)).sum();
+        let permit = self.limiter.acquire(
+            &self.endpoint,
+            self.default_rate_limit(),
+            "semantic-embedding",
+            priority,
+            estimated_tokens,
+            |status| tracing::debug!("Embedding request queued at position {} for {}", status.position, status.provider),
+        ).await?;
+
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "input": texts,
+        });
+        if let Some(dimensions) = self.dimensions {
+            body["dimensions"] = serde_json::json!(dimensions);
+        }
+
+        let mut request = self.client.post(&self.endpoint).json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| self.network_error("EMBEDDING_REQUEST_FAILED", e.to_string(), None))?;
+
+        let status = response.status();
+        self.limiter.observe_response(&self.endpoint, status.as_u16(), response.headers());
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(self.network_error(
+                "EMBEDDING_HTTP_ERROR",
+                format!("Embedding endpoint returned {}: {}", status, text),
+                Some(status.as_u16()),
+            ));
+        }
+
+        let payload: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| self.network_error("EMBEDDING_DECODE_FAILED", e.to_string(), None))?;
+
+        if let Some(used) = payload.pointer("/usage/total_tokens").and_then(|t| t.as_u64()) {
+            self.limiter.record_usage(&permit, used as u32);
+        }
+
+        let data = payload.get("data").and_then(|d| d.as_array()).ok_or_else(|| {
+            self.network_error("EMBEDDING_DECODE_FAILED", "Response has no `data` array".to_string(), None)
+        })?;
+
+        let mut vectors = vec![Vec::new(); texts.len()];
+        for (position, item) in data.iter().enumerate() {
+            let index = item.get("index").and_then(|i| i.as_u64()).map(|i| i as usize).unwrap_or(position);
+            let mut vector: Vec<f32> = item
+                .get("embedding")
+                .and_then(|e| e.as_array())
+                .map(|values| values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+                .unwrap_or_default();
+            normalize(&mut vector);
+            if index < vectors.len() {
+                vectors[index] = vector;
+            }
+        }
+
+        if vectors.iter().any(|v| v.is_empty()) {
+            return Err(self.network_error(
+                "EMBEDDING_DECODE_FAILED",
+                "Embedding response is missing vectors".to_string(),
+                None,
+            ));
+        }
+
+        Ok(vectors)
+    }
+}
+
+// ================================
+// VECTOR HELPERS
+// ================================
+
+pub fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for value in vector.iter_mut() {
+            *value /= norm;
+        }
+    }
+}
+
+/// Cosine similarity of two normalised vectors
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Split `parseHTTPResponse` / `parse_http_response` into lowercase parts
+fn split_identifier(word: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    for piece in word.split('_').filter(|p| !p.is_empty()) {
+        let chars: Vec<char> = piece.chars().collect();
+        let mut current = String::new();
+        for (i, &ch) in chars.iter().enumerate() {
+            let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            let prev_is_lower = i > 0 && chars[i - 1].is_lowercase();
+            if ch.is_uppercase() && !current.is_empty() && (prev_is_lower || next_is_lower) {
+                parts.push(current.to_lowercase());
+                current.clear();
+            }
+            current.push(ch);
+        }
+        if !current.is_empty() {
+            parts.push(current.to_lowercase());
+        }
+    }
+    parts
+}
@@ -0,0 +1,13 @@
+// Syntari AI IDE - Semantic Code Index
+// Local embedding index over syntactic code chunks for retrieval
+
+pub mod types;
+pub mod chunker;
+pub mod embedder;
+pub mod store;
+pub mod service;
+pub mod commands;
+
+pub use types::{CodeChunk, EmbedderConfig, SemanticSearchHit, SemanticIndexStats};
+pub use embedder::{Embedder, HashingEmbedder, ProviderEmbedder};
+pub use service::SemanticIndexManager;
@@ -0,0 +1,199 @@
+// Syntari AI IDE - Semantic Index Service
+// Owns loaded project indexes and queues watcher changes for incremental updates
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::core::{AppError, AppResult, content_hash};
+use crate::ai::rate_limit::RateLimiter;
+use crate::ai::semantic::embedder::{Embedder, build_embedder};
+use crate::ai::semantic::store::SemanticIndex;
+use crate::ai::semantic::types::{EmbedderConfig, SemanticSearchHit, SemanticIndexStats};
+
+struct LoadedIndex {
+    index: SemanticIndex,
+    embedder: Arc<dyn Embedder>,
+}
+
+/// Registered as Tauri state; the file watcher feeds it through `mark_dirty`
+pub struct SemanticIndexManager {
+    indexes: Mutex<HashMap<String, Arc<Mutex<LoadedIndex>>>>,
+    // Plain std mutexes: read and written from the watcher's debouncer thread
+    pending: std::sync::Mutex<HashSet<PathBuf>>,
+    /// Roots of the loaded indexes; changes elsewhere are not queued
+    roots: std::sync::Mutex<HashSet<PathBuf>>,
+    // Provider embedders share the app's limiter with every other provider call
+    limiter: Arc<RateLimiter>,
+}
+
+impl SemanticIndexManager {
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            indexes: Mutex::new(HashMap::new()),
+            pending: std::sync::Mutex::new(HashSet::new()),
+            roots: std::sync::Mutex::new(HashSet::new()),
+            limiter,
+        }
+    }
+
+    /// Directory holding a project's index under the app's index root
+    pub fn storage_dir_for(index_root: &Path, project_root: &str) -> PathBuf {
+        index_root.join(&content_hash(project_root.as_bytes())[..16])
+    }
+
+    /// Record a changed path; applied lazily before the next query or build
+    pub fn mark_dirty(&self, path: &Path) {
+        let indexed = self
+            .roots
+            .lock()
+            .map(|roots| roots.iter().any(|root| path.starts_with(root)))
+            .unwrap_or(false);
+        if !indexed {
+            return;
+        }
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(path.to_path_buf());
+        }
+    }
+
+    fn take_pending_for(&self, root: &Path) -> Vec<PathBuf> {
+        match self.pending.lock() {
+            Ok(mut pending) => {
+                let matched: Vec<PathBuf> = pending.iter().filter(|p| p.starts_with(root)).cloned().collect();
+                for path in &matched {
+                    pending.remove(path);
+                }
+                matched
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn pending_count_for(&self, root: &Path) -> usize {
+        self.pending
+            .lock()
+            .map(|pending| pending.iter().filter(|p| p.starts_with(root)).count())
+            .unwrap_or(0)
+    }
+
+    /// Build or refresh a project's index; a different embedder forces a full rebuild
+    pub async fn build(
+        &self,
+        project_root: &str,
+        index_root: &Path,
+        embedder_config: Option<EmbedderConfig>,
+    ) -> AppResult<SemanticIndexStats> {
+        let root = PathBuf::from(project_root);
+        if !root.is_dir() {
+            return Err(AppError::project_with_path(
+                "PROJECT_NOT_FOUND",
+                "Project path is not a directory",
+                project_root,
+            ));
+        }
+
+        let entry = self.open(project_root, index_root, embedder_config).await?;
+        let mut loaded = entry.lock().await;
+        let LoadedIndex { index, embedder } = &mut *loaded;
+
+        // A full sync supersedes any queued watcher events for this root
+        self.take_pending_for(&root);
+        let reindexed = index.sync(embedder.as_ref()).await?;
+        index.save()?;
+
+        tracing::info!("Semantic index for {} synced ({} files re-embedded)", project_root, reindexed);
+        Ok(index.stats(0))
+    }
+
+    /// Top-`k` chunks for a natural-language query, applying queued changes first
+    pub async fn search(
+        &self,
+        project_root: &str,
+        index_root: &Path,
+        query: &str,
+        k: usize,
+    ) -> AppResult<Vec<SemanticSearchHit>> {
+        let root = PathBuf::from(project_root);
+        let entry = self.open(project_root, index_root, None).await?;
+        let mut loaded = entry.lock().await;
+        let LoadedIndex { index, embedder } = &mut *loaded;
+
+        let dirty = self.take_pending_for(&root);
+        if !dirty.is_empty() {
+            let reindexed = index.update_paths(&dirty, embedder.as_ref()).await?;
+            index.save()?;
+            tracing::debug!("Applied {} watcher updates ({} files re-embedded)", dirty.len(), reindexed);
+        }
+
+        index.search(query, k, embedder.as_ref()).await
+    }
+
+    pub async fn stats(&self, project_root: &str, index_root: &Path) -> AppResult<SemanticIndexStats> {
+        let entry = self.open(project_root, index_root, None).await?;
+        let loaded = entry.lock().await;
+        Ok(loaded.index.stats(self.pending_count_for(Path::new(project_root))))
+    }
+
+    /// Return the in-memory index, loading it from disk or building it on first use.
+    /// The initial sync holds only the new entry's lock, so other projects stay usable.
+    async fn open(
+        &self,
+        project_root: &str,
+        index_root: &Path,
+        embedder_config: Option<EmbedderConfig>,
+    ) -> AppResult<Arc<Mutex<LoadedIndex>>> {
+        let existing = self.indexes.lock().await.get(project_root).cloned();
+        if let Some(existing) = existing {
+            let same_embedder = match &embedder_config {
+                Some(config) => existing.lock().await.index.embedder_config() == config,
+                None => true,
+            };
+            if same_embedder {
+                return Ok(existing);
+            }
+        }
+
+        let root = PathBuf::from(project_root);
+        let storage_dir = Self::storage_dir_for(index_root, project_root);
+        // Without an explicit choice, reopen with the embedder the index was built with
+        let config = embedder_config
+            .or_else(|| SemanticIndex::stored_embedder_config(&storage_dir))
+            .unwrap_or_default();
+        let embedder = build_embedder(&config, self.limiter.clone())?;
+
+        let (index, needs_sync) = match SemanticIndex::load(root.clone(), storage_dir.clone(), embedder.as_ref()) {
+            Some(index) => (index, false),
+            None => (SemanticIndex::new(root, storage_dir, config, embedder.as_ref()), true),
+        };
+
+        let entry = Arc::new(Mutex::new(LoadedIndex { index, embedder }));
+        // Lock the entry before publishing it so callers wait for the initial sync
+        let mut loaded = entry.lock().await;
+        self.indexes.lock().await.insert(project_root.to_string(), entry.clone());
+        if let Ok(mut roots) = self.roots.lock() {
+            roots.insert(PathBuf::from(project_root));
+        }
+
+        if needs_sync {
+            let LoadedIndex { index, embedder } = &mut *loaded;
+            let synced = match index.sync(embedder.as_ref()).await {
+                Ok(_) => index.save(),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = synced {
+                let mut indexes = self.indexes.lock().await;
+                if indexes.get(project_root).is_some_and(|current| Arc::ptr_eq(current, &entry)) {
+                    indexes.remove(project_root);
+                    if let Ok(mut roots) = self.roots.lock() {
+                        roots.remove(Path::new(project_root));
+                    }
+                }
+                return Err(e);
+            }
+        }
+
+        drop(loaded);
+        Ok(entry)
+    }
+}
@@ -0,0 +1,450 @@
+// Syntari AI IDE - Semantic Index Store
+// Per-project chunk and vector storage with incremental updates
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use ignore::WalkBuilder;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use crate::core::{
+    AppError, AppResult, current_timestamp, content_hash, write_file_atomic,
+    detect_language_from_extension, get_last_modified_timestamp,
+};
+use crate::ai::semantic::chunker::CodeChunker;
+use crate::ai::semantic::embedder::{Embedder, cosine};
+use crate::ai::semantic::types::{CodeChunk, EmbedderConfig, SemanticSearchHit, SemanticIndexStats};
+use crate::ai::types::RequestPriority;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const VECTORS_FILE: &str = "vectors.bin";
+const MANIFEST_VERSION: u32 = 2;
+const MAX_INDEXED_FILE_SIZE: u64 = 512 * 1024;
+const EMBED_BATCH_SIZE: usize = 32;
+
+/// Languages worth embedding; everything else detected as plaintext is skipped
+const SKIPPED_LANGUAGES: &[&str] = &["plaintext", "json"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    content_hash: String,
+    last_modified: u64,
+    chunk_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexManifest {
+    version: u32,
+    root_path: String,
+    embedder: EmbedderConfig,
+    embedder_id: String,
+    dimensions: usize,
+    updated_at: u64,
+    files: HashMap<String, IndexedFile>,
+    /// Chunk order matches the row order of `vectors.bin`
+    chunks: Vec<CodeChunk>,
+    /// Hash of the `vectors.bin` written alongside this manifest
+    vectors_hash: String,
+}
+
+/// In-memory view of one project's semantic index
+pub struct SemanticIndex {
+    root: PathBuf,
+    storage_dir: PathBuf,
+    embedder_config: EmbedderConfig,
+    embedder_id: String,
+    dimensions: usize,
+    updated_at: u64,
+    files: HashMap<String, IndexedFile>,
+    chunks: HashMap<String, (CodeChunk, Vec<f32>)>,
+    chunker: CodeChunker,
+}
+
+impl SemanticIndex {
+    pub fn new(root: PathBuf, storage_dir: PathBuf, embedder_config: EmbedderConfig, embedder: &dyn Embedder) -> Self {
+        Self {
+            root,
+            storage_dir,
+            embedder_config,
+            embedder_id: embedder.id(),
+            dimensions: embedder.dimensions(),
+            updated_at: 0,
+            files: HashMap::new(),
+            chunks: HashMap::new(),
+            chunker: CodeChunker::new(),
+        }
+    }
+
+    /// Embedder a stored index was built with, read without loading its chunks
+    pub fn stored_embedder_config(storage_dir: &Path) -> Option<EmbedderConfig> {
+        #[derive(Deserialize)]
+        struct ManifestHeader {
+            embedder: EmbedderConfig,
+        }
+        let bytes = std::fs::read(storage_dir.join(MANIFEST_FILE)).ok()?;
+        serde_json::from_slice::<ManifestHeader>(&bytes).ok().map(|header| header.embedder)
+    }
+
+    /// Load a stored index; returns `None` when absent, corrupt, or built by a different embedder
+    pub fn load(root: PathBuf, storage_dir: PathBuf, embedder: &dyn Embedder) -> Option<Self> {
+        let manifest_bytes = std::fs::read(storage_dir.join(MANIFEST_FILE)).ok()?;
+        let manifest: IndexManifest = serde_json::from_slice(&manifest_bytes).ok()?;
+        if manifest.version != MANIFEST_VERSION || manifest.embedder_id != embedder.id() {
+            tracing::info!("Discarding semantic index for {} (embedder or format changed)", root.display());
+            return None;
+        }
+
+        let vector_bytes = std::fs::read(storage_dir.join(VECTORS_FILE)).ok()?;
+        let dimensions = manifest.dimensions;
+        if dimensions == 0
+            || vector_bytes.len() != manifest.chunks.len() * dimensions * 4
+            || content_hash(&vector_bytes) != manifest.vectors_hash
+        {
+            tracing::warn!("Semantic index vectors for {} do not match manifest, rebuilding", root.display());
+            return None;
+        }
+
+        let mut chunks = HashMap::with_capacity(manifest.chunks.len());
+        for (row, chunk) in manifest.chunks.into_iter().enumerate() {
+            let offset = row * dimensions * 4;
+            let vector = vector_bytes[offset..offset + dimensions * 4]
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+            chunks.insert(chunk.id.clone(), (chunk, vector));
+        }
+
+        Some(Self {
+            root,
+            storage_dir,
+            embedder_config: manifest.embedder,
+            embedder_id: manifest.embedder_id,
+            dimensions,
+            updated_at: manifest.updated_at,
+            files: manifest.files,
+            chunks,
+            chunker: CodeChunker::new(),
+        })
+    }
+
+    /// Persist manifest and vectors; the manifest records the vectors' hash so a crash between the writes is rejected by `load`
+    pub fn save(&self) -> AppResult<()> {
+        let mut ordered: Vec<&(CodeChunk, Vec<f32>)> = self.chunks.values().collect();
+        ordered.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+
+        let mut vector_bytes = Vec::with_capacity(ordered.len() * self.dimensions * 4);
+        for (_, vector) in &ordered {
+            for value in vector {
+                vector_bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        let manifest = IndexManifest {
+            version: MANIFEST_VERSION,
+            root_path: self.root.to_string_lossy().to_string(),
+            embedder: self.embedder_config.clone(),
+            embedder_id: self.embedder_id.clone(),
+            dimensions: self.dimensions,
+            updated_at: self.updated_at,
+            files: self.files.clone(),
+            chunks: ordered.iter().map(|(chunk, _)| chunk.clone()).collect(),
+            vectors_hash: content_hash(&vector_bytes),
+        };
+
+        write_file_atomic(&self.storage_dir.join(VECTORS_FILE), &vector_bytes)?;
+        write_file_atomic(&self.storage_dir.join(MANIFEST_FILE), &serde_json::to_vec(&manifest)?)?;
+        Ok(())
+    }
+
+    pub fn embedder_config(&self) -> &EmbedderConfig {
+        &self.embedder_config
+    }
+
+    // ================================
+    // INDEXING
+    // ================================
+
+    /// Walk the project (respecting ignore files) and re-embed only files whose content changed
+    pub async fn sync(&mut self, embedder: &dyn Embedder) -> AppResult<usize> {
+        let mut seen = HashSet::new();
+        let mut changed = Vec::new();
+
+        let walker = WalkBuilder::new(&self.root)
+            .hidden(true)
+            .git_ignore(true)
+            .git_exclude(true)
+            .require_git(false)
+            .follow_links(false)
+            .build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let relative = self.relative_path(path);
+            seen.insert(relative.clone());
+
+            if let Some(file) = self.files.get(&relative) {
+                let unchanged = path
+                    .metadata()
+                    .map(|m| get_last_modified_timestamp(&m) == file.last_modified)
+                    .unwrap_or(false);
+                if unchanged {
+                    continue;
+                }
+            }
+            changed.push(path.to_path_buf());
+        }
+
+        let removed: Vec<String> = self.files.keys().filter(|k| !seen.contains(*k)).cloned().collect();
+        for relative in removed {
+            self.remove_file(&relative);
+        }
+
+        self.reindex_paths(&changed, embedder).await
+    }
+
+    /// Re-index specific paths (watcher events); deleted, ignored or unindexable paths are dropped
+    pub async fn update_paths(&mut self, paths: &[PathBuf], embedder: &dyn Embedder) -> AppResult<usize> {
+        let mut rules = IgnoreRules::new(&self.root);
+        let mut existing = Vec::new();
+        for path in paths {
+            if path.is_file() {
+                // The same rules `sync` walks with, so builds and installs never re-embed ignored files
+                if !rules.is_ignored(path) {
+                    existing.push(path.clone());
+                }
+            } else {
+                let relative = self.relative_path(path);
+                // A deleted directory takes every file beneath it
+                let prefix = format!("{}/", relative);
+                let doomed: Vec<String> = self.files
+                    .keys()
+                    .filter(|k| **k == relative || k.starts_with(&prefix))
+                    .cloned()
+                    .collect();
+                for key in doomed {
+                    self.remove_file(&key);
+                }
+            }
+        }
+        self.reindex_paths(&existing, embedder).await
+    }
+
+    async fn reindex_paths(&mut self, paths: &[PathBuf], embedder: &dyn Embedder) -> AppResult<usize> {
+        let mut pending: Vec<CodeChunk> = Vec::new();
+        // Files are only recorded once all their vectors are stored, so a failed embed is retried by the next sync
+        let mut awaiting: Vec<(String, IndexedFile)> = Vec::new();
+        let mut reindexed = 0;
+
+        for path in paths {
+            let relative = self.relative_path(path);
+            let Some((content, last_modified)) = read_indexable(path) else {
+                self.remove_file(&relative);
+                continue;
+            };
+
+            let hash = content_hash(content.as_bytes());
+            if let Some(file) = self.files.get_mut(&relative) {
+                if file.content_hash == hash {
+                    file.last_modified = last_modified;
+                    continue;
+                }
+            }
+
+            self.remove_file(&relative);
+            let language = detect_language_from_extension(path);
+            let chunks = self.chunker.chunk(&relative, &language, &content);
+            awaiting.push((relative, IndexedFile {
+                content_hash: hash,
+                last_modified,
+                chunk_ids: chunks.iter().map(|c| c.id.clone()).collect(),
+            }));
+            pending.extend(chunks);
+            reindexed += 1;
+        }
+
+        let mut recorded = 0;
+        for batch in pending.chunks(EMBED_BATCH_SIZE) {
+            if let Err(e) = self.embed_batch(batch, embedder).await {
+                // Drop the vectors of files that were only partly embedded
+                for (_, file) in &awaiting[recorded..] {
+                    for id in &file.chunk_ids {
+                        self.chunks.remove(id);
+                    }
+                }
+                return Err(e);
+            }
+            recorded += self.record_embedded(&awaiting[recorded..]);
+        }
+        self.record_embedded(&awaiting[recorded..]);
+
+        if reindexed > 0 || !pending.is_empty() {
+            self.updated_at = current_timestamp();
+        }
+        Ok(reindexed)
+    }
+
+    async fn embed_batch(&mut self, batch: &[CodeChunk], embedder: &dyn Embedder) -> AppResult<()> {
+        let texts: Vec<String> = batch.iter().map(|c| c.embedding_text()).collect();
+        let vectors = embedder.embed(&texts, RequestPriority::Background).await?;
+        if vectors.len() != batch.len() {
+            return Err(AppError::ai("EMBEDDING_COUNT_MISMATCH", "Embedder returned the wrong number of vectors"));
+        }
+        for (chunk, vector) in batch.iter().cloned().zip(vectors) {
+            if self.dimensions == 0 {
+                // Provider embedders learn their dimensionality from the first response
+                self.dimensions = vector.len();
+            }
+            if vector.len() != self.dimensions {
+                return Err(AppError::ai("EMBEDDING_DIMENSION_MISMATCH", "Embedder returned vectors of inconsistent size"));
+            }
+            self.chunks.insert(chunk.id.clone(), (chunk, vector));
+        }
+        Ok(())
+    }
+
+    /// Record the leading files whose chunks all have vectors; returns how many were recorded
+    fn record_embedded(&mut self, awaiting: &[(String, IndexedFile)]) -> usize {
+        let complete = awaiting
+            .iter()
+            .take_while(|(_, file)| file.chunk_ids.iter().all(|id| self.chunks.contains_key(id)))
+            .count();
+        for (relative, file) in &awaiting[..complete] {
+            self.files.insert(relative.clone(), file.clone());
+        }
+        complete
+    }
+
+    fn remove_file(&mut self, relative: &str) {
+        if let Some(file) = self.files.remove(relative) {
+            for id in file.chunk_ids {
+                self.chunks.remove(&id);
+            }
+            self.updated_at = current_timestamp();
+        }
+    }
+
+    fn relative_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    // ================================
+    // QUERYING
+    // ================================
+
+    pub async fn search(&self, query: &str, k: usize, embedder: &dyn Embedder) -> AppResult<Vec<SemanticSearchHit>> {
+        if query.trim().is_empty() || k == 0 || self.chunks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_vector = embedder
+            .embed(&[query.to_string()], RequestPriority::Interactive)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        let mut hits: Vec<SemanticSearchHit> = self.chunks
+            .values()
+            .map(|(chunk, vector)| SemanticSearchHit {
+                chunk: chunk.clone(),
+                score: cosine(&query_vector, vector),
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        hits.truncate(k);
+        Ok(hits)
+    }
+
+    pub fn stats(&self, pending_updates: usize) -> SemanticIndexStats {
+        SemanticIndexStats {
+            root_path: self.root.to_string_lossy().to_string(),
+            embedder: self.embedder_id.clone(),
+            dimensions: self.dimensions,
+            file_count: self.files.len(),
+            chunk_count: self.chunks.len(),
+            pending_updates,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+/// The hidden-file and ignore-file rules of the `sync` walk, applied to single paths
+struct IgnoreRules {
+    root: PathBuf,
+    matchers: HashMap<PathBuf, Gitignore>,
+}
+
+impl IgnoreRules {
+    fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            matchers: HashMap::new(),
+        }
+    }
+
+    /// `.gitignore` and `.ignore` of one directory, plus `.git/info/exclude` at the root
+    fn matcher(&mut self, dir: &Path) -> &Gitignore {
+        let is_root = dir == self.root;
+        self.matchers.entry(dir.to_path_buf()).or_insert_with(|| {
+            let mut builder = GitignoreBuilder::new(dir);
+            let mut files = vec![dir.join(".gitignore"), dir.join(".ignore")];
+            if is_root {
+                files.insert(0, dir.join(".git").join("info").join("exclude"));
+            }
+            for file in files.iter().filter(|file| file.is_file()) {
+                if let Some(e) = builder.add(file) {
+                    tracing::debug!("Skipping ignore rules in {}: {}", file.display(), e);
+                }
+            }
+            builder.build().unwrap_or_else(|_| Gitignore::empty())
+        })
+    }
+
+    fn is_ignored(&mut self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return true;
+        };
+        if relative.components().any(|c| c.as_os_str().to_string_lossy().starts_with('.')) {
+            return true;
+        }
+
+        // Deeper ignore files take precedence, as in the walk
+        let is_dir = path.is_dir();
+        let dirs: Vec<PathBuf> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.root))
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in dirs {
+            let matched = self.matcher(&dir).matched_path_or_any_parents(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+/// Read a file if it is a reasonably sized text source file
+fn read_indexable(path: &Path) -> Option<(String, u64)> {
+    let metadata = path.metadata().ok()?;
+    if metadata.len() == 0 || metadata.len() > MAX_INDEXED_FILE_SIZE {
+        return None;
+    }
+    let language = detect_language_from_extension(path);
+    if SKIPPED_LANGUAGES.contains(&language.as_str()) {
+        return None;
+    }
+    let content = std::fs::read_to_string(path).ok()?;
+    Some((content, get_last_modified_timestamp(&metadata)))
+}
@@ -0,0 +1,86 @@
+// Syntari AI IDE - Semantic Index Types
+// Data structures for code chunks, embedder configuration and search results
+
+use serde::{Deserialize, Serialize};
+
+// ================================
+// CODE CHUNK TYPES
+// ================================
+
+/// A syntactically bounded slice of a source file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeChunk {
+    pub id: String,
+    pub file_path: String,
+    pub language: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub symbol: Option<String>,
+    pub content: String,
+}
+
+impl CodeChunk {
+    /// Text fed to the embedder: path and symbol give short queries something to match
+    pub fn embedding_text(&self) -> String {
+        match &self.symbol {
+            Some(symbol) => format!("{}\n{}\n{}", self.file_path, symbol, self.content),
+            None => format!("{}\n{}", self.file_path, self.content),
+        }
+    }
+}
+
+// ================================
+// EMBEDDER CONFIGURATION
+// ================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EmbedderConfig {
+    /// Built-in offline feature-hashing model
+    Hashing { dimensions: usize },
+    /// OpenAI-compatible `/embeddings` endpoint
+    Provider {
+        endpoint: String,
+        model: String,
+        /// Name of the environment variable holding the API key (never the key itself)
+        api_key_env: Option<String>,
+        dimensions: Option<usize>,
+    },
+}
+
+impl Default for EmbedderConfig {
+    fn default() -> Self {
+        Self::Hashing { dimensions: 512 }
+    }
+}
+
+impl EmbedderConfig {
+    /// Stable identifier recorded with stored vectors; a change forces a rebuild
+    pub fn id(&self) -> String {
+        match self {
+            Self::Hashing { dimensions } => format!("hashing-{}", dimensions),
+            Self::Provider { endpoint, model, .. } => format!("provider-{}-{}", endpoint, model),
+        }
+    }
+}
+
+// ================================
+// SEARCH RESULT TYPES
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticSearchHit {
+    pub chunk: CodeChunk,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticIndexStats {
+    pub root_path: String,
+    pub embedder: String,
+    pub dimensions: usize,
+    pub file_count: usize,
+    pub chunk_count: usize,
+    pub pending_updates: usize,
+    pub updated_at: u64,
+}
@@ -3,6 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use crate::project::types::ProjectContext;
+use crate::chat::types::ImagePart;
 
 // ================================
 // AI PROVIDER TYPES
@@ -50,12 +51,27 @@ impl AiProvider {
         self.specialties = specialties;
         self
     }
+    
+    /// Providers advertising the `vision` specialty accept image input natively
+    pub fn supports_vision(&self) -> bool {
+        self.specialties.iter().any(|s| s == "vision")
+    }
 }
 
 // ================================
 // AI REQUEST TYPES
 // ================================
 
+/// Scheduling priority when providers are rate limited; higher runs first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestPriority {
+    Background,
+    #[default]
+    Normal,
+    Interactive,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiRequest {
     pub id: String,
@@ -65,6 +81,10 @@ pub struct AiRequest {
     pub max_tokens: Option<u32>,
     pub temperature: Option<f32>,
     pub timestamp: u64,
+    #[serde(default)]
+    pub priority: RequestPriority,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImagePart>,
 }
 
 impl AiRequest {
@@ -77,6 +97,8 @@ impl AiRequest {
             max_tokens: None,
             temperature: None,
             timestamp: crate::core::current_timestamp(),
+            priority: RequestPriority::default(),
+            images: Vec::new(),
         }
     }
     
@@ -99,6 +121,16 @@ impl AiRequest {
         self.temperature = Some(temperature);
         self
     }
+    
+    pub fn with_priority(mut self, priority: RequestPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+    
+    pub fn with_images(mut self, images: Vec<ImagePart>) -> Self {
+        self.images = images;
+        self
+    }
 }
 
 // ================================
@@ -0,0 +1,238 @@
+// Syntari AI IDE - Chat File Attachments
+// Captures file ranges at send time and detects when they have since changed
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use crate::core::{AppError, AppResult, current_timestamp};
+use crate::core::file_utils::content_hash;
+use crate::chat::types::{AttachmentKind, ChatContext, ChatSession, FileAttachment};
+
+/// Ranges larger than this keep only their hash, so staleness is flagged without a diff
+pub const MAX_SNAPSHOT_BYTES: usize = 256 * 1024;
+
+// ================================
+// STATUS TYPES
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentState {
+    /// The range still holds the text that was sent
+    Current,
+    /// The same text now lives at other lines
+    Moved,
+    Modified,
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentStatus {
+    pub message_id: String,
+    pub kind: AttachmentKind,
+    pub path: String,
+    pub start_line: Option<u32>,
+    pub end_line: Option<u32>,
+    pub state: AttachmentState,
+    /// Where a moved range is found now
+    pub current_start_line: Option<u32>,
+    pub current_end_line: Option<u32>,
+    /// Unified diff from the sent text to the current text, when a snapshot was kept
+    pub diff: Option<String>,
+}
+
+impl AttachmentStatus {
+    pub fn is_stale(&self) -> bool {
+        matches!(self.state, AttachmentState::Modified | AttachmentState::Missing)
+    }
+}
+
+// ================================
+// CAPTURE
+// ================================
+
+/// Read a file range and record its hash; lines are 1-based and inclusive
+pub fn capture_file(path: &str, start_line: Option<u32>, end_line: Option<u32>) -> AppResult<FileAttachment> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AppError::filesystem_with_path(
+            "ATTACHMENT_READ_FAILED".to_string(),
+            format!("Cannot attach file: {}", e),
+            path.to_string(),
+        )
+    })?;
+    let text = line_range(&content, start_line, end_line);
+    let kind = if start_line.is_some() { AttachmentKind::Selection } else { AttachmentKind::File };
+    Ok(attachment(kind, path, start_line, end_line.or(start_line), text))
+}
+
+/// Turn the editor selection in `context` into an attachment.
+/// The selection is located in the saved file nearest the cursor and its whole lines are captured,
+/// as `check` compares whole lines; when the file cannot be read the raw selection is kept.
+pub fn capture_selection(context: &ChatContext) -> Option<FileAttachment> {
+    let path = context.active_file.as_deref()?;
+    let selection = context.selected_text.as_deref().filter(|s| !s.trim().is_empty())?;
+    let cursor_line = context.cursor_position.as_ref().map(|cursor| cursor.line);
+    let saved = std::fs::read_to_string(path).ok();
+
+    let start_line = saved
+        .as_deref()
+        .and_then(|content| locate(content, selection, cursor_line.unwrap_or(1)))
+        .or(cursor_line)
+        .unwrap_or(1);
+    let end_line = start_line + span(selection) - 1;
+
+    let text = saved
+        .as_deref()
+        .map(|content| line_range(content, Some(start_line), Some(end_line)))
+        .filter(|lines| !lines.is_empty())
+        .unwrap_or(selection);
+    Some(attachment(AttachmentKind::Selection, path, Some(start_line), Some(end_line), text))
+}
+
+fn attachment(kind: AttachmentKind, path: &str, start_line: Option<u32>, end_line: Option<u32>, text: &str) -> FileAttachment {
+    FileAttachment {
+        kind,
+        path: path.to_string(),
+        start_line,
+        end_line,
+        content_hash: content_hash(text.as_bytes()),
+        snapshot: (text.len() <= MAX_SNAPSHOT_BYTES).then(|| text.to_string()),
+        captured_at: current_timestamp(),
+    }
+}
+
+// ================================
+// STALENESS
+// ================================
+
+/// Check every attachment on the active branch against the files on disk
+pub fn check_session(session: &ChatSession) -> Vec<AttachmentStatus> {
+    session
+        .active_path()
+        .into_iter()
+        .flat_map(|message| {
+            message
+                .attachments()
+                .into_iter()
+                .map(move |attachment| check(&message.id, attachment))
+        })
+        .collect()
+}
+
+pub fn check(message_id: &str, attachment: &FileAttachment) -> AttachmentStatus {
+    let mut status = AttachmentStatus {
+        message_id: message_id.to_string(),
+        kind: attachment.kind,
+        path: attachment.path.clone(),
+        start_line: attachment.start_line,
+        end_line: attachment.end_line,
+        state: AttachmentState::Current,
+        current_start_line: attachment.start_line,
+        current_end_line: attachment.end_line,
+        diff: None,
+    };
+
+    let content = match std::fs::read_to_string(&attachment.path) {
+        Ok(content) => content,
+        Err(_) => {
+            status.state = AttachmentState::Missing;
+            status.current_start_line = None;
+            status.current_end_line = None;
+            return status;
+        }
+    };
+
+    let current = line_range(&content, attachment.start_line, attachment.end_line);
+    if content_hash(current.as_bytes()) == attachment.content_hash {
+        return status;
+    }
+
+    // Edits above a range shift it without changing it
+    if let (Some(snapshot), Some(start)) = (&attachment.snapshot, attachment.start_line) {
+        if let Some(moved_to) = locate(&content, snapshot, start) {
+            status.state = AttachmentState::Moved;
+            status.current_start_line = Some(moved_to);
+            status.current_end_line = Some(moved_to + span(snapshot) - 1);
+            return status;
+        }
+    }
+
+    status.state = AttachmentState::Modified;
+    status.diff = attachment.snapshot.as_ref().map(|snapshot| unified_diff(&attachment.location(), snapshot, current));
+    status
+}
+
+/// Prompt note listing attachments that no longer match the files they came from
+pub fn stale_note(statuses: &[AttachmentStatus]) -> Option<String> {
+    let stale: Vec<&AttachmentStatus> = statuses.iter().filter(|status| status.is_stale()).collect();
+    if stale.is_empty() {
+        return None;
+    }
+
+    let mut note = String::from("Note: these files changed after they were shared earlier in the conversation; the code quoted there may be out of date:\n");
+    for status in stale {
+        let state = match status.state {
+            AttachmentState::Missing => "deleted or unreadable",
+            _ => "modified",
+        };
+        note.push_str(&format!("- {} ({})\n", Path::new(&status.path).display(), state));
+    }
+    note.push('\n');
+    Some(note)
+}
+
+fn unified_diff(location: &str, old: &str, new: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("{} (when sent)", location), &format!("{} (now)", location))
+        .to_string()
+}
+
+// ================================
+// LINE HELPERS
+// ================================
+
+/// Text of lines `start..=end` (1-based), keeping line endings; `None` means the whole file
+fn line_range(content: &str, start_line: Option<u32>, end_line: Option<u32>) -> &str {
+    let Some(start) = start_line else {
+        return content;
+    };
+    let end = end_line.unwrap_or(start).max(start);
+
+    let mut offset = 0;
+    let mut range_start = None;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let number = index as u32 + 1;
+        if number == start {
+            range_start = Some(offset);
+        }
+        offset += line.len();
+        if number == end {
+            break;
+        }
+    }
+    match range_start {
+        Some(range_start) => &content[range_start..offset],
+        None => "",
+    }
+}
+
+/// Line where `needle` starts, choosing the occurrence nearest `near_line`.
+/// Only whole-line occurrences count; a snapshot found mid-line is not the shared range.
+fn locate(content: &str, needle: &str, near_line: u32) -> Option<u32> {
+    content
+        .match_indices(needle)
+        .filter(|(offset, _)| *offset == 0 || content.as_bytes()[offset - 1] == b'\n')
+        .filter(|(offset, _)| {
+            let end = offset + needle.len();
+            needle.ends_with('\n') || end == content.len() || content[end..].starts_with(['\r', '\n'])
+        })
+        .map(|(offset, _)| content[..offset].matches('\n').count() as u32 + 1)
+        .min_by_key(|line| line.abs_diff(near_line))
+}
+
+/// Number of lines a piece of text covers
+fn span(text: &str) -> u32 {
+    (text.trim_end_matches('\n').matches('\n').count() as u32) + 1
+}
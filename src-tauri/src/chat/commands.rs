@@ -1,15 +1,23 @@
 // Syntari AI IDE - Chat Commands
 // Chat-related commands exposed to the frontend
 
+use std::collections::BTreeMap;
 use tauri::State;
-use crate::core::{AppState, TauriResult, generate_id};
-use crate::chat::types::{ChatMessage, ChatSession};
+use crate::core::{AppError, AppState, TauriResult};
+use crate::chat::attachments::{self, AttachmentStatus, capture_selection};
+use crate::chat::multimodal::{prepare_parts, parts_to_text};
+use crate::chat::export::{ExportFormat, export_session, import_session};
+use crate::chat::search::{ChatSearchHit, ChatSearchQuery};
+use crate::chat::service::ChatService;
+use crate::chat::store::ChatStore;
+use crate::chat::types::{ChatContext, ChatMessage, ChatSession, ChatSessionSummary, MessagePart, MessagePartInput, ThreadMessage};
 use crate::project::types::ProjectContext;
 
 #[tauri::command]
 pub async fn create_chat_session(
     project_path: String,
     state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
 ) -> std::result::Result<TauriResult<String>, String> {
     tracing::info!("Creating chat session for project: {}", project_path);
     
@@ -22,9 +30,6 @@ pub async fn create_chat_session(
         }
     };
     
-    // Generate a unique session ID
-    let session_id = generate_id();
-    
     // Create the chat session
     let session_name = format!("Chat - {}", 
         std::path::Path::new(&project_path)
@@ -35,54 +40,372 @@ pub async fn create_chat_session(
     
     let chat_session = ChatSession::new(session_name, project_context);
     
-    // Store the session in the application state
-    {
-        let mut sessions = state.chat_sessions.lock().await;
-        sessions.insert(session_id.clone(), chat_session);
+    // Persist the session and cache it in the application state
+    match ChatService::create_session(&state, &store, chat_session).await {
+        Ok(session) => {
+            tracing::info!("Created chat session with ID: {}", session.id);
+            Ok(TauriResult::success(session.id))
+        }
+        Err(e) => {
+            e.log_error();
+            Ok(TauriResult::error(e.to_string()))
+        }
     }
-    
-    tracing::info!("Created chat session with ID: {}", session_id);
-    Ok(TauriResult::success(session_id))
 }
 
 #[tauri::command]
 pub async fn send_chat_message(
     session_id: String,
     content: String,
+    parts: Option<Vec<MessagePartInput>>,
+    context: Option<ChatContext>,
+    app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
 ) -> std::result::Result<TauriResult<ChatMessage>, String> {
     tracing::info!("Sending message to session: {}", session_id);
     
-    let user_message = ChatMessage::user_message(content);
+    let mut attachments = match prepare_parts(parts.unwrap_or_default()) {
+        Ok(attachments) => attachments,
+        Err(e) => {
+            e.log_warn();
+            return Ok(TauriResult::error(e.to_string()));
+        }
+    };
     
-    // Add message to session
-    {
-        let mut sessions = state.chat_sessions.lock().await;
-        if let Some(session) = sessions.get_mut(&session_id) {
-            session.add_message(user_message.clone());
-            tracing::debug!("Added user message to session {}", session_id);
-        } else {
-            tracing::warn!("Session not found: {}", session_id);
-            return Ok(TauriResult::error("Chat session not found".to_string()));
+    // The editor selection is stored with the message so later turns can tell when it goes stale
+    if let Some(selection) = context.as_ref().and_then(capture_selection) {
+        attachments.push(MessagePart::Attachment(selection));
+    }
+    
+    let user_message = if attachments.is_empty() {
+        ChatMessage::user_message(content)
+    } else {
+        let mut message_parts = Vec::with_capacity(attachments.len() + 1);
+        if !content.trim().is_empty() {
+            message_parts.push(MessagePart::Text { text: content });
         }
+        message_parts.extend(attachments);
+        ChatMessage::user_message(parts_to_text(&message_parts)).with_parts(message_parts)
+    };
+    
+    // Add message to session
+    if let Err(e) = ChatService::append_message(&state, &store, &session_id, user_message).await {
+        e.log_warn();
+        return Ok(TauriResult::error(e.to_string()));
     }
+    tracing::debug!("Added user message to session {}", session_id);
     
-    // For now, return the user message (AI response would be implemented later)
-    Ok(TauriResult::success(user_message))
+    let result = ChatService::generate_reply(&app_handle, &state, &store, &session_id, context.as_ref()).await;
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
 }
 
 #[tauri::command]
 pub async fn get_chat_session(
     session_id: String,
     state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
 ) -> std::result::Result<TauriResult<ChatSession>, String> {
     tracing::debug!("Retrieving chat session: {}", session_id);
     
-    let sessions = state.chat_sessions.lock().await;
-    if let Some(session) = sessions.get(&session_id) {
-        Ok(TauriResult::success(session.clone()))
-    } else {
-        tracing::warn!("Session not found: {}", session_id);
-        Ok(TauriResult::error("Chat session not found".to_string()))
+    let result = ChatService::get_session(&state, &store, &session_id).await;
+    if let Err(e) = &result {
+        e.log_warn();
     }
-} 
\ No newline at end of file
+    Ok(TauriResult::from_result(result))
+}
+
+/// Compare the files attached on the active branch with their current contents.
+/// Called when a session is reopened so stale code can be flagged alongside its diff.
+#[tauri::command]
+pub async fn check_chat_attachments(
+    session_id: String,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<Vec<AttachmentStatus>>, String> {
+    let result = ChatService::get_session(&state, &store, &session_id)
+        .await
+        .map(|session| attachments::check_session(&session));
+    
+    match &result {
+        Ok(statuses) => {
+            let stale = statuses.iter().filter(|status| status.is_stale()).count();
+            if stale > 0 {
+                tracing::info!("{} of {} attachments in session {} changed since sent", stale, statuses.len(), session_id);
+            }
+        }
+        Err(e) => e.log_warn(),
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn list_chat_sessions(
+    project_path: Option<String>,
+    include_archived: Option<bool>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<Vec<ChatSessionSummary>>, String> {
+    Ok(TauriResult::success(store.list(project_path.as_deref(), include_archived.unwrap_or(false))))
+}
+
+#[tauri::command]
+pub async fn list_chat_sessions_by_project(
+    include_archived: Option<bool>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<BTreeMap<String, Vec<ChatSessionSummary>>>, String> {
+    Ok(TauriResult::success(store.grouped_by_project(include_archived.unwrap_or(false))))
+}
+
+#[tauri::command]
+pub async fn rename_chat_session(
+    session_id: String,
+    name: String,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<ChatSessionSummary>, String> {
+    let result = ChatService::rename_session(&state, &store, &session_id, name)
+        .await
+        .map(|session| ChatSessionSummary::from_session(&session));
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn delete_chat_session(
+    session_id: String,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<()>, String> {
+    tracing::info!("Deleting chat session: {}", session_id);
+    
+    let result = ChatService::delete_session(&state, &store, &session_id).await;
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn clear_chat_session(
+    session_id: String,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<ChatSessionSummary>, String> {
+    let result = ChatService::clear_session(&state, &store, &session_id)
+        .await
+        .map(|session| ChatSessionSummary::from_session(&session));
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn archive_chat_session(
+    session_id: String,
+    archived: Option<bool>,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<ChatSessionSummary>, String> {
+    let result = ChatService::set_archived(&state, &store, &session_id, archived.unwrap_or(true))
+        .await
+        .map(|session| ChatSessionSummary::from_session(&session));
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn fork_chat_session(
+    session_id: String,
+    message_id: String,
+    name: Option<String>,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<ChatSessionSummary>, String> {
+    let result = ChatService::fork_session(&state, &store, &session_id, &message_id, name)
+        .await
+        .map(|session| ChatSessionSummary::from_session(&session));
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn compact_chat_session(
+    session_id: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<Option<ChatMessage>>, String> {
+    let result = ChatService::compact_if_needed(&app_handle, &state, &store, &session_id).await;
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn search_chat_history(
+    query: ChatSearchQuery,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<Vec<ChatSearchHit>>, String> {
+    let result = store.search(&query);
+    match &result {
+        Ok(hits) => tracing::debug!("Chat search '{}' returned {} hits", query.query, hits.len()),
+        Err(e) => e.log_error(),
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Render a session as Markdown, HTML or JSON, optionally writing it to `output_path`
+#[tauri::command]
+pub async fn export_chat_session(
+    session_id: String,
+    format: ExportFormat,
+    output_path: Option<String>,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<String>, String> {
+    let result = async {
+        let session = ChatService::get_session(&state, &store, &session_id).await?;
+        let content = export_session(&session, format)?;
+        if let Some(path) = &output_path {
+            std::fs::write(path, &content)?;
+            tracing::info!("📤 Exported chat session {} to {}", session_id, path);
+        }
+        Ok::<_, AppError>(content)
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Restore a JSON export as a new session, re-bound to `project_path` or the open project
+#[tauri::command]
+pub async fn import_chat_session(
+    json: Option<String>,
+    input_path: Option<String>,
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<ChatSessionSummary>, String> {
+    let result = async {
+        let json = match (json, input_path) {
+            (Some(json), _) => json,
+            (None, Some(path)) => std::fs::read_to_string(&path)?,
+            (None, None) => {
+                return Err(AppError::validation("MISSING_IMPORT_SOURCE", "Provide export JSON or a file path"));
+            }
+        };
+        
+        let current = state.get_current_project().await;
+        let project_context = match project_path {
+            Some(path) => Some(match current {
+                Some(context) if context.root_path == path => context,
+                _ => ProjectContext::new(path, "unknown".to_string()),
+            }),
+            None => current,
+        };
+        
+        let session = import_session(&json, project_context)?;
+        tracing::info!("📥 Imported chat session {} ({} messages)", session.id, session.messages.len());
+        let session = ChatService::create_session(&state, &store, session).await?;
+        Ok(ChatSessionSummary::from_session(&session))
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Replace a user prompt with an edited sibling and answer it; the old branch is kept
+#[tauri::command]
+pub async fn edit_chat_message(
+    session_id: String,
+    message_id: String,
+    content: String,
+    context: Option<ChatContext>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<ChatMessage>, String> {
+    let result = async {
+        ChatService::edit_message(&state, &store, &session_id, &message_id, content).await?;
+        ChatService::generate_reply(&app_handle, &state, &store, &session_id, context.as_ref()).await
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Produce an alternative AI reply as a sibling of `message_id`
+#[tauri::command]
+pub async fn regenerate_chat_reply(
+    session_id: String,
+    message_id: String,
+    context: Option<ChatContext>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<ChatMessage>, String> {
+    let result = async {
+        ChatService::prepare_regenerate(&state, &store, &session_id, &message_id).await?;
+        ChatService::generate_reply(&app_handle, &state, &store, &session_id, context.as_ref()).await
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn switch_chat_branch(
+    session_id: String,
+    message_id: String,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<Vec<ThreadMessage>>, String> {
+    let result = ChatService::switch_branch(&state, &store, &session_id, &message_id)
+        .await
+        .map(|session| ChatService::thread(&session));
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// The active branch of a session with sibling information for branch navigation
+#[tauri::command]
+pub async fn get_chat_thread(
+    session_id: String,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<Vec<ThreadMessage>>, String> {
+    let result = ChatService::get_session(&state, &store, &session_id)
+        .await
+        .map(|session| ChatService::thread(&session));
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
@@ -0,0 +1,127 @@
+// Syntari AI IDE - Conversation Compaction
+// Folds older turns of long sessions into an incrementally updated summary
+
+use std::collections::HashMap;
+use crate::core::generate_id;
+use crate::ai::commands::estimate_tokens;
+use crate::ai::types::{AiRequest, RequestPriority};
+use crate::chat::types::{ChatMessage, ChatSession};
+
+/// Unsummarized history above this size triggers compaction
+pub const COMPACTION_THRESHOLD_TOKENS: u32 = 6000;
+/// Recent history left verbatim after compaction
+const RECENT_TOKENS_KEPT: u32 = 2000;
+const MIN_RECENT_MESSAGES: usize = 4;
+const SUMMARY_MAX_TOKENS: u32 = 800;
+
+const SUMMARY_KIND: &str = "conversation_summary";
+const KEY_KIND: &str = "kind";
+const KEY_SUMMARIZED_IDS: &str = "summarized_message_ids";
+const KEY_COVERS_THROUGH: &str = "covers_through";
+
+/// IDs of every message folded into a summary message
+pub fn summarized_message_ids(summary: &ChatMessage) -> Vec<String> {
+    summary
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(KEY_SUMMARIZED_IDS))
+        .and_then(|ids| serde_json::from_value(ids.clone()).ok())
+        .unwrap_or_default()
+}
+
+fn covers_through(summary: &ChatMessage) -> Option<&str> {
+    summary.metadata.as_ref()?.get(KEY_COVERS_THROUGH)?.as_str()
+}
+
+/// The session's summary, if it covers a prefix of the active branch
+pub fn active_summary(session: &ChatSession) -> Option<&ChatMessage> {
+    let summary = session.history_summary.as_ref()?;
+    let last = covers_through(summary)?;
+    session.active_path().iter().any(|m| m.id == last).then_some(summary)
+}
+
+/// Messages of the active branch not yet folded into the summary
+pub fn unsummarized(session: &ChatSession) -> Vec<&ChatMessage> {
+    let path = session.active_path();
+    let start = active_summary(session)
+        .and_then(covers_through)
+        .and_then(|last| path.iter().position(|m| m.id == last))
+        .map(|position| position + 1)
+        .unwrap_or(0);
+    path[start..].to_vec()
+}
+
+fn message_tokens(message: &ChatMessage) -> u32 {
+    estimate_tokens(&message.content)
+}
+
+/// Older unsummarized turns to fold in, or nothing if the history still fits
+pub fn select_for_compaction(session: &ChatSession) -> Vec<&ChatMessage> {
+    let pending = unsummarized(session);
+    let total: u32 = pending.iter().map(|m| message_tokens(m)).sum();
+    if total <= COMPACTION_THRESHOLD_TOKENS || pending.len() <= MIN_RECENT_MESSAGES {
+        return Vec::new();
+    }
+
+    // Walk back from the newest message until the verbatim budget is spent
+    let mut kept_tokens = 0;
+    let mut split = pending.len();
+    while split > 0 {
+        let kept = pending.len() - split;
+        let next = message_tokens(pending[split - 1]);
+        if kept >= MIN_RECENT_MESSAGES && kept_tokens + next > RECENT_TOKENS_KEPT {
+            break;
+        }
+        kept_tokens += next;
+        split -= 1;
+    }
+
+    // Never summarize the turns the user is actively working with
+    let split = split.min(pending.len() - MIN_RECENT_MESSAGES);
+    pending[..split].to_vec()
+}
+
+/// Request asking the model to merge the previous summary with newly aged-out turns
+pub fn summary_request(session: &ChatSession, turns: &[&ChatMessage]) -> AiRequest {
+    let mut prompt = String::from(
+        "Summarize this conversation between a developer and an AI assistant so it can continue without the full transcript. \
+         Keep decisions, file and symbol names, code snippets that matter, and open questions. Be concise.\n\n",
+    );
+
+    if let Some(previous) = active_summary(session) {
+        prompt.push_str("Summary so far:\n");
+        prompt.push_str(&previous.content);
+        prompt.push_str("\n\n");
+    }
+
+    prompt.push_str("New turns to fold in:\n");
+    for message in turns.iter().filter(|m| !m.is_system_message()) {
+        let speaker = if message.is_user_message() { "User" } else { "Assistant" };
+        prompt.push_str(&format!("{}: {}\n", speaker, message.content));
+    }
+
+    AiRequest::new(generate_id(), prompt)
+        .with_context(session.context.clone())
+        .with_priority(RequestPriority::Background)
+        .with_max_tokens(SUMMARY_MAX_TOKENS)
+}
+
+/// Whether `summary` covers more of the active branch than the session's current summary
+pub fn supersedes(session: &ChatSession, summary: &ChatMessage) -> bool {
+    let current = active_summary(session).map(|s| summarized_message_ids(s).len()).unwrap_or(0);
+    summarized_message_ids(summary).len() > current
+}
+
+/// System message replacing the previous summary; records every message it covers
+pub fn summary_message(session: &ChatSession, turns: &[&ChatMessage], content: String) -> Option<ChatMessage> {
+    let last = turns.last()?;
+    let mut ids = active_summary(session).map(summarized_message_ids).unwrap_or_default();
+    ids.extend(turns.iter().map(|m| m.id.clone()));
+
+    let mut metadata = HashMap::new();
+    metadata.insert(KEY_KIND.to_string(), serde_json::json!(SUMMARY_KIND));
+    metadata.insert(KEY_COVERS_THROUGH.to_string(), serde_json::json!(last.id));
+    metadata.insert(KEY_SUMMARIZED_IDS.to_string(), serde_json::json!(ids));
+
+    Some(ChatMessage::system_message(content).with_metadata(metadata))
+}
@@ -0,0 +1,295 @@
+// Syntari AI IDE - Chat Export and Import
+// Markdown, self-contained HTML and lossless JSON transcripts of chat sessions
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::core::{AppError, AppResult, current_timestamp, generate_id};
+use crate::chat::types::{ChatMessage, ChatSession, MessagePart};
+use crate::project::types::ProjectContext;
+
+const EXPORT_FORMAT_ID: &str = "syntari-chat";
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Envelope of a JSON export; `session` round-trips without loss
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatExport {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: u64,
+    pub session: ChatSession,
+}
+
+pub fn export_session(session: &ChatSession, format: ExportFormat) -> AppResult<String> {
+    match format {
+        ExportFormat::Markdown => Ok(to_markdown(session)),
+        ExportFormat::Html => Ok(to_html(session)),
+        ExportFormat::Json => {
+            let export = ChatExport {
+                format: EXPORT_FORMAT_ID.to_string(),
+                version: EXPORT_VERSION,
+                exported_at: current_timestamp(),
+                session: session.clone(),
+            };
+            Ok(serde_json::to_string_pretty(&export)?)
+        }
+    }
+}
+
+// ================================
+// MARKDOWN
+// ================================
+
+fn to_markdown(session: &ChatSession) -> String {
+    let mut out = format!("# {}\n\n", session.name);
+    out.push_str(&format!(
+        "_Project: `{}` · {} messages · exported {}_\n\n",
+        session.context.root_path,
+        session.active_path().len(),
+        format_timestamp(current_timestamp())
+    ));
+
+    // Markdown and HTML show the active branch; JSON keeps every branch
+    for message in session.active_path() {
+        out.push_str(&format!("---\n\n### {} · {}\n\n", speaker(message), format_timestamp(message.timestamp)));
+
+        // Content already carries fenced code blocks, which pass through unchanged
+        out.push_str(message_text(message).trim_end());
+        out.push_str("\n\n");
+
+        for part in &message.parts {
+            match part {
+                // Inline base64 would swamp a pasted transcript; HTML and JSON keep the pixels
+                MessagePart::Image(_) | MessagePart::FileReference { .. } | MessagePart::Attachment(_) => {
+                    out.push_str(&format!("> {}\n\n", part.to_text()))
+                }
+                MessagePart::Text { .. } => {}
+            }
+        }
+    }
+    out
+}
+
+// ================================
+// HTML
+// ================================
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:860px;margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.55}\
+header{border-bottom:1px solid #d0d7de;margin-bottom:1.5rem}header p{color:#656d76;font-size:.9rem}\
+.message{border:1px solid #d0d7de;border-radius:8px;padding:.75rem 1rem;margin:1rem 0}\
+.message.user{background:#f6f8fa}.message.system{background:#fff8c5}\
+.meta{font-size:.8rem;color:#656d76;margin-bottom:.5rem}.meta strong{color:#1f2328}\
+pre{background:#0d1117;color:#e6edf3;padding:.75rem;border-radius:6px;overflow-x:auto}\
+code{font-family:ui-monospace,SFMono-Regular,Menlo,monospace;font-size:.85rem}\
+img{max-width:100%;border-radius:6px}.file{font-family:ui-monospace,monospace;font-size:.85rem;color:#0969da}";
+
+fn to_html(session: &ChatSession) -> String {
+    let mut body = String::new();
+    let thread = session.active_path();
+    for message in &thread {
+        body.push_str(&format!(
+            "<section class=\"message {}\">\n<div class=\"meta\"><strong>{}</strong> · {}</div>\n",
+            escape_html(&message.message_type),
+            speaker(message),
+            format_timestamp(message.timestamp)
+        ));
+        body.push_str(&markdown_blocks_to_html(message_text(message)));
+
+        for part in &message.parts {
+            match part {
+                MessagePart::Image(image) => body.push_str(&format!(
+                    "<p><img alt=\"{}\" src=\"data:{};base64,{}\"></p>\n",
+                    escape_html(image.source_path.as_deref().unwrap_or("image")),
+                    escape_html(&image.mime_type),
+                    image.data
+                )),
+                MessagePart::FileReference { .. } | MessagePart::Attachment(_) => {
+                    body.push_str(&format!("<p class=\"file\">{}</p>\n", escape_html(&part.to_text())))
+                }
+                MessagePart::Text { .. } => {}
+            }
+        }
+        body.push_str("</section>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n\
+         <header><h1>{title}</h1><p>Project: <code>{root}</code> · {count} messages · exported {exported}</p></header>\n{body}</body>\n</html>\n",
+        title = escape_html(&session.name),
+        style = HTML_STYLE,
+        root = escape_html(&session.context.root_path),
+        count = thread.len(),
+        exported = format_timestamp(current_timestamp()),
+        body = body,
+    )
+}
+
+/// Paragraphs and fenced code blocks; inline markup is kept as literal text
+fn markdown_blocks_to_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<(String, Vec<&str>)> = None;
+
+    let flush_paragraph = |paragraph: &mut Vec<&str>, html: &mut String| {
+        if !paragraph.is_empty() {
+            let lines: Vec<String> = paragraph.iter().map(|line| escape_html(line)).collect();
+            html.push_str(&format!("<p>{}</p>\n", lines.join("<br>")));
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            match code.take() {
+                Some((language, lines)) => {
+                    let class = if language.is_empty() { String::new() } else { format!(" class=\"language-{}\"", escape_html(&language)) };
+                    html.push_str(&format!("<pre><code{}>{}</code></pre>\n", class, escape_html(&lines.join("\n"))));
+                }
+                None => {
+                    flush_paragraph(&mut paragraph, &mut html);
+                    code = Some((info.trim().to_string(), Vec::new()));
+                }
+            }
+            continue;
+        }
+
+        match code.as_mut() {
+            Some((_, lines)) => lines.push(line),
+            None if line.trim().is_empty() => flush_paragraph(&mut paragraph, &mut html),
+            None => paragraph.push(line),
+        }
+    }
+
+    // An unterminated fence still renders as code
+    if let Some((_, lines)) = code {
+        html.push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(&lines.join("\n"))));
+    }
+    flush_paragraph(&mut paragraph, &mut html);
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// ================================
+// IMPORT
+// ================================
+
+/// Restore a JSON export as a new session with fresh IDs, bound to `project_context`
+pub fn import_session(json: &str, project_context: Option<ProjectContext>) -> AppResult<ChatSession> {
+    let export: ChatExport = serde_json::from_str(json).map_err(|e| {
+        AppError::validation_with_field("INVALID_CHAT_EXPORT", &format!("Not a chat export: {}", e), "json")
+    })?;
+    if export.format != EXPORT_FORMAT_ID || export.version > EXPORT_VERSION {
+        return Err(AppError::validation_with_field(
+            "UNSUPPORTED_CHAT_EXPORT",
+            &format!("Unsupported export format {} v{}", export.format, export.version),
+            "json",
+        ));
+    }
+
+    let mut session = export.session;
+    let ids: HashMap<String, String> = session
+        .messages
+        .iter()
+        .chain(session.history_summary.iter())
+        .map(|message| (message.id.clone(), generate_id()))
+        .collect();
+
+    session.id = generate_id();
+    for message in session.messages.iter_mut().chain(session.history_summary.iter_mut()) {
+        rekey_message(message, &ids);
+    }
+    session.active_leaf = session.active_leaf.as_ref().and_then(|id| ids.get(id)).cloned();
+
+    if let Some(context) = project_context {
+        session.context = context;
+    }
+    session.archived = false;
+    Ok(session)
+}
+
+/// Swap a message's ID and any references to old IDs in its metadata
+fn rekey_message(message: &mut ChatMessage, ids: &HashMap<String, String>) {
+    if let Some(new_id) = ids.get(&message.id) {
+        message.id = new_id.clone();
+    }
+    if let Some(parent_id) = message.parent_id.as_mut() {
+        if let Some(new_id) = ids.get(parent_id.as_str()) {
+            *parent_id = new_id.clone();
+        }
+    }
+    if let Some(metadata) = message.metadata.as_mut() {
+        for value in metadata.values_mut() {
+            rekey_value(value, ids);
+        }
+    }
+}
+
+fn rekey_value(value: &mut serde_json::Value, ids: &HashMap<String, String>) {
+    match value {
+        serde_json::Value::String(text) => {
+            if let Some(new_id) = ids.get(text.as_str()) {
+                *text = new_id.clone();
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|item| rekey_value(item, ids)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|item| rekey_value(item, ids)),
+        _ => {}
+    }
+}
+
+// ================================
+// HELPERS
+// ================================
+
+fn speaker(message: &ChatMessage) -> &'static str {
+    if message.is_user_message() {
+        "User"
+    } else if message.is_ai_message() {
+        "Assistant"
+    } else {
+        "System"
+    }
+}
+
+/// Text of a message without the placeholders `content` uses for attachments
+fn message_text(message: &ChatMessage) -> &str {
+    message
+        .parts
+        .iter()
+        .find_map(|part| match part {
+            MessagePart::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .unwrap_or(if message.parts.is_empty() { message.content.as_str() } else { "" })
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|date| date.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
@@ -3,8 +3,16 @@
 
 pub mod types;
 pub mod service;
+pub mod multimodal;
+pub mod attachments;
+pub mod store;
+pub mod compaction;
+pub mod search;
+pub mod export;
+pub mod slash;
 pub mod commands;
 
 // Re-export commonly used types
 pub use types::*;
-pub use service::ChatService; 
\ No newline at end of file
+pub use service::ChatService;
+pub use store::ChatStore; 
\ No newline at end of file
@@ -0,0 +1,146 @@
+// Syntari AI IDE - Multimodal Message Parts
+// Validates, downscales and encodes image attachments for chat messages
+
+use std::io::Cursor;
+use std::path::Path;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use image::{DynamicImage, ImageFormat, imageops::FilterType};
+use crate::core::{AppError, AppResult};
+use crate::chat::attachments::capture_file;
+use crate::chat::types::{ImagePart, MessagePart, MessagePartInput};
+
+/// Largest image accepted from disk or the clipboard before decoding
+pub const MAX_IMAGE_INPUT_BYTES: usize = 20 * 1024 * 1024;
+/// Longest edge sent to providers; larger images are downscaled
+pub const MAX_IMAGE_DIMENSION: u32 = 1568;
+/// Upper bound on an encoded image after downscaling
+pub const MAX_ENCODED_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+pub const MAX_IMAGES_PER_MESSAGE: usize = 8;
+
+const JPEG_QUALITY: u8 = 85;
+
+/// Turn frontend parts into stored parts, decoding and downscaling every image
+pub fn prepare_parts(inputs: Vec<MessagePartInput>) -> AppResult<Vec<MessagePart>> {
+    let image_count = inputs
+        .iter()
+        .filter(|input| matches!(input, MessagePartInput::ImagePath { .. } | MessagePartInput::ImageData { .. }))
+        .count();
+    if image_count > MAX_IMAGES_PER_MESSAGE {
+        return Err(AppError::validation_with_field(
+            "TOO_MANY_IMAGES",
+            &format!("A message can carry at most {} images", MAX_IMAGES_PER_MESSAGE),
+            "parts",
+        ));
+    }
+
+    inputs
+        .into_iter()
+        .map(|input| match input {
+            MessagePartInput::Text { text } => Ok(MessagePart::Text { text }),
+            MessagePartInput::ImagePath { path } => load_image_file(Path::new(&path)).map(MessagePart::Image),
+            MessagePartInput::ImageData { data, mime_type } => {
+                let bytes = BASE64.decode(data.trim()).map_err(|e| {
+                    AppError::validation_with_field("INVALID_IMAGE_DATA", &format!("Image data is not valid base64: {}", e), "data")
+                })?;
+                prepare_image(&bytes, Some(&mime_type), None).map(MessagePart::Image)
+            }
+            MessagePartInput::FileReference { path, start_line, end_line } => {
+                capture_file(&path, start_line, end_line).map(MessagePart::Attachment)
+            }
+        })
+        .collect()
+}
+
+/// Plain-text rendering of parts, used as the message `content`
+pub fn parts_to_text(parts: &[MessagePart]) -> String {
+    parts.iter().map(|part| part.to_text()).collect::<Vec<_>>().join("\n")
+}
+
+fn load_image_file(path: &Path) -> AppResult<ImagePart> {
+    let display = path.to_string_lossy().to_string();
+    let metadata = std::fs::metadata(path).map_err(|e| {
+        AppError::filesystem_with_path("IMAGE_NOT_FOUND".to_string(), format!("Cannot read image: {}", e), display.clone())
+    })?;
+    if metadata.len() as usize > MAX_IMAGE_INPUT_BYTES {
+        return Err(too_large(metadata.len() as usize, MAX_IMAGE_INPUT_BYTES));
+    }
+
+    let bytes = std::fs::read(path).map_err(|e| {
+        AppError::filesystem_with_path("IMAGE_READ_FAILED".to_string(), format!("Cannot read image: {}", e), display.clone())
+    })?;
+    prepare_image(&bytes, None, Some(display))
+}
+
+/// Decode, downscale to `MAX_IMAGE_DIMENSION` and re-encode an image
+pub fn prepare_image(bytes: &[u8], mime_type: Option<&str>, source_path: Option<String>) -> AppResult<ImagePart> {
+    if bytes.len() > MAX_IMAGE_INPUT_BYTES {
+        return Err(too_large(bytes.len(), MAX_IMAGE_INPUT_BYTES));
+    }
+
+    let format = image::guess_format(bytes)
+        .ok()
+        .or_else(|| mime_type.and_then(ImageFormat::from_mime_type))
+        .ok_or_else(|| AppError::validation_with_field("UNSUPPORTED_IMAGE", "Unrecognised image format", "mime_type"))?;
+
+    let decoded = image::load_from_memory_with_format(bytes, format).map_err(|e| {
+        AppError::validation_with_field("UNSUPPORTED_IMAGE", &format!("Failed to decode image: {}", e), "data")
+    })?;
+
+    let image = if decoded.width() > MAX_IMAGE_DIMENSION || decoded.height() > MAX_IMAGE_DIMENSION {
+        decoded.resize(MAX_IMAGE_DIMENSION, MAX_IMAGE_DIMENSION, FilterType::Lanczos3)
+    } else {
+        decoded
+    };
+
+    // Photos stay JPEG; anything that may carry transparency or sharp UI edges stays PNG
+    let mut encoded = if format == ImageFormat::Jpeg {
+        encode_jpeg(&image)?
+    } else {
+        encode_png(&image)?
+    };
+    if encoded.0.len() > MAX_ENCODED_IMAGE_BYTES && encoded.1 != "image/jpeg" {
+        encoded = encode_jpeg(&image)?;
+    }
+    if encoded.0.len() > MAX_ENCODED_IMAGE_BYTES {
+        return Err(too_large(encoded.0.len(), MAX_ENCODED_IMAGE_BYTES));
+    }
+
+    let (data, mime_type) = encoded;
+    Ok(ImagePart {
+        mime_type: mime_type.to_string(),
+        data: BASE64.encode(data),
+        width: image.width(),
+        height: image.height(),
+        source_path,
+    })
+}
+
+fn encode_png(image: &DynamicImage) -> AppResult<(Vec<u8>, &'static str)> {
+    let mut buffer = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+        .map_err(|e| AppError::internal("IMAGE_ENCODE_FAILED", &format!("Failed to encode PNG: {}", e)))?;
+    Ok((buffer, "image/png"))
+}
+
+fn encode_jpeg(image: &DynamicImage) -> AppResult<(Vec<u8>, &'static str)> {
+    let mut buffer = Vec::new();
+    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY);
+    DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_with_encoder(encoder)
+        .map_err(|e| AppError::internal("IMAGE_ENCODE_FAILED", &format!("Failed to encode JPEG: {}", e)))?;
+    Ok((buffer, "image/jpeg"))
+}
+
+fn too_large(size: usize, limit: usize) -> AppError {
+    AppError::validation_with_field(
+        "IMAGE_TOO_LARGE",
+        &format!(
+            "Image is {:.1} MB; the limit is {} MB",
+            size as f64 / (1024.0 * 1024.0),
+            limit / (1024 * 1024)
+        ),
+        "parts",
+    )
+}
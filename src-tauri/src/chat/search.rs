@@ -0,0 +1,328 @@
+// Syntari AI IDE - Chat History Search
+// In-memory inverted index over persisted chat messages with BM25 ranking
+
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use crate::chat::types::{ChatMessage, ChatSession, MessagePart};
+
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+/// Extra weight for terms inside code blocks and file references
+const CODE_TERM_WEIGHT: u32 = 2;
+const PHRASE_BOOST: f32 = 1.5;
+const SNIPPET_CHARS: usize = 180;
+const DEFAULT_LIMIT: usize = 50;
+
+// ================================
+// QUERY TYPES
+// ================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatSearchQuery {
+    pub query: String,
+    pub project_root: Option<String>,
+    /// Inclusive bounds in seconds since the epoch
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    /// `user`, `ai` or `system`
+    pub role: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSearchHit {
+    pub session_id: String,
+    pub message_id: String,
+    pub project_root: String,
+    pub role: String,
+    pub timestamp: u64,
+    pub score: f32,
+    pub snippet: Vec<SnippetSegment>,
+}
+
+// ================================
+// INDEX
+// ================================
+
+struct IndexedMessage {
+    session_id: String,
+    message_id: String,
+    project_root: String,
+    role: String,
+    timestamp: u64,
+    text: String,
+    length: u32,
+}
+
+#[derive(Default)]
+pub struct ChatSearchIndex {
+    // Removed documents leave a tombstone so posting indices stay valid
+    docs: Vec<Option<IndexedMessage>>,
+    postings: HashMap<String, Vec<(u32, u32)>>,
+    by_session: HashMap<String, Vec<u32>>,
+    live_docs: usize,
+    total_length: u64,
+}
+
+impl ChatSearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_session(&mut self, session: &ChatSession) {
+        for message in &session.messages {
+            self.add_message(session, message);
+        }
+    }
+
+    pub fn add_message(&mut self, session: &ChatSession, message: &ChatMessage) {
+        let text = searchable_text(message);
+        let frequencies = term_frequencies(&text);
+        if frequencies.is_empty() {
+            return;
+        }
+
+        let doc_id = self.docs.len() as u32;
+        let length: u32 = frequencies.values().sum();
+        for (term, frequency) in frequencies {
+            self.postings.entry(term).or_default().push((doc_id, frequency));
+        }
+
+        self.docs.push(Some(IndexedMessage {
+            session_id: session.id.clone(),
+            message_id: message.id.clone(),
+            project_root: session.context.root_path.clone(),
+            role: message.message_type.clone(),
+            timestamp: message.timestamp,
+            text,
+            length,
+        }));
+        self.by_session.entry(session.id.clone()).or_default().push(doc_id);
+        self.live_docs += 1;
+        self.total_length += length as u64;
+    }
+
+    pub fn remove_session(&mut self, session_id: &str) {
+        let Some(doc_ids) = self.by_session.remove(session_id) else {
+            return;
+        };
+
+        for doc_id in doc_ids {
+            if let Some(doc) = self.docs[doc_id as usize].take() {
+                self.live_docs -= 1;
+                self.total_length -= doc.length as u64;
+            }
+        }
+
+        // Compact postings once tombstones outnumber live documents
+        if self.docs.len() > 2 * self.live_docs.max(64) {
+            self.rebuild();
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let docs = std::mem::take(&mut self.docs);
+        *self = Self::default();
+        for doc in docs.into_iter().flatten() {
+            let frequencies = term_frequencies(&doc.text);
+            let doc_id = self.docs.len() as u32;
+            for (term, frequency) in frequencies {
+                self.postings.entry(term).or_default().push((doc_id, frequency));
+            }
+            self.by_session.entry(doc.session_id.clone()).or_default().push(doc_id);
+            self.live_docs += 1;
+            self.total_length += doc.length as u64;
+            self.docs.push(Some(doc));
+        }
+    }
+
+    /// Ranked hits, skipping sessions in `excluded` before the limit is applied
+    pub fn search(&self, query: &ChatSearchQuery, excluded: &HashSet<String>) -> Vec<ChatSearchHit> {
+        let terms: Vec<String> = {
+            let mut seen = HashSet::new();
+            tokenize(&query.query).into_iter().filter(|t| seen.insert(t.clone())).collect()
+        };
+        if terms.is_empty() || self.live_docs == 0 {
+            return Vec::new();
+        }
+
+        let average_length = self.total_length as f32 / self.live_docs as f32;
+        let mut scores: HashMap<u32, f32> = HashMap::new();
+
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let live: Vec<&(u32, u32)> = postings.iter().filter(|(id, _)| self.docs[*id as usize].is_some()).collect();
+            let document_frequency = live.len() as f32;
+            let idf = ((self.live_docs as f32 - document_frequency + 0.5) / (document_frequency + 0.5) + 1.0).ln();
+
+            for (doc_id, frequency) in live {
+                let doc = self.docs[*doc_id as usize].as_ref().expect("filtered to live docs");
+                if excluded.contains(&doc.session_id) || !matches_filters(doc, query) {
+                    continue;
+                }
+                let tf = *frequency as f32;
+                let norm = 1.0 - BM25_B + BM25_B * doc.length as f32 / average_length;
+                *scores.entry(*doc_id).or_default() += idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * norm);
+            }
+        }
+
+        let phrase = query.query.trim().to_lowercase();
+        let mut hits: Vec<ChatSearchHit> = scores
+            .into_iter()
+            .filter_map(|(doc_id, mut score)| {
+                let doc = self.docs[doc_id as usize].as_ref()?;
+                if terms.len() > 1 && doc.text.to_lowercase().contains(&phrase) {
+                    score *= PHRASE_BOOST;
+                }
+                Some(ChatSearchHit {
+                    session_id: doc.session_id.clone(),
+                    message_id: doc.message_id.clone(),
+                    project_root: doc.project_root.clone(),
+                    role: doc.role.clone(),
+                    timestamp: doc.timestamp,
+                    score,
+                    snippet: snippet(&doc.text, &terms),
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.timestamp.cmp(&a.timestamp)));
+        hits.truncate(query.limit.unwrap_or(DEFAULT_LIMIT));
+        hits
+    }
+}
+
+fn matches_filters(doc: &IndexedMessage, query: &ChatSearchQuery) -> bool {
+    query.project_root.as_ref().is_none_or(|root| &doc.project_root == root)
+        && query.role.as_ref().is_none_or(|role| &doc.role == role)
+        && query.from.is_none_or(|from| doc.timestamp >= from)
+        && query.to.is_none_or(|to| doc.timestamp <= to)
+}
+
+// ================================
+// TEXT PROCESSING
+// ================================
+
+/// Message content plus file references that only live in typed parts
+fn searchable_text(message: &ChatMessage) -> String {
+    let mut text = message.content.clone();
+    for part in &message.parts {
+        let path = match part {
+            MessagePart::FileReference { path, .. } => path,
+            MessagePart::Attachment(attachment) => &attachment.path,
+            _ => continue,
+        };
+        if !text.contains(path.as_str()) {
+            text.push('\n');
+            text.push_str(path);
+        }
+    }
+    text
+}
+
+fn term_frequencies(text: &str) -> HashMap<String, u32> {
+    let mut frequencies = HashMap::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        let is_code = in_code_block || line.contains('/') || line.contains('`');
+        let weight = if is_code { CODE_TERM_WEIGHT } else { 1 };
+        for term in tokenize(line) {
+            *frequencies.entry(term).or_insert(0) += weight;
+        }
+    }
+    frequencies
+}
+
+/// Lowercased words plus the parts of `snake_case`, `camelCase` and path identifiers
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric() && c != '_') {
+        if word.is_empty() || word.len() > 64 {
+            continue;
+        }
+        let lowered = word.to_lowercase();
+
+        let mut parts = Vec::new();
+        for piece in word.split('_').filter(|p| !p.is_empty()) {
+            let mut current = String::new();
+            let mut previous_lower = false;
+            for ch in piece.chars() {
+                if ch.is_uppercase() && previous_lower && !current.is_empty() {
+                    parts.push(current.to_lowercase());
+                    current.clear();
+                }
+                previous_lower = ch.is_lowercase() || ch.is_ascii_digit();
+                current.push(ch);
+            }
+            if !current.is_empty() {
+                parts.push(current.to_lowercase());
+            }
+        }
+
+        if parts.len() > 1 {
+            tokens.extend(parts.into_iter().filter(|p| *p != lowered && p.len() > 1));
+        }
+        tokens.push(lowered);
+    }
+    tokens
+}
+
+/// Window around the first matching term, split into highlighted and plain segments
+fn snippet(text: &str, terms: &[String]) -> Vec<SnippetSegment> {
+    let chars: Vec<char> = text.chars().collect();
+    let lowered: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+
+    let matches_at = |start: usize| -> Option<usize> {
+        terms.iter().map(|t| t.chars().collect::<Vec<char>>()).find_map(|term| {
+            let end = start + term.len();
+            (end <= lowered.len() && lowered[start..end] == term[..]).then_some(end)
+        })
+    };
+
+    let first = (0..chars.len()).find(|&i| matches_at(i).is_some()).unwrap_or(0);
+    let start = first.saturating_sub(SNIPPET_CHARS / 3);
+    let end = (start + SNIPPET_CHARS).min(chars.len());
+
+    let mut segments: Vec<SnippetSegment> = Vec::new();
+    let mut push = |text: String, highlighted: bool| {
+        match segments.last_mut() {
+            Some(last) if last.highlighted == highlighted => last.text.push_str(&text),
+            _ => segments.push(SnippetSegment { text, highlighted }),
+        }
+    };
+
+    if start > 0 {
+        push("…".to_string(), false);
+    }
+    let mut i = start;
+    while i < end {
+        match matches_at(i) {
+            Some(match_end) => {
+                let match_end = match_end.min(end);
+                push(chars[i..match_end].iter().collect(), true);
+                i = match_end;
+            }
+            None => {
+                push(chars[i].to_string(), false);
+                i += 1;
+            }
+        }
+    }
+    if end < chars.len() {
+        push("…".to_string(), false);
+    }
+    segments
+}
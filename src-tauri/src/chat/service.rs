@@ -1,10 +1,481 @@
 // Syntari AI IDE - Chat Service
 // Business logic for chat operations
 
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::Manager;
+use crate::core::{AppError, AppResult, AppState, current_timestamp, generate_id};
+use crate::ai::commands::estimate_tokens;
+use crate::ai::commands::run_ai_request;
+use crate::ai::types::{AiRequest, ConsensusResult, RequestPriority};
+use crate::chat::compaction;
+use crate::chat::store::{ChatStore, SessionRecord};
+use crate::chat::attachments;
+use crate::chat::multimodal::parts_to_text;
+use crate::chat::types::{AiPreferences, ChatContext, ChatMessage, ChatSession, MessagePart, ThreadMessage};
+use crate::project::types::ProjectContext;
+
+/// Earlier messages replayed to the model with each new message
+const HISTORY_LIMIT: usize = 20;
+
 pub struct ChatService;
 
 impl ChatService {
     pub fn new() -> Self {
         Self
     }
-} 
\ No newline at end of file
+    
+    /// Session from the in-memory cache, loading it from disk on first access
+    pub async fn get_session(state: &AppState, store: &ChatStore, session_id: &str) -> AppResult<ChatSession> {
+        let mut sessions = state.chat_sessions.lock().await;
+        Self::cached(&mut sessions, store, session_id).map(|session| session.clone())
+    }
+    
+    /// Cache entry for a session, loading it under the caller's lock so a concurrent update cannot be overwritten
+    fn cached<'a>(
+        sessions: &'a mut HashMap<String, ChatSession>,
+        store: &ChatStore,
+        session_id: &str,
+    ) -> AppResult<&'a mut ChatSession> {
+        if !sessions.contains_key(session_id) {
+            if !store.contains(session_id) {
+                return Err(AppError::chat_with_session("SESSION_NOT_FOUND", "Chat session not found", session_id));
+            }
+            let session = store.load(session_id)?;
+            tracing::debug!("Loaded chat session {} from disk ({} messages)", session_id, session.messages.len());
+            sessions.insert(session.id.clone(), session);
+        }
+        sessions
+            .get_mut(session_id)
+            .ok_or_else(|| AppError::chat_with_session("SESSION_NOT_FOUND", "Chat session not found", session_id))
+    }
+    
+    /// Persist a new session and cache it
+    pub async fn create_session(state: &AppState, store: &ChatStore, session: ChatSession) -> AppResult<ChatSession> {
+        store.create(&session)?;
+        state.chat_sessions.lock().await.insert(session.id.clone(), session.clone());
+        Ok(session)
+    }
+    
+    /// Append a message durably, then update the cached session
+    pub async fn append_message(
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+        message: ChatMessage,
+    ) -> AppResult<ChatSession> {
+        let record = SessionRecord::Message { message: message.clone() };
+        Self::apply_record(state, store, session_id, record, |session| {
+            session.add_message(message);
+        }).await
+    }
+    
+    /// Append a message exactly at its `parent_id`, starting a new branch if it has siblings
+    pub async fn append_branch_message(
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+        message: ChatMessage,
+    ) -> AppResult<ChatSession> {
+        let record = SessionRecord::Message { message: message.clone() };
+        Self::apply_record(state, store, session_id, record, |session| {
+            session.attach_message(message);
+            session.updated_at = current_timestamp();
+        }).await
+    }
+    
+    /// Log a change, then commit it to the cached session.
+    /// The session's lock is held throughout so concurrent updates to it are applied in turn.
+    async fn apply_record(
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+        record: SessionRecord,
+        update: impl FnOnce(&mut ChatSession),
+    ) -> AppResult<ChatSession> {
+        let applied = Self::apply_record_if(state, store, session_id, record, |session| {
+            update(session);
+            true
+        }).await?;
+        applied.ok_or_else(|| AppError::internal("CHAT_UPDATE_SKIPPED", "Chat session update was not applied"))
+    }
+    
+    /// Like `apply_record`, but nothing is logged when `update` returns false
+    async fn apply_record_if(
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+        record: SessionRecord,
+        update: impl FnOnce(&mut ChatSession) -> bool,
+    ) -> AppResult<Option<ChatSession>> {
+        let lock = Self::session_lock(state, session_id).await;
+        let _guard = lock.lock().await;
+        let mut session = Self::get_session(state, store, session_id).await?;
+        if !update(&mut session) {
+            return Ok(None);
+        }
+        
+        // Disk first: the cache never holds changes that a crash would lose.
+        // The shared cache lock is not held across the synced write.
+        store.append(&session, &record)?;
+        state.chat_sessions.lock().await.insert(session.id.clone(), session.clone());
+        Ok(Some(session))
+    }
+    
+    async fn session_lock(state: &AppState, session_id: &str) -> Arc<tokio::sync::Mutex<()>> {
+        state.chat_session_locks.lock().await.entry(session_id.to_string()).or_default().clone()
+    }
+    
+    pub async fn rename_session(state: &AppState, store: &ChatStore, session_id: &str, name: String) -> AppResult<ChatSession> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::validation_with_field("INVALID_SESSION_NAME", "Session name cannot be empty", "name"));
+        }
+        
+        let at = current_timestamp();
+        Self::apply_record(state, store, session_id, SessionRecord::Renamed { name: name.clone(), at }, |session| {
+            session.name = name;
+            session.updated_at = at;
+        }).await
+    }
+    
+    pub async fn clear_session(state: &AppState, store: &ChatStore, session_id: &str) -> AppResult<ChatSession> {
+        let at = current_timestamp();
+        Self::apply_record(state, store, session_id, SessionRecord::Cleared { at }, |session| {
+            session.clear_messages();
+            session.updated_at = at;
+        }).await
+    }
+    
+    pub async fn set_archived(state: &AppState, store: &ChatStore, session_id: &str, archived: bool) -> AppResult<ChatSession> {
+        let at = current_timestamp();
+        Self::apply_record(state, store, session_id, SessionRecord::Archived { archived, at }, |session| {
+            session.archived = archived;
+            session.updated_at = at;
+        }).await
+    }
+    
+    pub async fn delete_session(state: &AppState, store: &ChatStore, session_id: &str) -> AppResult<()> {
+        if !store.contains(session_id) && !state.chat_session_exists(session_id).await {
+            return Err(AppError::chat_with_session("SESSION_NOT_FOUND", "Chat session not found", session_id));
+        }
+        
+        let lock = Self::session_lock(state, session_id).await;
+        let _guard = lock.lock().await;
+        store.delete(session_id)?;
+        state.chat_sessions.lock().await.remove(session_id);
+        state.chat_session_locks.lock().await.remove(session_id);
+        Ok(())
+    }
+    
+    /// Copy a session up to and including `message_id` into a new session
+    pub async fn fork_session(
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+        message_id: &str,
+        name: Option<String>,
+    ) -> AppResult<ChatSession> {
+        let source = Self::get_session(state, store, session_id).await?;
+        let name = name
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| format!("{} (fork)", source.name));
+        
+        let fork = source.fork_at(message_id, name).ok_or_else(|| {
+            AppError::chat_with_session(
+                "MESSAGE_NOT_FOUND",
+                &format!("Message {} is not part of this session", message_id),
+                session_id,
+            )
+        })?;
+        
+        tracing::info!("Forked chat session {} into {} ({} messages)", session_id, fork.id, fork.messages.len());
+        Self::create_session(state, store, fork).await
+    }
+    
+    // ================================
+    // BRANCHING
+    // ================================
+    
+    fn find_message<'a>(session: &'a ChatSession, message_id: &str) -> AppResult<&'a ChatMessage> {
+        session.find_message(message_id).ok_or_else(|| {
+            AppError::chat_with_session(
+                "MESSAGE_NOT_FOUND",
+                &format!("Message {} is not part of this session", message_id),
+                &session.id,
+            )
+        })
+    }
+    
+    async fn set_active_leaf(state: &AppState, store: &ChatStore, session_id: &str, leaf: String) -> AppResult<ChatSession> {
+        let record = SessionRecord::ActiveLeaf { message_id: leaf.clone() };
+        Self::apply_record(state, store, session_id, record, |session| {
+            session.active_leaf = Some(leaf);
+        }).await
+    }
+    
+    /// Show the branch containing `message_id`, continuing to its newest leaf
+    pub async fn switch_branch(state: &AppState, store: &ChatStore, session_id: &str, message_id: &str) -> AppResult<ChatSession> {
+        let session = Self::get_session(state, store, session_id).await?;
+        Self::find_message(&session, message_id)?;
+        let leaf = session.latest_leaf_from(message_id).unwrap_or_else(|| message_id.to_string());
+        Self::set_active_leaf(state, store, session_id, leaf).await
+    }
+    
+    /// Add an edited copy of a user message as its sibling; the original branch is kept
+    pub async fn edit_message(
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+        message_id: &str,
+        content: String,
+    ) -> AppResult<ChatSession> {
+        let session = Self::get_session(state, store, session_id).await?;
+        let original = Self::find_message(&session, message_id)?;
+        if !original.is_user_message() {
+            return Err(AppError::validation_with_field("NOT_A_USER_MESSAGE", "Only user messages can be edited", "message_id"));
+        }
+        
+        // Attachments carry over; only the text changes
+        let attachments: Vec<MessagePart> = original
+            .parts
+            .iter()
+            .filter(|part| !matches!(part, MessagePart::Text { .. }))
+            .cloned()
+            .collect();
+        let edited = if attachments.is_empty() {
+            ChatMessage::user_message(content)
+        } else {
+            let mut parts = vec![MessagePart::Text { text: content }];
+            parts.extend(attachments);
+            ChatMessage::user_message(parts_to_text(&parts)).with_parts(parts)
+        };
+        
+        Self::append_branch_message(state, store, session_id, edited.with_parent(original.parent_id.clone())).await
+    }
+    
+    /// Make an AI reply's prompt the active leaf so a new reply becomes its sibling
+    pub async fn prepare_regenerate(state: &AppState, store: &ChatStore, session_id: &str, message_id: &str) -> AppResult<ChatSession> {
+        let session = Self::get_session(state, store, session_id).await?;
+        let reply = Self::find_message(&session, message_id)?;
+        let prompt_id = match (&reply.parent_id, reply.is_ai_message()) {
+            (Some(parent_id), true) => parent_id.clone(),
+            _ => {
+                return Err(AppError::validation_with_field(
+                    "NOT_AN_AI_REPLY",
+                    "Only AI replies to a prompt can be regenerated",
+                    "message_id",
+                ));
+            }
+        };
+        Self::set_active_leaf(state, store, session_id, prompt_id).await
+    }
+    
+    /// The active branch with the alternatives available at each message
+    pub fn thread(session: &ChatSession) -> Vec<ThreadMessage> {
+        session
+            .active_path()
+            .into_iter()
+            .map(|message| {
+                let sibling_ids: Vec<String> = session.siblings(&message.id).into_iter().map(|m| m.id.clone()).collect();
+                let sibling_index = sibling_ids.iter().position(|id| *id == message.id).unwrap_or(0);
+                ThreadMessage {
+                    message: message.clone(),
+                    sibling_ids,
+                    sibling_index,
+                }
+            })
+            .collect()
+    }
+    
+    // ================================
+    // AI REPLIES
+    // ================================
+    
+    /// Answer the active leaf of a session and record the reply as its child
+    pub async fn generate_reply(
+        app_handle: &tauri::AppHandle,
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+        context: Option<&ChatContext>,
+    ) -> AppResult<ChatMessage> {
+        let session = Self::get_session(state, store, session_id).await?;
+        let prompt_id = session.last_message().map(|m| m.id.clone());
+        
+        // Ask the model with the session history, then record its reply
+        let mut request = Self::build_ai_request(&session, context);
+        if let Some(note) = attachments::stale_note(&attachments::check_session(&session)) {
+            request.prompt.insert_str(0, &note);
+        }
+        let consensus = run_ai_request(app_handle, &request).await?;
+        let reply = Self::reply_message(&session, &request, &consensus).with_parent(prompt_id);
+        Self::append_branch_message(state, store, session_id, reply.clone()).await?;
+        
+        tracing::info!("🤖 Chat reply added to session {} ({} chars)", session_id, reply.content.len());
+        Self::spawn_compaction(app_handle, session_id);
+        Ok(reply)
+    }
+    
+    /// Compact in the background once a reply is stored, so background requests never delay the user's turn.
+    /// At most one compaction runs per session; replies stored meanwhile are picked up by the next one.
+    fn spawn_compaction(app_handle: &tauri::AppHandle, session_id: &str) {
+        let app_handle = app_handle.clone();
+        let session_id = session_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            let state = app_handle.state::<AppState>();
+            let store = app_handle.state::<ChatStore>();
+            if !state.compacting_sessions.lock().await.insert(session_id.clone()) {
+                return;
+            }
+            
+            // Compaction failures only cost context on later turns
+            if let Err(e) = Self::compact_if_needed(&app_handle, &state, &store, &session_id).await {
+                e.log_warn();
+            }
+            state.compacting_sessions.lock().await.remove(&session_id);
+        });
+    }
+    
+    /// Fold aged-out turns into the session summary once history passes the token threshold
+    pub async fn compact_if_needed(
+        app_handle: &tauri::AppHandle,
+        state: &AppState,
+        store: &ChatStore,
+        session_id: &str,
+    ) -> AppResult<Option<ChatMessage>> {
+        let session = Self::get_session(state, store, session_id).await?;
+        let turns = compaction::select_for_compaction(&session);
+        if turns.is_empty() {
+            return Ok(None);
+        }
+        
+        tracing::info!("🗜️ Compacting {} messages of chat session {}", turns.len(), session_id);
+        let request = compaction::summary_request(&session, &turns);
+        let result = run_ai_request(app_handle, &request).await?;
+        
+        let summary = match compaction::summary_message(&session, &turns, result.best_response.content) {
+            Some(summary) => summary,
+            None => return Ok(None),
+        };
+        
+        // The session may have moved on while the model was summarizing; never replace a newer summary
+        let record = SessionRecord::Summarized { summary: summary.clone() };
+        let applied = Self::apply_record_if(state, store, session_id, record, |session| {
+            if !compaction::supersedes(session, &summary) {
+                return false;
+            }
+            session.history_summary = Some(summary.clone());
+            true
+        }).await?;
+        Ok(applied.map(|_| summary))
+    }
+    
+    /// Build the AI request for the newest message in `session`, replaying earlier history
+    pub fn build_ai_request(session: &ChatSession, context: Option<&ChatContext>) -> AiRequest {
+        // Summarized turns are replaced by the summary; the rest is replayed verbatim
+        let pending = compaction::unsummarized(session);
+        let history = &pending[pending.len().saturating_sub(HISTORY_LIMIT + 1)..];
+        let (latest, earlier) = match history.split_last() {
+            Some((latest, earlier)) => (Some(*latest), earlier),
+            None => (None, history),
+        };
+        
+        let mut prompt = String::new();
+        if let Some(summary) = compaction::active_summary(session) {
+            prompt.push_str("Summary of earlier conversation:\n");
+            prompt.push_str(&summary.content);
+            prompt.push_str("\n\n");
+        }
+        if !earlier.is_empty() {
+            prompt.push_str("Conversation so far:\n");
+            for message in earlier.iter().filter(|m| !m.is_system_message()) {
+                let speaker = if message.is_user_message() { "User" } else { "Assistant" };
+                prompt.push_str(&format!("{}: {}\n", speaker, message.content));
+            }
+            prompt.push('\n');
+        }
+        
+        if let Some(context) = context {
+            if let Some(file) = &context.active_file {
+                match &context.cursor_position {
+                    Some(cursor) => prompt.push_str(&format!("Active file: {} (line {}, column {})\n", file, cursor.line, cursor.column)),
+                    None => prompt.push_str(&format!("Active file: {}\n", file)),
+                }
+            }
+            if context.active_file.is_some() {
+                prompt.push('\n');
+            }
+        }
+        
+        // Attached code travels as captured at send time, not as the file reads today
+        if let Some(latest) = latest {
+            for attachment in latest.attachments() {
+                if let Some(snapshot) = &attachment.snapshot {
+                    prompt.push_str(&format!("Attached {} {}:\n```\n{}\n```\n\n", attachment.kind.label(), attachment.location(), snapshot.trim_end()));
+                }
+            }
+            prompt.push_str(&latest.content);
+        }
+        
+        let request = AiRequest::new(generate_id(), prompt)
+            .with_images(latest.map(|m| m.images().into_iter().cloned().collect()).unwrap_or_default());
+        Self::prepare_request(session, request, context)
+    }
+    
+    /// Apply the session's project context and the user's AI preferences to an interactive request
+    pub fn prepare_request(session: &ChatSession, request: AiRequest, context: Option<&ChatContext>) -> AiRequest {
+        let preferences = context
+            .and_then(|c| c.ai_preferences.clone())
+            .unwrap_or_else(AiPreferences::default);
+        
+        // Without project context the model only learns where the project lives
+        let project_context = if preferences.include_project_context {
+            session.context.clone()
+        } else {
+            ProjectContext::new(session.context.root_path.clone(), "unknown")
+        };
+        
+        let mut request = request
+            .with_context(project_context)
+            .with_priority(RequestPriority::Interactive);
+        if let Some(provider) = preferences.preferred_provider {
+            request = request.with_provider(provider);
+        }
+        if let Some(max_tokens) = preferences.max_tokens {
+            request = request.with_max_tokens(max_tokens);
+        }
+        if let Some(temperature) = preferences.temperature {
+            request = request.with_temperature(temperature);
+        }
+        request
+    }
+    
+    /// AI reply message carrying provider, token, cost and latency metadata
+    pub fn reply_message(session: &ChatSession, request: &AiRequest, result: &ConsensusResult) -> ChatMessage {
+        let response = &result.best_response;
+        let prompt_tokens = estimate_tokens(&request.prompt);
+        let completion_tokens = estimate_tokens(&response.content);
+        
+        let mut metadata = HashMap::new();
+        metadata.insert("request_id".to_string(), serde_json::json!(request.id));
+        metadata.insert("provider".to_string(), serde_json::json!(response.provider));
+        metadata.insert("prompt_tokens".to_string(), serde_json::json!(prompt_tokens));
+        metadata.insert("completion_tokens".to_string(), serde_json::json!(completion_tokens));
+        metadata.insert("total_tokens".to_string(), serde_json::json!(prompt_tokens + completion_tokens));
+        metadata.insert("cost".to_string(), serde_json::json!(result.total_cost));
+        metadata.insert("latency_ms".to_string(), serde_json::json!(response.response_time));
+        metadata.insert("confidence".to_string(), serde_json::json!(result.confidence_score));
+        
+        // Which turns the model only saw in summarized form
+        if let Some(summary) = compaction::active_summary(session) {
+            metadata.insert("summary_message_id".to_string(), serde_json::json!(summary.id));
+            metadata.insert(
+                "summarized_message_ids".to_string(),
+                serde_json::json!(compaction::summarized_message_ids(summary)),
+            );
+        }
+        
+        ChatMessage::ai_message(response.content.clone()).with_metadata(metadata)
+    }
+}
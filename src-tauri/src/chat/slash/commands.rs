@@ -0,0 +1,63 @@
+// Syntari AI IDE - Slash Command Commands
+// Chat command listing and execution exposed to the frontend
+
+use std::path::PathBuf;
+use tauri::State;
+use crate::core::{AppResult, AppState, TauriResult, app_data_subdir};
+use crate::chat::service::ChatService;
+use crate::chat::store::ChatStore;
+use crate::chat::types::ChatContext;
+use crate::chat::slash::handlers::execute;
+use crate::chat::slash::registry::SlashCommandRegistry;
+use crate::chat::slash::types::{SlashCommandResult, SlashCommandSpec};
+
+/// User commands live in `<app data>/chat-commands/commands.json`
+const USER_COMMAND_DIR: &str = "chat-commands";
+const USER_COMMAND_FILE: &str = "commands.json";
+
+fn load_registry(app_handle: &tauri::AppHandle, project_root: Option<&str>) -> AppResult<SlashCommandRegistry> {
+    let user_config = app_data_subdir(app_handle, USER_COMMAND_DIR)?.join(USER_COMMAND_FILE);
+    Ok(SlashCommandRegistry::load(Some(&user_config), project_root.map(PathBuf::from).as_deref()))
+}
+
+/// Commands available for autocomplete in the chat box
+#[tauri::command]
+pub async fn list_chat_commands(
+    project_path: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<Vec<SlashCommandSpec>>, String> {
+    let result = async {
+        let project_root = match project_path {
+            Some(path) => Some(path),
+            None => state.get_current_project().await.map(|p| p.root_path),
+        };
+        load_registry(&app_handle, project_root.as_deref()).map(|registry| registry.list())
+    }.await;
+
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn run_chat_command(
+    session_id: String,
+    input: String,
+    context: Option<ChatContext>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    store: State<'_, ChatStore>,
+) -> std::result::Result<TauriResult<SlashCommandResult>, String> {
+    let result = async {
+        let session = ChatService::get_session(&state, &store, &session_id).await?;
+        let registry = load_registry(&app_handle, Some(&session.context.root_path))?;
+        execute(&app_handle, &state, &store, &registry, &session_id, &input, context.as_ref()).await
+    }.await;
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
@@ -0,0 +1,209 @@
+// Syntari AI IDE - Slash Command Handlers
+// Gathers context for a chat command, builds its prompt and records the typed result
+
+use std::collections::HashMap;
+use tauri::Manager;
+use crate::core::{AppError, AppResult, AppState, app_data_subdir, generate_id};
+use crate::ai::commands::run_ai_request;
+use crate::ai::prompts::library::parse_template;
+use crate::ai::prompts::renderer::render;
+use crate::ai::prompts::types::{PromptContext, TemplateScope};
+use crate::ai::semantic::SemanticIndexManager;
+use crate::ai::semantic::commands::INDEX_DIR;
+use crate::ai::types::AiRequest;
+use crate::chat::attachments::capture_selection;
+use crate::chat::multimodal::parts_to_text;
+use crate::chat::service::ChatService;
+use crate::chat::store::ChatStore;
+use crate::chat::slash::registry::SlashCommandRegistry;
+use crate::chat::slash::types::{ContextScope, ParsedSlashCommand, SlashCommandKind, SlashCommandResult, SlashCommandSource, SlashCommandSpec};
+use crate::chat::types::{ChatContext, ChatMessage, ChatSession, MessagePart};
+
+const SEARCH_RESULT_COUNT: usize = 8;
+
+/// Run a `/command` line in a session: the line is stored as the user's turn and the result as its reply
+pub async fn execute(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    store: &ChatStore,
+    registry: &SlashCommandRegistry,
+    session_id: &str,
+    input: &str,
+    context: Option<&ChatContext>,
+) -> AppResult<SlashCommandResult> {
+    let (command, parsed) = registry.parse(input)?;
+    let session = ChatService::get_session(state, store, session_id).await?;
+
+    // Context is gathered up front so a command that cannot run leaves the session untouched
+    let (prompt, sources) = match &command.kind {
+        SlashCommandKind::Help => (None, None),
+        SlashCommandKind::Search => {
+            let (prompt, sources) = search_prompt(app_handle, &session, &parsed).await?;
+            (prompt, Some(sources))
+        }
+        SlashCommandKind::Prompt { body, scope } => {
+            (Some(render_prompt(command, body, *scope, &parsed, context, &session)?), None)
+        }
+    };
+
+    let command_line = ChatService::append_message(state, store, session_id, command_message(command, &parsed, input, context)).await?;
+    let prompt_id = command_line.last_message().map(|message| message.id.clone());
+
+    let reply = match prompt {
+        Some(prompt) => {
+            let request = ChatService::prepare_request(&session, AiRequest::new(generate_id(), prompt), context);
+            let consensus = run_ai_request(app_handle, &request).await?;
+            ChatService::reply_message(&session, &request, &consensus)
+        }
+        None if sources.is_some() => ChatMessage::system_message(format!(
+            "No indexed code matched \"{}\".",
+            parsed.arguments.get("query").map(String::as_str).unwrap_or_default()
+        )),
+        None => ChatMessage::system_message(registry.help_text()),
+    };
+
+    let mut metadata = reply.metadata.clone().unwrap_or_default();
+    metadata.insert("slash_command".to_string(), serde_json::json!(command.name));
+    metadata.insert("result_type".to_string(), serde_json::json!(command.result_type));
+    if let Some(sources) = sources {
+        metadata.insert("sources".to_string(), serde_json::Value::Array(sources));
+    }
+    let reply = reply.with_metadata(metadata).with_parent(prompt_id);
+    ChatService::append_branch_message(state, store, session_id, reply.clone()).await?;
+
+    tracing::info!("⚡ Ran /{} in session {}", command.name, session_id);
+    Ok(SlashCommandResult {
+        command: command.name.clone(),
+        result_type: command.result_type.clone(),
+        message: reply,
+    })
+}
+
+/// The user's turn for a command, keeping the selection it ran against
+fn command_message(command: &SlashCommandSpec, parsed: &ParsedSlashCommand, input: &str, context: Option<&ChatContext>) -> ChatMessage {
+    let mut metadata = HashMap::new();
+    metadata.insert("slash_command".to_string(), serde_json::json!(command.name));
+    metadata.insert("arguments".to_string(), serde_json::json!(parsed.arguments));
+
+    let selection = match command.kind {
+        SlashCommandKind::Prompt { scope: ContextScope::Selection, .. } => context.and_then(capture_selection),
+        _ => None,
+    };
+    let message = match selection {
+        Some(selection) => {
+            let parts = vec![MessagePart::Text { text: input.trim().to_string() }, MessagePart::Attachment(selection)];
+            ChatMessage::user_message(parts_to_text(&parts)).with_parts(parts)
+        }
+        None => ChatMessage::user_message(input.trim()),
+    };
+    message.with_metadata(metadata)
+}
+
+// ================================
+// PROMPT COMMANDS
+// ================================
+
+fn render_prompt(
+    command: &SlashCommandSpec,
+    body: &str,
+    scope: ContextScope,
+    parsed: &ParsedSlashCommand,
+    context: Option<&ChatContext>,
+    session: &ChatSession,
+) -> AppResult<String> {
+    let template_scope = match command.source {
+        SlashCommandSource::Builtin => TemplateScope::Builtin,
+        SlashCommandSource::User => TemplateScope::User,
+        SlashCommandSource::Project => TemplateScope::Project,
+    };
+    let mut template = parse_template(&command.name, body, template_scope, None);
+
+    // Argument placeholders follow the command's own required flags
+    for variable in &mut template.variables {
+        if let Some(argument) = command.arguments.iter().find(|a| a.name == variable.name) {
+            variable.required = argument.required;
+        }
+    }
+
+    let mut prompt_context = context.map(PromptContext::from).unwrap_or_default();
+    if scope == ContextScope::Repository {
+        prompt_context.active_file = None;
+        prompt_context.selected_text = None;
+        prompt_context.cursor_position = None;
+    }
+    prompt_context.values = parsed.arguments.clone();
+
+    let rendered = render(&template, &prompt_context, Some(&session.context));
+    if !rendered.is_complete() {
+        let needs: Vec<String> = rendered
+            .missing_variables
+            .iter()
+            .map(|name| match name.as_str() {
+                "selection" | "file" => "a selection or an open file".to_string(),
+                other => other.to_string(),
+            })
+            .collect();
+        return Err(AppError::validation_with_field(
+            "MISSING_COMMAND_CONTEXT",
+            &format!("/{} needs {}", command.name, needs.join(", ")),
+            "context",
+        ));
+    }
+    if scope == ContextScope::Repository
+        && template.variables.iter().any(|v| v.name == "git_diff")
+        && rendered.values.get("git_diff").is_none_or(|diff| diff.trim().is_empty())
+    {
+        return Err(AppError::validation("NO_CHANGES", "There are no uncommitted changes to review"));
+    }
+
+    // Arguments the body does not reference are passed on as extra instructions
+    let mut prompt = rendered.prompt.trim_end().to_string();
+    for argument in &command.arguments {
+        let unused = !template.variables.iter().any(|v| v.name == argument.name);
+        if let Some(value) = parsed.arguments.get(&argument.name).filter(|v| unused && !v.trim().is_empty()) {
+            prompt.push_str(&format!("\n\n{}: {}", argument.description, value.trim()));
+        }
+    }
+    Ok(prompt)
+}
+
+// ================================
+// SEARCH COMMAND
+// ================================
+
+/// Semantic search over the session's project; no prompt is built when nothing matches
+async fn search_prompt(
+    app_handle: &tauri::AppHandle,
+    session: &ChatSession,
+    parsed: &ParsedSlashCommand,
+) -> AppResult<(Option<String>, Vec<serde_json::Value>)> {
+    let query = parsed.arguments.get("query").map(|q| q.trim()).unwrap_or_default();
+    let manager = app_handle.state::<SemanticIndexManager>();
+    let index_root = app_data_subdir(app_handle, INDEX_DIR)?;
+    let hits = manager.search(&session.context.root_path, &index_root, query, SEARCH_RESULT_COUNT).await?;
+    if hits.is_empty() {
+        return Ok((None, Vec::new()));
+    }
+
+    let mut prompt = format!(
+        "Answer the request \"{}\" using these search results from the project. Cite the files and lines you rely on.\n",
+        query
+    );
+    let mut sources = Vec::with_capacity(hits.len());
+    for hit in &hits {
+        let chunk = &hit.chunk;
+        let symbol = chunk.symbol.as_deref().map(|s| format!(" ({})", s)).unwrap_or_default();
+        prompt.push_str(&format!(
+            "\n### {}:{}-{}{}\n```{}\n{}\n```\n",
+            chunk.file_path, chunk.start_line, chunk.end_line, symbol, chunk.language, chunk.content.trim_end()
+        ));
+        sources.push(serde_json::json!({
+            "path": chunk.file_path,
+            "start_line": chunk.start_line,
+            "end_line": chunk.end_line,
+            "symbol": chunk.symbol,
+            "score": hit.score,
+        }));
+    }
+    Ok((Some(prompt), sources))
+}
@@ -0,0 +1,10 @@
+// Syntari AI IDE - Chat Slash Commands
+// `/explain`, `/tests`, `/search` and friends, plus commands defined in configuration
+
+pub mod types;
+pub mod registry;
+pub mod handlers;
+pub mod commands;
+
+pub use types::{SlashCommandSpec, SlashCommandResult, SlashCommandConfig, CustomSlashCommand};
+pub use registry::SlashCommandRegistry;
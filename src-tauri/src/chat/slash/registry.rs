@@ -0,0 +1,356 @@
+// Syntari AI IDE - Slash Command Registry
+// Built-in chat commands plus user and project commands, with argument parsing
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use crate::core::{AppError, AppResult};
+use crate::chat::slash::types::{
+    ContextScope, CustomSlashCommand, ParsedSlashCommand, SlashArgument, SlashCommandConfig, SlashCommandKind,
+    SlashCommandSource, SlashCommandSpec,
+};
+
+/// Project commands live in `<root>/.syntari/commands.json`
+pub const PROJECT_COMMAND_FILE: &str = ".syntari/commands.json";
+
+const EXPLAIN_PROMPT: &str = r#"Explain the following {{language}} code from `{{file}}`.
+Describe what it does, how it works and any non-obvious behaviour or edge cases.
+
+```{{language}}
+{{selection}}
+```"#;
+
+const TESTS_PROMPT: &str = r#"Write unit tests for the following {{language}} code from `{{file}}`.
+Follow the testing conventions already used in the project and cover edge cases and error paths.
+
+```{{language}}
+{{selection}}
+```"#;
+
+const FIX_PROMPT: &str = r#"Fix the problems in the following {{language}} code from `{{file}}`.
+Return the corrected code and explain each change briefly.
+
+```{{language}}
+{{selection}}
+```
+
+Reported diagnostics:
+{{diagnostics}}"#;
+
+const DOC_PROMPT: &str = r#"Write documentation comments for the following {{language}} code from `{{file}}`.
+Follow the documentation conventions of {{language}} and of the surrounding code; do not change behaviour.
+
+```{{language}}
+{{selection}}
+```"#;
+
+const GIT_DIFF_PROMPT: &str = r#"Review these uncommitted changes.
+Summarize what changed, point out bugs or risky edits, and suggest a commit message.
+
+```diff
+{{git_diff}}
+```"#;
+
+fn argument(name: &str, description: &str, required: bool) -> SlashArgument {
+    SlashArgument {
+        name: name.to_string(),
+        description: description.to_string(),
+        required,
+    }
+}
+
+fn builtin(name: &str, description: &str, result_type: &str, arguments: Vec<SlashArgument>, kind: SlashCommandKind) -> SlashCommandSpec {
+    SlashCommandSpec {
+        name: name.to_string(),
+        aliases: Vec::new(),
+        description: description.to_string(),
+        arguments,
+        result_type: result_type.to_string(),
+        source: SlashCommandSource::Builtin,
+        path: None,
+        kind,
+    }
+}
+
+fn prompt(body: &str, scope: ContextScope) -> SlashCommandKind {
+    SlashCommandKind::Prompt { body: body.to_string(), scope }
+}
+
+fn builtin_commands() -> Vec<SlashCommandSpec> {
+    vec![
+        builtin(
+            "explain",
+            "Explain the selected code",
+            "explanation",
+            vec![argument("question", "What to focus on", false)],
+            prompt(EXPLAIN_PROMPT, ContextScope::Selection),
+        ),
+        builtin(
+            "tests",
+            "Write unit tests for the selected code",
+            "tests",
+            vec![argument("framework", "Test framework to use", false)],
+            prompt(TESTS_PROMPT, ContextScope::Selection),
+        ),
+        builtin(
+            "fix",
+            "Fix the selected code using the reported diagnostics",
+            "fix",
+            vec![argument("problem", "Description of what is wrong", false)],
+            prompt(FIX_PROMPT, ContextScope::Selection),
+        ),
+        builtin(
+            "doc",
+            "Write documentation comments for the selected code",
+            "documentation",
+            Vec::new(),
+            prompt(DOC_PROMPT, ContextScope::Selection),
+        ),
+        builtin(
+            "search",
+            "Search the project and answer from the matching code",
+            "search",
+            vec![argument("query", "What to look for", true)],
+            SlashCommandKind::Search,
+        ),
+        builtin(
+            "git diff",
+            "Review uncommitted changes in the repository",
+            "git_diff",
+            vec![argument("focus", "What the review should concentrate on", false)],
+            prompt(GIT_DIFF_PROMPT, ContextScope::Repository),
+        ),
+        builtin("help", "List the available commands", "help", Vec::new(), SlashCommandKind::Help),
+    ]
+}
+
+pub struct SlashCommandRegistry {
+    commands: BTreeMap<String, SlashCommandSpec>,
+}
+
+impl SlashCommandRegistry {
+    /// Load every command visible for a project; later sources override earlier ones by name
+    pub fn load(user_config: Option<&Path>, project_root: Option<&Path>) -> Self {
+        let mut commands = BTreeMap::new();
+
+        for command in builtin_commands() {
+            commands.insert(command.name.clone(), command);
+        }
+
+        if let Some(path) = user_config {
+            for command in load_config(path, SlashCommandSource::User) {
+                commands.insert(command.name.clone(), command);
+            }
+        }
+
+        if let Some(root) = project_root {
+            for command in load_config(&root.join(PROJECT_COMMAND_FILE), SlashCommandSource::Project) {
+                commands.insert(command.name.clone(), command);
+            }
+        }
+
+        Self { commands }
+    }
+
+    pub fn list(&self) -> Vec<SlashCommandSpec> {
+        self.commands.values().cloned().collect()
+    }
+
+    /// Resolve `/name args...` to a command, preferring the longest name and exact names over aliases
+    pub fn parse(&self, input: &str) -> AppResult<(&SlashCommandSpec, ParsedSlashCommand)> {
+        let line = input.trim().strip_prefix('/').ok_or_else(|| {
+            AppError::validation_with_field("NOT_A_COMMAND", "Chat commands start with '/'", "input")
+        })?;
+        let words: Vec<String> = line.split_whitespace().map(|word| word.to_lowercase()).collect();
+
+        let mut best: Option<(&SlashCommandSpec, usize, bool)> = None;
+        for command in self.commands.values() {
+            let names = std::iter::once((&command.name, true)).chain(command.aliases.iter().map(|alias| (alias, false)));
+            for (name, exact) in names {
+                let name_words: Vec<String> = name.split_whitespace().map(|word| word.to_lowercase()).collect();
+                if name_words.is_empty() || !words.starts_with(&name_words) {
+                    continue;
+                }
+                let better = match best {
+                    Some((_, length, was_exact)) => (name_words.len(), exact) > (length, was_exact),
+                    None => true,
+                };
+                if better {
+                    best = Some((command, name_words.len(), exact));
+                }
+            }
+        }
+
+        let (command, name_length, _) = best.ok_or_else(|| {
+            let name = line.split_whitespace().next().unwrap_or_default();
+            AppError::validation_with_field(
+                "UNKNOWN_COMMAND",
+                &format!("Unknown command '/{}'. Type /help to see the available commands.", name),
+                "input",
+            )
+        })?;
+
+        let arguments = parse_arguments(command, skip_words(line, name_length))?;
+        Ok((command, ParsedSlashCommand { name: command.name.clone(), arguments }))
+    }
+
+    /// Markdown listing of every command with its usage
+    pub fn help_text(&self) -> String {
+        let mut text = String::from("Available commands:\n\n");
+        for command in self.commands.values() {
+            text.push_str(&format!("- `{}` — {}", command.usage(), command.description));
+            if !command.aliases.is_empty() {
+                let aliases: Vec<String> = command.aliases.iter().map(|alias| format!("/{}", alias)).collect();
+                text.push_str(&format!(" (also {})", aliases.join(", ")));
+            }
+            text.push('\n');
+            for argument in &command.arguments {
+                text.push_str(&format!("  - `{}`: {}\n", argument.name, argument.description));
+            }
+        }
+        text
+    }
+}
+
+// ================================
+// CONFIGURATION
+// ================================
+
+fn load_config(path: &Path, source: SlashCommandSource) -> Vec<SlashCommandSpec> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+
+    let config: SlashCommandConfig = match serde_json::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!("Ignoring chat command config {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    config
+        .commands
+        .into_iter()
+        .filter_map(|command| match validate_name(&command.name) {
+            Ok(()) => Some(custom_spec(command, source, path)),
+            Err(reason) => {
+                tracing::warn!("Skipping chat command '{}' in {}: {}", command.name, path.display(), reason);
+                None
+            }
+        })
+        .collect()
+}
+
+fn custom_spec(command: CustomSlashCommand, source: SlashCommandSource, path: &Path) -> SlashCommandSpec {
+    let name = normalize_name(&command.name);
+    SlashCommandSpec {
+        description: command.description.unwrap_or_else(|| format!("Run /{}", name)),
+        aliases: command.aliases.iter().map(|alias| normalize_name(alias)).filter(|alias| !alias.is_empty()).collect(),
+        result_type: command.result_type.unwrap_or_else(|| name.replace(' ', "_")),
+        arguments: command.arguments,
+        source,
+        path: Some(path.to_string_lossy().to_string()),
+        kind: SlashCommandKind::Prompt { body: command.prompt, scope: command.scope },
+        name,
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().trim_start_matches('/').split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn validate_name(name: &str) -> Result<(), &'static str> {
+    let name = normalize_name(name);
+    if name.is_empty() {
+        return Err("name is empty");
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' ')) {
+        return Err("names may only contain letters, digits, '-', '_' and spaces");
+    }
+    Ok(())
+}
+
+// ================================
+// ARGUMENT PARSING
+// ================================
+
+/// Assign words to positional arguments; quoted words may contain spaces and the last argument takes the rest
+fn parse_arguments(command: &SlashCommandSpec, mut rest: &str) -> AppResult<HashMap<String, String>> {
+    let mut values = HashMap::new();
+
+    for (index, argument) in command.arguments.iter().enumerate() {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let value = if index + 1 == command.arguments.len() {
+            let value = unquote(rest.trim_end()).to_string();
+            rest = "";
+            value
+        } else {
+            let (value, remainder) = next_word(rest);
+            rest = remainder;
+            value
+        };
+        values.insert(argument.name.clone(), value);
+    }
+
+    let missing: Vec<&str> = command
+        .arguments
+        .iter()
+        .filter(|argument| argument.required && values.get(&argument.name).is_none_or(|v| v.trim().is_empty()))
+        .map(|argument| argument.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::validation_with_field(
+            "MISSING_COMMAND_ARGUMENT",
+            &format!("Missing {}. Usage: {}", missing.join(", "), command.usage()),
+            "input",
+        ));
+    }
+    if !rest.trim().is_empty() {
+        return Err(AppError::validation_with_field(
+            "UNEXPECTED_COMMAND_ARGUMENT",
+            &format!("/{} takes no arguments. Usage: {}", command.name, command.usage()),
+            "input",
+        ));
+    }
+
+    Ok(values)
+}
+
+/// One word, or a `"..."` / `'...'` quoted phrase, and the text after it
+fn next_word(text: &str) -> (String, &str) {
+    let mut chars = text.char_indices();
+    if let Some((_, quote @ ('"' | '\''))) = chars.next() {
+        if let Some(end) = text[1..].find(quote) {
+            return (text[1..end + 1].to_string(), &text[end + 2..]);
+        }
+    }
+    match text.find(char::is_whitespace) {
+        Some(end) => (text[..end].to_string(), &text[end..]),
+        None => (text.to_string(), ""),
+    }
+}
+
+fn unquote(text: &str) -> &str {
+    for quote in ['"', '\''] {
+        if text.len() >= 2 && text.starts_with(quote) && text.ends_with(quote) && !text[1..text.len() - 1].contains(quote) {
+            return &text[1..text.len() - 1];
+        }
+    }
+    text
+}
+
+/// The text after the first `count` whitespace-separated words
+fn skip_words(text: &str, count: usize) -> &str {
+    let mut rest = text.trim_start();
+    for _ in 0..count {
+        rest = match rest.find(char::is_whitespace) {
+            Some(end) => rest[end..].trim_start(),
+            None => "",
+        };
+    }
+    rest
+}
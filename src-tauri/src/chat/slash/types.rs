@@ -0,0 +1,126 @@
+// Syntari AI IDE - Slash Command Types
+// Command specifications, configuration format and execution results
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::chat::types::ChatMessage;
+
+// ================================
+// COMMAND SPECIFICATIONS
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashCommandSource {
+    Builtin,
+    User,
+    Project,
+}
+
+/// Which editor state a prompt command sees
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextScope {
+    /// The selection or active file
+    #[default]
+    Selection,
+    /// The whole repository; the active file and selection are ignored
+    Repository,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlashCommandKind {
+    /// Render a prompt template body and send it to the model
+    Prompt { body: String, scope: ContextScope },
+    /// Answer from semantic code search results
+    Search,
+    /// List the available commands without calling the model
+    Help,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashArgument {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashCommandSpec {
+    /// Invocation name without the slash; may span words, e.g. `git diff`
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+    /// Positional arguments; the last one takes the rest of the line
+    pub arguments: Vec<SlashArgument>,
+    /// Kind of result appended to the session, stored in message metadata
+    pub result_type: String,
+    pub source: SlashCommandSource,
+    pub path: Option<String>,
+    pub kind: SlashCommandKind,
+}
+
+impl SlashCommandSpec {
+    /// `/name <required> [optional]`
+    pub fn usage(&self) -> String {
+        let mut usage = format!("/{}", self.name);
+        for argument in &self.arguments {
+            if argument.required {
+                usage.push_str(&format!(" <{}>", argument.name));
+            } else {
+                usage.push_str(&format!(" [{}]", argument.name));
+            }
+        }
+        usage
+    }
+}
+
+// ================================
+// CONFIGURATION TYPES
+// ================================
+
+/// Contents of `chat-commands/commands.json` in app data (user) or `.syntari/commands.json` (project)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlashCommandConfig {
+    #[serde(default)]
+    pub commands: Vec<CustomSlashCommand>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomSlashCommand {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<SlashArgument>,
+    /// Template body; may use `{{selection}}`, `{{file}}`, `{{language}}`,
+    /// `{{diagnostics}}`, `{{git_diff}}` and the command's argument names
+    pub prompt: String,
+    #[serde(default)]
+    pub scope: ContextScope,
+    #[serde(default)]
+    pub result_type: Option<String>,
+}
+
+// ================================
+// EXECUTION TYPES
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedSlashCommand {
+    pub name: String,
+    pub arguments: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashCommandResult {
+    pub command: String,
+    pub result_type: String,
+    /// The message appended to the session for this command
+    pub message: ChatMessage,
+}
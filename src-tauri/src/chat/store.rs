@@ -0,0 +1,429 @@
+// Syntari AI IDE - Chat Session Store
+// Crash-safe persistence: one append-only JSONL log per session plus an atomic index
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use crate::core::{AppError, AppResult, write_file_atomic};
+use crate::chat::search::{ChatSearchHit, ChatSearchIndex, ChatSearchQuery};
+use crate::chat::types::{ChatMessage, ChatSession, ChatSessionSummary};
+use crate::project::types::ProjectContext;
+
+const INDEX_FILE: &str = "index.json";
+const INDEX_VERSION: u32 = 1;
+const LOG_EXTENSION: &str = "jsonl";
+
+/// One line of a session log; replayed in order to rebuild the session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SessionRecord {
+    Created {
+        id: String,
+        name: String,
+        context: Box<ProjectContext>,
+        created_at: u64,
+        /// Logs written before branching have no parent links and replay as one thread
+        #[serde(default)]
+        branching: bool,
+    },
+    Message { message: ChatMessage },
+    Renamed { name: String, at: u64 },
+    Cleared { at: u64 },
+    Archived { archived: bool, at: u64 },
+    Summarized { summary: ChatMessage },
+    /// Switch the displayed branch
+    ActiveLeaf { message_id: String },
+}
+
+impl SessionRecord {
+    fn apply(self, session: &mut Option<ChatSession>, linear: &mut bool) {
+        match (self, session.as_mut()) {
+            (SessionRecord::Created { id, name, context, created_at, branching }, None) => {
+                *linear = !branching;
+                *session = Some(ChatSession {
+                    id,
+                    name,
+                    messages: Vec::new(),
+                    context: *context,
+                    created_at,
+                    updated_at: created_at,
+                    archived: false,
+                    history_summary: None,
+                    active_leaf: None,
+                });
+            }
+            (SessionRecord::Message { message }, Some(current)) => {
+                current.updated_at = current.updated_at.max(message.timestamp);
+                if *linear {
+                    current.insert_message(message);
+                } else {
+                    current.attach_message(message);
+                }
+            }
+            (SessionRecord::Renamed { name, at }, Some(current)) => {
+                current.name = name;
+                current.updated_at = current.updated_at.max(at);
+            }
+            (SessionRecord::Cleared { at }, Some(current)) => {
+                current.messages.clear();
+                current.history_summary = None;
+                current.active_leaf = None;
+                current.updated_at = current.updated_at.max(at);
+            }
+            (SessionRecord::Archived { archived, at }, Some(current)) => {
+                current.archived = archived;
+                current.updated_at = current.updated_at.max(at);
+            }
+            (SessionRecord::Summarized { summary }, Some(current)) => {
+                current.history_summary = Some(summary);
+            }
+            (SessionRecord::ActiveLeaf { message_id }, Some(current)) => {
+                current.active_leaf = Some(message_id);
+            }
+            (record, _) => tracing::warn!("Ignoring out-of-order session record: {:?}", record),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ChatIndex {
+    version: u32,
+    sessions: BTreeMap<String, ChatSessionSummary>,
+}
+
+/// Registered as Tauri state; only the index is read at startup, logs load on demand
+pub struct ChatStore {
+    root: PathBuf,
+    index: Mutex<ChatIndex>,
+    // Built from every log on the first search, then kept current by writes
+    search: Mutex<Option<ChatSearchIndex>>,
+}
+
+impl ChatStore {
+    pub fn open(root: PathBuf) -> Self {
+        if let Err(e) = std::fs::create_dir_all(&root) {
+            tracing::error!("Failed to create chat store at {}: {}", root.display(), e);
+        }
+
+        let store = Self {
+            index: Mutex::new(ChatIndex::default()),
+            search: Mutex::new(None),
+            root,
+        };
+        let index = store.load_index();
+        tracing::info!("💬 Chat store opened with {} sessions", index.sessions.len());
+        if let Ok(mut current) = store.index.lock() {
+            *current = index;
+        }
+        store
+    }
+
+    /// Open the store under the app data directory, falling back to the temp dir
+    pub fn open_for_app(app_handle: &tauri::AppHandle) -> Self {
+        let root = crate::core::app_data_subdir(app_handle, "chat-sessions").unwrap_or_else(|e| {
+            e.log_error();
+            std::env::temp_dir().join("syntari-chat-sessions")
+        });
+        Self::open(root)
+    }
+
+    /// Session IDs come from the frontend, so anything but a generated ID could name a path outside the store
+    fn log_path(&self, session_id: &str) -> AppResult<PathBuf> {
+        let valid = !session_id.is_empty() && session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(AppError::chat_with_session("INVALID_SESSION_ID", "Invalid chat session ID", session_id));
+        }
+        Ok(self.root.join(format!("{}.{}", session_id, LOG_EXTENSION)))
+    }
+
+    fn lock_index(&self) -> AppResult<std::sync::MutexGuard<'_, ChatIndex>> {
+        self.index
+            .lock()
+            .map_err(|_| AppError::internal("CHAT_STORE_POISONED", "Chat store index is poisoned"))
+    }
+
+    // ================================
+    // WRITES
+    // ================================
+
+    pub fn create(&self, session: &ChatSession) -> AppResult<()> {
+        let mut log = Vec::new();
+        push_line(&mut log, &SessionRecord::Created {
+            id: session.id.clone(),
+            name: session.name.clone(),
+            context: Box::new(session.context.clone()),
+            created_at: session.created_at,
+            branching: true,
+        })?;
+        for message in &session.messages {
+            push_line(&mut log, &SessionRecord::Message { message: message.clone() })?;
+        }
+        if session.active_leaf.is_some() && session.active_leaf.as_ref() != session.messages.last().map(|m| &m.id) {
+            push_line(&mut log, &SessionRecord::ActiveLeaf { message_id: session.active_leaf.clone().unwrap_or_default() })?;
+        }
+        if let Some(summary) = &session.history_summary {
+            push_line(&mut log, &SessionRecord::Summarized { summary: summary.clone() })?;
+        }
+        if session.archived {
+            push_line(&mut log, &SessionRecord::Archived { archived: true, at: session.updated_at })?;
+        }
+
+        // The header is written atomically so a session file is never half-created
+        write_file_atomic(&self.log_path(&session.id)?, &log)?;
+        self.with_search_index(|search| {
+            search.remove_session(&session.id);
+            search.add_session(session);
+        });
+        self.update_index(session)
+    }
+
+    /// Append a record to the session's log, then refresh its index entry
+    pub fn append(&self, session: &ChatSession, record: &SessionRecord) -> AppResult<()> {
+        let path = self.log_path(&session.id)?;
+        if !path.exists() {
+            return self.create(session);
+        }
+
+        let mut line = Vec::new();
+        push_line(&mut line, record)?;
+        append_durably(&path, &line)?;
+
+        match record {
+            SessionRecord::Message { message } => self.with_search_index(|search| search.add_message(session, message)),
+            SessionRecord::Cleared { .. } => self.with_search_index(|search| search.remove_session(&session.id)),
+            _ => {}
+        }
+        self.update_index(session)
+    }
+
+    /// Remove a session's log and index entry
+    pub fn delete(&self, session_id: &str) -> AppResult<()> {
+        let path = self.log_path(session_id)?;
+        {
+            let mut index = self.lock_index()?;
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+            index.sessions.remove(session_id);
+            self.save_index(&index)?;
+        }
+        // Never held together with the index lock, which `search` takes first
+        self.with_search_index(|search| search.remove_session(session_id));
+        Ok(())
+    }
+
+    fn update_index(&self, session: &ChatSession) -> AppResult<()> {
+        let mut index = self.lock_index()?;
+        index.sessions.insert(session.id.clone(), ChatSessionSummary::from_session(session));
+        self.save_index(&index)
+    }
+
+    fn save_index(&self, index: &ChatIndex) -> AppResult<()> {
+        let bytes = serde_json::to_vec_pretty(index)?;
+        write_file_atomic(&self.root.join(INDEX_FILE), &bytes)
+    }
+
+    // ================================
+    // READS
+    // ================================
+
+    pub fn contains(&self, session_id: &str) -> bool {
+        self.index.lock().map(|index| index.sessions.contains_key(session_id)).unwrap_or(false)
+    }
+
+    /// Replay a session's log from disk
+    pub fn load(&self, session_id: &str) -> AppResult<ChatSession> {
+        let path = self.log_path(session_id)?;
+        let session = read_log(&path)?.ok_or_else(|| {
+            AppError::chat_with_session("SESSION_NOT_FOUND", "Chat session not found", session_id)
+        })?;
+        Ok(session)
+    }
+
+    /// Summaries, newest first, optionally limited to one project
+    pub fn list(&self, project_root: Option<&str>, include_archived: bool) -> Vec<ChatSessionSummary> {
+        let mut sessions: Vec<ChatSessionSummary> = match self.index.lock() {
+            Ok(index) => index
+                .sessions
+                .values()
+                .filter(|s| project_root.is_none_or(|root| s.project_root == root))
+                .filter(|s| include_archived || !s.archived)
+                .cloned()
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+        sessions
+    }
+
+    /// Summaries grouped by `ProjectContext.root_path`
+    pub fn grouped_by_project(&self, include_archived: bool) -> BTreeMap<String, Vec<ChatSessionSummary>> {
+        let mut groups: BTreeMap<String, Vec<ChatSessionSummary>> = BTreeMap::new();
+        for summary in self.list(None, include_archived) {
+            groups.entry(summary.project_root.clone()).or_default().push(summary);
+        }
+        groups
+    }
+
+    /// Full-text search over every persisted message; archived sessions are skipped
+    pub fn search(&self, query: &ChatSearchQuery) -> AppResult<Vec<ChatSearchHit>> {
+        // Read everything needed from the index before taking the search lock
+        let (session_ids, archived): (Vec<String>, HashSet<String>) = {
+            let index = self.lock_index()?;
+            (
+                index.sessions.keys().cloned().collect(),
+                index.sessions.values().filter(|s| s.archived).map(|s| s.id.clone()).collect(),
+            )
+        };
+
+        let mut search = self
+            .search
+            .lock()
+            .map_err(|_| AppError::internal("CHAT_STORE_POISONED", "Chat search index is poisoned"))?;
+
+        if search.is_none() {
+            let mut built = ChatSearchIndex::new();
+            for session_id in &session_ids {
+                match self.load(session_id) {
+                    Ok(session) => built.add_session(&session),
+                    Err(e) => e.log_warn(),
+                }
+            }
+            tracing::info!("🔎 Built chat search index over {} sessions", session_ids.len());
+            *search = Some(built);
+        }
+
+        Ok(search.as_ref().map(|index| index.search(query, &archived)).unwrap_or_default())
+    }
+
+    /// Apply an incremental update if the search index has been built
+    fn with_search_index(&self, update: impl FnOnce(&mut ChatSearchIndex)) {
+        if let Ok(mut search) = self.search.lock() {
+            if let Some(index) = search.as_mut() {
+                update(index);
+            }
+        }
+    }
+
+    // ================================
+    // INDEX RECOVERY
+    // ================================
+
+    /// Read the index, reconciling it with the logs actually on disk
+    fn load_index(&self) -> ChatIndex {
+        let mut index = std::fs::read(self.root.join(INDEX_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<ChatIndex>(&bytes).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_else(|| ChatIndex { version: INDEX_VERSION, sessions: BTreeMap::new() });
+
+        let mut changed = false;
+        let logs: Vec<PathBuf> = std::fs::read_dir(&self.root)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == LOG_EXTENSION))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // A crash between appending and re-indexing leaves the index stale; the log wins
+        for path in &logs {
+            let id = match path.file_stem() {
+                Some(stem) => stem.to_string_lossy().to_string(),
+                None => continue,
+            };
+            let stale = match (index.sessions.get(&id), std::fs::metadata(path).and_then(|m| m.modified())) {
+                (Some(_), Ok(modified)) => {
+                    let index_modified = std::fs::metadata(self.root.join(INDEX_FILE)).and_then(|m| m.modified());
+                    index_modified.map(|indexed| modified > indexed).unwrap_or(true)
+                }
+                _ => true,
+            };
+            if !stale {
+                continue;
+            }
+            match read_log(path) {
+                Ok(Some(session)) => {
+                    index.sessions.insert(id, ChatSessionSummary::from_session(&session));
+                    changed = true;
+                }
+                Ok(None) => {}
+                Err(e) => e.log_warn(),
+            }
+        }
+
+        let before = index.sessions.len();
+        index.sessions.retain(|id, _| logs.iter().any(|path| path.file_stem().is_some_and(|stem| stem == id.as_str())));
+        changed |= index.sessions.len() != before;
+
+        if changed {
+            if let Err(e) = self.save_index(&index) {
+                e.log_warn();
+            }
+        }
+        index
+    }
+}
+
+// ================================
+// LOG FILE HELPERS
+// ================================
+
+fn push_line(buffer: &mut Vec<u8>, record: &SessionRecord) -> AppResult<()> {
+    serde_json::to_writer(&mut *buffer, record)?;
+    buffer.push(b'\n');
+    Ok(())
+}
+
+/// Append and fsync, first cutting off any torn line left by an interrupted write
+fn append_durably(path: &Path, bytes: &[u8]) -> AppResult<()> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let length = file.metadata()?.len();
+
+    if length > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::Start(length - 1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            let mut contents = Vec::new();
+            file.seek(SeekFrom::Start(0))?;
+            file.read_to_end(&mut contents)?;
+            let keep = contents.iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+            tracing::warn!("Repairing torn write in {} ({} bytes dropped)", path.display(), length as usize - keep);
+            file.set_len(keep as u64)?;
+        }
+    }
+
+    file.seek(SeekFrom::End(0))?;
+    file.write_all(bytes)?;
+    file.sync_data()?;
+    Ok(())
+}
+
+/// Replay a log; malformed lines (such as a torn final write) are skipped
+fn read_log(path: &Path) -> AppResult<Option<ChatSession>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut session = None;
+    let mut linear = false;
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<SessionRecord>(line) {
+            Ok(record) => record.apply(&mut session, &mut linear),
+            Err(e) => tracing::warn!("Skipping unreadable line {} of {}: {}", number + 1, path.display(), e),
+        }
+    }
+    Ok(session)
+}
@@ -16,6 +16,12 @@ pub struct ChatMessage {
     pub content: String,
     pub timestamp: u64,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Typed content; `content` stays the plain-text rendering for text-only consumers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<MessagePart>,
+    /// Previous message in this branch; `None` for the first message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
 }
 
 impl ChatMessage {
@@ -26,6 +32,8 @@ impl ChatMessage {
             content: content.into(),
             timestamp: crate::core::current_timestamp(),
             metadata: None,
+            parts: Vec::new(),
+            parent_id: None,
         }
     }
     
@@ -36,6 +44,8 @@ impl ChatMessage {
             content: content.into(),
             timestamp: crate::core::current_timestamp(),
             metadata: None,
+            parts: Vec::new(),
+            parent_id: None,
         }
     }
     
@@ -46,6 +56,8 @@ impl ChatMessage {
             content: content.into(),
             timestamp: crate::core::current_timestamp(),
             metadata: None,
+            parts: Vec::new(),
+            parent_id: None,
         }
     }
     
@@ -54,6 +66,34 @@ impl ChatMessage {
         self
     }
     
+    pub fn with_parent(mut self, parent_id: Option<String>) -> Self {
+        self.parent_id = parent_id;
+        self
+    }
+    
+    pub fn with_parts(mut self, parts: Vec<MessagePart>) -> Self {
+        self.parts = parts;
+        self
+    }
+    
+    pub fn images(&self) -> Vec<&ImagePart> {
+        self.parts.iter().filter_map(|part| match part {
+            MessagePart::Image(image) => Some(image),
+            _ => None,
+        }).collect()
+    }
+    
+    pub fn attachments(&self) -> Vec<&FileAttachment> {
+        self.parts.iter().filter_map(|part| match part {
+            MessagePart::Attachment(attachment) => Some(attachment),
+            _ => None,
+        }).collect()
+    }
+    
+    pub fn has_images(&self) -> bool {
+        self.parts.iter().any(|part| matches!(part, MessagePart::Image(_)))
+    }
+    
     pub fn is_user_message(&self) -> bool {
         self.message_type == "user"
     }
@@ -67,18 +107,138 @@ impl ChatMessage {
     }
 }
 
+// ================================
+// MESSAGE PART TYPES
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessagePart {
+    Text { text: String },
+    Image(ImagePart),
+    FileReference {
+        path: String,
+        start_line: Option<u32>,
+        end_line: Option<u32>,
+    },
+    /// A file or selection captured at send time, so later changes can be detected
+    Attachment(FileAttachment),
+}
+
+impl MessagePart {
+    /// Plain-text stand-in used for `ChatMessage.content` and text-only providers
+    pub fn to_text(&self) -> String {
+        match self {
+            MessagePart::Text { text } => text.clone(),
+            MessagePart::Image(image) => format!(
+                "[image: {} {}x{}]",
+                image.source_path.as_deref().unwrap_or("pasted"),
+                image.width,
+                image.height
+            ),
+            MessagePart::FileReference { path, start_line, end_line } => match (start_line, end_line) {
+                (Some(start), Some(end)) => format!("[file: {}:{}-{}]", path, start, end),
+                (Some(start), None) => format!("[file: {}:{}]", path, start),
+                _ => format!("[file: {}]", path),
+            },
+            MessagePart::Attachment(attachment) => format!("[{}: {}]", attachment.kind.label(), attachment.location()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    File,
+    Selection,
+}
+
+impl AttachmentKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AttachmentKind::File => "file",
+            AttachmentKind::Selection => "selection",
+        }
+    }
+}
+
+/// A file range as it was when the message was sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileAttachment {
+    pub kind: AttachmentKind,
+    pub path: String,
+    /// 1-based inclusive line range; `None` attaches the whole file
+    pub start_line: Option<u32>,
+    pub end_line: Option<u32>,
+    /// SHA-256 of the attached text at send time
+    pub content_hash: String,
+    /// The attached text, kept for diffs unless the range was too large
+    pub snapshot: Option<String>,
+    pub captured_at: u64,
+}
+
+impl FileAttachment {
+    /// `path`, `path:line` or `path:start-end`
+    pub fn location(&self) -> String {
+        match (self.start_line, self.end_line) {
+            (Some(start), Some(end)) if end != start => format!("{}:{}-{}", self.path, start, end),
+            (Some(start), _) => format!("{}:{}", self.path, start),
+            _ => self.path.clone(),
+        }
+    }
+}
+
+/// An image already validated and downscaled for provider upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagePart {
+    pub mime_type: String,
+    /// Base64-encoded image bytes
+    pub data: String,
+    pub width: u32,
+    pub height: u32,
+    pub source_path: Option<String>,
+}
+
+/// Message parts as sent by the frontend, before image processing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessagePartInput {
+    Text { text: String },
+    ImagePath { path: String },
+    ImageData {
+        /// Base64-encoded bytes
+        data: String,
+        mime_type: String,
+    },
+    FileReference {
+        path: String,
+        start_line: Option<u32>,
+        end_line: Option<u32>,
+    },
+}
+
 // ================================
 // CHAT SESSION TYPES
 // ================================
 
+/// Messages form a tree through `parent_id`; `active_leaf` selects the branch being shown
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatSession {
     pub id: String,
     pub name: String,
+    /// Every message of every branch, in insertion order (parents before children)
     pub messages: Vec<ChatMessage>,
     pub context: ProjectContext,
     pub created_at: u64,
     pub updated_at: u64,
+    /// Archived sessions are hidden from listings but kept on disk
+    #[serde(default)]
+    pub archived: bool,
+    /// System message condensing older turns; the originals stay in `messages`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_summary: Option<ChatMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_leaf: Option<String>,
 }
 
 impl ChatSession {
@@ -92,20 +252,94 @@ impl ChatSession {
             context,
             created_at: now,
             updated_at: now,
+            archived: false,
+            history_summary: None,
+            active_leaf: None,
         }
     }
     
     pub fn add_message(&mut self, message: ChatMessage) {
-        self.messages.push(message);
+        self.insert_message(message);
         self.updated_at = crate::core::current_timestamp();
     }
     
+    /// Attach a message to the tree and make it the active leaf.
+    /// Messages without a parent continue the active branch.
+    pub fn insert_message(&mut self, mut message: ChatMessage) {
+        if message.parent_id.is_none() {
+            message.parent_id = self.active_leaf_id().map(str::to_string);
+        }
+        self.attach_message(message);
+    }
+    
+    /// Attach a message exactly where its `parent_id` points (a root when `None`)
+    pub fn attach_message(&mut self, message: ChatMessage) {
+        self.active_leaf = Some(message.id.clone());
+        self.messages.push(message);
+    }
+    
     pub fn message_count(&self) -> usize {
         self.messages.len()
     }
     
+    pub fn find_message(&self, message_id: &str) -> Option<&ChatMessage> {
+        self.messages.iter().find(|m| m.id == message_id)
+    }
+    
+    fn active_leaf_id(&self) -> Option<&str> {
+        self.active_leaf
+            .as_deref()
+            .filter(|id| self.find_message(id).is_some())
+            .or_else(|| self.messages.last().map(|m| m.id.as_str()))
+    }
+    
+    /// Last message of the active branch
     pub fn last_message(&self) -> Option<&ChatMessage> {
-        self.messages.last()
+        self.active_leaf_id().and_then(|id| self.find_message(id))
+    }
+    
+    /// Root-to-`message_id` chain of messages
+    pub fn path_to(&self, message_id: &str) -> Vec<&ChatMessage> {
+        let mut path = Vec::new();
+        let mut current = self.find_message(message_id);
+        while let Some(message) = current {
+            // Guard against cycles in hand-edited or imported data
+            if path.len() > self.messages.len() {
+                break;
+            }
+            path.push(message);
+            current = message.parent_id.as_deref().and_then(|id| self.find_message(id));
+        }
+        path.reverse();
+        path
+    }
+    
+    /// Messages of the active branch, oldest first
+    pub fn active_path(&self) -> Vec<&ChatMessage> {
+        match self.active_leaf_id() {
+            Some(leaf) => self.path_to(leaf),
+            None => Vec::new(),
+        }
+    }
+    
+    /// Messages sharing `message_id`'s parent, including itself, oldest first
+    pub fn siblings(&self, message_id: &str) -> Vec<&ChatMessage> {
+        match self.find_message(message_id) {
+            Some(message) => self.messages.iter().filter(|m| m.parent_id == message.parent_id).collect(),
+            None => Vec::new(),
+        }
+    }
+    
+    /// Newest leaf below `message_id`, following the latest child at each fork
+    pub fn latest_leaf_from(&self, message_id: &str) -> Option<String> {
+        let mut current = self.find_message(message_id)?;
+        for _ in 0..self.messages.len() {
+            match self.messages.iter().rev().find(|m| m.parent_id.as_deref() == Some(current.id.as_str())) {
+                Some(child) => current = child,
+                None => break,
+            }
+        }
+        Some(current.id.clone())
     }
     
     pub fn user_messages(&self) -> Vec<&ChatMessage> {
@@ -116,24 +350,76 @@ impl ChatSession {
         self.messages.iter().filter(|m| m.is_ai_message()).collect()
     }
     
+    /// Most recent messages of the active branch
     pub fn get_conversation_history(&self, limit: Option<usize>) -> Vec<&ChatMessage> {
+        let path = self.active_path();
         match limit {
             Some(l) => {
-                let start = if self.messages.len() > l {
-                    self.messages.len() - l
-                } else {
-                    0
-                };
-                self.messages[start..].iter().collect()
+                let start = path.len().saturating_sub(l);
+                path[start..].to_vec()
             }
-            None => self.messages.iter().collect(),
+            None => path,
         }
     }
     
     pub fn clear_messages(&mut self) {
         self.messages.clear();
+        self.history_summary = None;
+        self.active_leaf = None;
         self.updated_at = crate::core::current_timestamp();
     }
+    
+    /// New session holding the branch up to and including `message_id`
+    pub fn fork_at(&self, message_id: &str, name: impl Into<String>) -> Option<Self> {
+        let path = self.path_to(message_id);
+        if path.is_empty() {
+            return None;
+        }
+        let mut fork = Self::new(name, self.context.clone());
+        fork.messages = path.into_iter().cloned().collect();
+        fork.active_leaf = Some(message_id.to_string());
+        Some(fork)
+    }
+}
+
+/// A message on the active branch with the alternatives at its position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadMessage {
+    pub message: ChatMessage,
+    /// IDs of this message and its alternatives, oldest first
+    pub sibling_ids: Vec<String>,
+    pub sibling_index: usize,
+}
+
+/// Lightweight listing entry kept in the on-disk session index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSessionSummary {
+    pub id: String,
+    pub name: String,
+    pub project_root: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub message_count: usize,
+    pub preview: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl ChatSessionSummary {
+    const PREVIEW_CHARS: usize = 120;
+    
+    pub fn from_session(session: &ChatSession) -> Self {
+        Self {
+            id: session.id.clone(),
+            name: session.name.clone(),
+            project_root: session.context.root_path.clone(),
+            created_at: session.created_at,
+            updated_at: session.updated_at,
+            message_count: session.messages.len(),
+            preview: session.last_message().map(|m| m.content.chars().take(Self::PREVIEW_CHARS).collect()),
+            archived: session.archived,
+        }
+    }
 }
 
 // ================================
@@ -147,6 +433,9 @@ pub struct ChatContext {
     pub selected_text: Option<String>,
     pub cursor_position: Option<CursorPosition>,
     pub ai_preferences: Option<AiPreferences>,
+    /// Editor diagnostics for the active file, one per line
+    #[serde(default)]
+    pub diagnostics: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -264,4 +264,65 @@ pub fn validate_file(path: &Path) -> AppResult<()> {
     }
     
     Ok(())
-} 
\ No newline at end of file
+} 
+// ================================
+// PERSISTENCE UTILITIES
+// ================================
+
+/// Compute a hex-encoded SHA-256 digest of the given bytes
+pub fn content_hash(bytes: &[u8]) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, bytes);
+    digest.as_ref().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Write a file atomically: write to a sibling temp file, fsync, then rename over the target
+pub fn write_file_atomic(path: &Path, bytes: &[u8]) -> AppResult<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let tmp_path = path.with_extension(format!(
+        "{}.tmp",
+        path.extension().and_then(|ext| ext.to_str()).unwrap_or("")
+    ));
+
+    {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+
+    std::fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        AppError::filesystem_with_path(
+            "ATOMIC_WRITE_FAILED".to_string(),
+            format!("Failed to replace file: {}", e),
+            path.to_string_lossy().to_string()
+        )
+    })
+}
+
+/// Resolve (and create) a named subdirectory of the application data directory
+pub fn app_data_subdir(app_handle: &tauri::AppHandle, name: &str) -> AppResult<std::path::PathBuf> {
+    use tauri::Manager;
+
+    let base = app_handle.path().app_data_dir().map_err(|e| {
+        AppError::filesystem(
+            "APP_DATA_DIR_UNAVAILABLE".to_string(),
+            format!("Failed to get app data directory: {}", e)
+        )
+    })?;
+
+    let dir = base.join(name);
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        AppError::filesystem_with_path(
+            "APP_DATA_DIR_CREATE_FAILED".to_string(),
+            format!("Failed to create app data directory: {}", e),
+            dir.to_string_lossy().to_string()
+        )
+    })?;
+
+    Ok(dir)
+}
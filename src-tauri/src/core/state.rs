@@ -1,7 +1,8 @@
 // Syntari AI IDE - Application State
 // Centralized state management with domain separation
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tokio::sync::Mutex;
 use crate::core::{Result, SyntariError, StateCollection, OptionalStateValue, PreferenceManager};
 use crate::ai::types::AiProvider;
@@ -16,6 +17,10 @@ use crate::project::types::ProjectContext;
 pub struct AppState {
     pub current_project: Mutex<Option<ProjectContext>>,
     pub chat_sessions: Mutex<HashMap<String, ChatSession>>,
+    /// Per-session update locks, so one session's disk writes never block the others
+    pub chat_session_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    /// Sessions with a background compaction running
+    pub compacting_sessions: Mutex<HashSet<String>>,
     pub ai_providers: Mutex<HashMap<String, AiProvider>>, // Changed to HashMap for consistency
     pub user_preferences: Mutex<HashMap<String, serde_json::Value>>,
 }
@@ -88,7 +93,7 @@ impl AppState {
                 is_available: true,
                 cost_per_token: 0.003,
                 latency: 200,
-                specialties: vec!["coding".to_string(), "analysis".to_string()],
+                specialties: vec!["coding".to_string(), "analysis".to_string(), "vision".to_string()],
             });
             
             providers.insert("gpt4".to_string(), AiProvider {
@@ -98,7 +103,7 @@ impl AppState {
                 is_available: true,
                 cost_per_token: 0.01,
                 latency: 300,
-                specialties: vec!["general".to_string(), "coding".to_string()],
+                specialties: vec!["general".to_string(), "coding".to_string(), "vision".to_string()],
             });
         }
         
@@ -126,6 +131,16 @@ impl AppState {
         ProjectContextValue::has_value(&self.current_project).await
     }
     
+    /// Root path of an explicit project, falling back to the currently open one
+    pub async fn resolve_project_root(&self, project_path: Option<String>) -> Result<String> {
+        match project_path {
+            Some(path) => Ok(path),
+            None => self.get_current_project().await
+                .map(|project| project.root_path)
+                .ok_or_else(|| SyntariError::project("NO_PROJECT_OPEN", "No project is currently open")),
+        }
+    }
+    
     // ================================
     // AI PROVIDER STATE MANAGEMENT (using StateCollection trait)
     // ================================
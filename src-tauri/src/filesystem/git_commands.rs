@@ -502,4 +502,40 @@ pub async fn git_get_diff(repository_path: String, file_path: String, staged: bo
     }).map_err(|e| AppError::git_error("DIFF_PRINT_ERROR", &format!("Failed to print diff: {}", e)))?;
     
     Ok(diff_text)
-} 
\ No newline at end of file
+} 
+/// Patch of all uncommitted changes (staged and unstaged) against HEAD, optionally limited to one path
+pub fn collect_working_tree_diff(repository_path: &str, file_path: Option<&str>) -> AppResult<String> {
+    use git2::{Repository, DiffOptions};
+    
+    let repo = Repository::discover(repository_path)
+        .map_err(|e| AppError::git_error("REPO_NOT_FOUND", &format!("Repository not found: {}", e)))?;
+    
+    let mut diff_options = DiffOptions::new();
+    diff_options.include_untracked(true).recurse_untracked_dirs(true).show_untracked_content(true);
+    
+    if let Some(file_path) = file_path {
+        let workdir = repo.workdir().map(|w| w.to_path_buf()).unwrap_or_default();
+        let relative = Path::new(file_path).strip_prefix(&workdir).unwrap_or(Path::new(file_path));
+        diff_options.pathspec(relative);
+    }
+    
+    let head_tree = repo.head()
+        .ok()
+        .and_then(|head| head.peel_to_tree().ok());
+    
+    let diff = repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut diff_options))
+        .map_err(|e| AppError::git_error("DIFF_ERROR", &format!("Failed to get working tree diff: {}", e)))?;
+    
+    let mut diff_text = String::new();
+    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            diff_text.push(line.origin());
+        }
+        if let Ok(content) = std::str::from_utf8(line.content()) {
+            diff_text.push_str(content);
+        }
+        true
+    }).map_err(|e| AppError::git_error("DIFF_PRINT_ERROR", &format!("Failed to print diff: {}", e)))?;
+    
+    Ok(diff_text)
+}
@@ -8,7 +8,9 @@ use std::time::Duration;
 use notify::RecursiveMode;
 use notify_debouncer_mini::{new_debouncer, DebounceEventResult, DebouncedEvent};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+use crate::ai::semantic::SemanticIndexManager;
+use crate::project::DiagnosticsManager;
 use lazy_static::lazy_static;
 
 // Global file cache to track known files for better event detection
@@ -376,8 +378,23 @@ impl FileSystemWatcher {
                     // Process and optionally log smaller batches
                 }
                 
+                let semantic_index = app_handle.try_state::<SemanticIndexManager>();
+                let diagnostics = app_handle.try_state::<DiagnosticsManager>();
+                
                 for event in events {
                     if let Some(fs_event) = Self::convert_debounced_event(event, root_path, app_handle) {
+                        // Queue file changes for incremental semantic re-indexing
+                        if let Some(manager) = semantic_index.as_ref() {
+                            if !fs_event.is_directory || fs_event.event_type == "deleted" {
+                                manager.mark_dirty(Path::new(&fs_event.path));
+                            }
+                        }
+                        // Saves rerun build and lint diagnostics for projects that opted in
+                        if let Some(manager) = diagnostics.as_ref() {
+                            if !fs_event.is_directory && fs_event.event_type != "deleted" {
+                                manager.file_changed(app_handle, Path::new(&fs_event.path));
+                            }
+                        }
                         Self::emit_event(app_handle, fs_event);
                     }
                 }
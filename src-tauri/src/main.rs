@@ -8,17 +8,31 @@
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+use tauri::Manager;
 use syntari_desktop_lib::{
     core, filesystem, project, ai, chat, terminal
 };
 
 fn main() {
+    // One limiter for chat completions and embedding requests alike
+    let rate_limiter = std::sync::Arc::new(ai::rate_limit::RateLimiter::new());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(core::state::AppState::default())
         .manage(terminal::commands::TerminalManager::new())
+        .manage(ai::semantic::SemanticIndexManager::new(rate_limiter.clone()))
+        .manage(rate_limiter)
+        .manage(ai::context7::LocalDocsService::new())
+        .manage(project::TaskRunner::new())
+        .manage(project::DiagnosticsManager::new())
+        .manage(project::TestExplorer::new())
         .setup(|app| {
+            // Chat history and recent projects live under the app data dir; only their indexes are read here
+            app.manage(chat::ChatStore::open_for_app(app.handle()));
+            app.manage(project::WorkspaceStore::open_for_app(app.handle()));
+            
             // Initialize the robust file system watcher
             filesystem::watcher::initialize_watcher(app.handle().clone());
             println!("🎮 [RUST] Syntari Desktop App initialized with robust file watcher");
@@ -42,16 +56,74 @@ fn main() {
             
             // Project management commands  
             project::commands::open_project,
+            project::commands::analyze_project,
+            project::commands::list_recent_projects,
+            project::commands::remove_recent_project,
+            project::commands::save_workspace_state,
+            project::commands::restore_workspace,
+            project::commands::get_cargo_workspace,
+            project::commands::get_project_tree,
+            project::commands::set_active_file,
+            project::commands::get_dependency_graph,
+            project::commands::why_dependency,
+            project::commands::find_duplicate_dependencies,
+            project::commands::audit_dependencies,
+            project::commands::list_tasks,
+            project::commands::run_task,
+            project::commands::rerun_last_task,
+            project::commands::cancel_task,
+            project::commands::get_task_history,
+            project::commands::run_diagnostics,
+            project::commands::get_diagnostics,
+            project::commands::set_diagnostics_on_save,
+            project::commands::discover_tests,
+            project::commands::get_test_tree,
+            project::commands::run_tests,
+            project::commands::cancel_test_run,
+            project::commands::get_test_runs,
+            #[cfg(feature = "compliance")]
+            project::licenses::commands::get_license_report,
+            #[cfg(feature = "compliance")]
+            project::licenses::commands::export_license_report,
             
             // AI provider commands
             ai::commands::generate_ai_response,
             ai::context7::commands::resolve_library_id,
             ai::context7::commands::get_library_docs,
+            ai::context7::commands::index_local_docs,
+            ai::context7::commands::resolve_local_library_id,
+            ai::context7::commands::get_local_library_docs,
+            ai::semantic::commands::build_semantic_index,
+            ai::semantic::commands::semantic_search,
+            ai::semantic::commands::get_semantic_index_stats,
+            ai::prompts::commands::list_prompt_templates,
+            ai::prompts::commands::render_prompt_template,
+            ai::prompts::commands::execute_prompt_template,
+            ai::rate_limit::commands::get_rate_limit_status,
+            ai::rate_limit::commands::set_provider_rate_limit,
             
             // Chat commands
             chat::commands::create_chat_session,
             chat::commands::send_chat_message,
             chat::commands::get_chat_session,
+            chat::commands::check_chat_attachments,
+            chat::commands::list_chat_sessions,
+            chat::commands::list_chat_sessions_by_project,
+            chat::commands::rename_chat_session,
+            chat::commands::delete_chat_session,
+            chat::commands::clear_chat_session,
+            chat::commands::archive_chat_session,
+            chat::commands::fork_chat_session,
+            chat::commands::compact_chat_session,
+            chat::commands::search_chat_history,
+            chat::commands::export_chat_session,
+            chat::commands::import_chat_session,
+            chat::commands::edit_chat_message,
+            chat::commands::regenerate_chat_reply,
+            chat::commands::switch_chat_branch,
+            chat::commands::get_chat_thread,
+            chat::slash::commands::list_chat_commands,
+            chat::slash::commands::run_chat_command,
             
             // Filesystem operations
             filesystem::commands::read_file,
@@ -1,10 +1,502 @@
 // Syntari AI IDE - Project Analyzer
 // Project structure analysis and detection
 
+use std::collections::HashMap;
+use std::path::Path;
+use ignore::WalkBuilder;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::core::{AppResult, detect_language_from_extension, validate_directory};
+use crate::project::types::{ComplexityHotspot, Framework, LanguageStats, ProjectAnalysis, ProjectContext};
+
+/// Stop walking after this many files so huge trees still answer quickly
+const MAX_FILES: usize = 50_000;
+/// Larger files are counted but not read
+const MAX_ANALYZED_FILE_BYTES: u64 = 1024 * 1024;
+const HOTSPOT_COUNT: usize = 10;
+/// Nesting at or beyond this depth is flagged as hard to follow
+const DEEP_NESTING: u32 = 5;
+
+/// Directories skipped even when no ignore file lists them
+pub(crate) const SKIPPED_DIRECTORIES: &[&str] = &["node_modules", "target", "dist", "build", "vendor", "__pycache__", ".venv", "venv"];
+
+/// Languages that are data or markup; counted, but not scored for complexity
+const NON_CODE_LANGUAGES: &[&str] = &["json", "toml", "yaml", "xml", "markdown", "html", "css", "scss", "sql", "plaintext"];
+
+/// Manifests and the lockfiles that pin them
+const LOCKFILES: &[(&str, &[&str])] = &[
+    ("Cargo.toml", &["Cargo.lock"]),
+    ("package.json", &["package-lock.json", "yarn.lock", "pnpm-lock.yaml", "bun.lockb", "bun.lock"]),
+    ("pyproject.toml", &["poetry.lock", "uv.lock", "pdm.lock", "Pipfile.lock"]),
+    ("Pipfile", &["Pipfile.lock"]),
+    ("Gemfile", &["Gemfile.lock"]),
+    ("composer.json", &["composer.lock"]),
+    ("go.mod", &["go.sum"]),
+];
+
+const DEPENDENCY_WEIGHT: f64 = 0.5;
+const CONFIG_WEIGHT: f64 = 0.3;
+const SOURCE_WEIGHT: f64 = 0.2;
+const LAYOUT_WEIGHT: f64 = 0.1;
+
+lazy_static! {
+    static ref DECISION_PATTERN: Regex =
+        Regex::new(r"\b(if|elif|elsif|for|foreach|while|until|unless|case|catch|except|when)\b|&&|\|\|").unwrap();
+    static ref RUST_ARM_PATTERN: Regex = Regex::new(r"=>").unwrap();
+    static ref STRING_PATTERN: Regex = Regex::new(r#""(?:[^"\\]|\\.)*"|`(?:[^`\\]|\\.)*`"#).unwrap();
+    static ref SOURCE_SIGNALS: Vec<(Framework, Regex)> = vec![
+        (Framework::NextJs, Regex::new(r#"from\s+['"]next/"#).unwrap()),
+        (Framework::React, Regex::new(r#"from\s+['"]react['"]|require\(\s*['"]react['"]\s*\)"#).unwrap()),
+        (Framework::Vue, Regex::new(r#"from\s+['"]vue['"]"#).unwrap()),
+        (Framework::Angular, Regex::new(r#"from\s+['"]@angular/core['"]"#).unwrap()),
+        (Framework::Svelte, Regex::new(r#"from\s+['"]svelte"#).unwrap()),
+        (Framework::Nuxt, Regex::new(r"defineNuxtConfig|useNuxtApp").unwrap()),
+        (Framework::Express, Regex::new(r#"(from\s+|require\(\s*)['"]express['"]"#).unwrap()),
+        (Framework::Tauri, Regex::new(r"@tauri-apps/api|tauri::Builder").unwrap()),
+        (Framework::Django, Regex::new(r"(?m)^\s*(from\s+django\b|import\s+django\b)").unwrap()),
+        (Framework::Flask, Regex::new(r"(?m)^\s*from\s+flask\s+import\b").unwrap()),
+        (Framework::SpringBoot, Regex::new(r"@SpringBootApplication|org\.springframework\.boot").unwrap()),
+    ];
+}
+
+#[derive(Debug, Default)]
 pub struct ProjectAnalyzer;
 
 impl ProjectAnalyzer {
     pub fn new() -> Self {
         Self
     }
-} 
\ No newline at end of file
+
+    /// Walk the project, respecting ignore files, and compute languages, complexity, framework and suggestions
+    pub fn analyze(&self, root: &Path, context: ProjectContext) -> AppResult<ProjectAnalysis> {
+        validate_directory(root)?;
+
+        let mut walk = WalkState::default();
+        let walker = WalkBuilder::new(root)
+            .require_git(false)
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                !(entry.file_type().is_some_and(|t| t.is_dir()) && SKIPPED_DIRECTORIES.contains(&name.as_ref()))
+            })
+            .build();
+
+        for entry in walker.flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            if walk.file_count as usize >= MAX_FILES {
+                tracing::warn!("Project analysis stopped after {} files", MAX_FILES);
+                break;
+            }
+            walk.visit(root, entry.path());
+        }
+
+        let mut analysis = ProjectAnalysis::new(context);
+        analysis.file_count = walk.file_count;
+        analysis.total_lines = walk.languages.values().map(|stats| stats.lines).sum();
+        analysis.language_distribution = walk.languages.values().map(|stats| (stats.language.clone(), stats.code_lines)).collect();
+        analysis.complexity_score = walk.complexity_score();
+        analysis.complexity_hotspots = walk.hotspots();
+
+        let mut languages: Vec<LanguageStats> = walk.languages.values().cloned().collect();
+        languages.sort_by(|a, b| b.code_lines.cmp(&a.code_lines).then_with(|| a.language.cmp(&b.language)));
+        analysis.languages = languages;
+
+        let mut evidence = walk.framework_evidence;
+        collect_manifest_signals(root, &mut evidence);
+        if let Some((framework, confidence, signals)) = evidence.best() {
+            analysis.framework = Some(framework.to_string().to_string());
+            analysis.framework_confidence = confidence;
+            analysis.framework_signals = signals;
+        }
+
+        analysis.suggested_improvements = suggestions(root, walk.test_files, &analysis.complexity_hotspots);
+
+        tracing::info!(
+            "📊 Analyzed {}: {} files, {} lines, complexity {:.1}",
+            root.display(),
+            analysis.file_count,
+            analysis.total_lines,
+            analysis.complexity_score
+        );
+        Ok(analysis)
+    }
+}
+
+// ================================
+// WALK STATE
+// ================================
+
+#[derive(Default)]
+struct WalkState {
+    file_count: u32,
+    test_files: u32,
+    languages: HashMap<String, LanguageStats>,
+    files: Vec<(String, FileMetrics)>,
+    framework_evidence: FrameworkEvidence,
+}
+
+impl WalkState {
+    fn visit(&mut self, root: &Path, path: &Path) {
+        self.file_count += 1;
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let language = detect_language_from_extension(path);
+
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+        match extension {
+            "vue" => self.framework_evidence.add(Framework::Vue, SOURCE_WEIGHT, "Vue single-file components"),
+            "svelte" => self.framework_evidence.add(Framework::Svelte, SOURCE_WEIGHT, "Svelte components"),
+            _ => {}
+        }
+        if is_test_path(relative) {
+            self.test_files += 1;
+        }
+        if language == "plaintext" {
+            return;
+        }
+
+        let stats = self.languages.entry(language.clone()).or_insert_with(|| LanguageStats {
+            language: language.clone(),
+            ..LanguageStats::default()
+        });
+        stats.files += 1;
+
+        if path.metadata().map(|m| m.len() > MAX_ANALYZED_FILE_BYTES).unwrap_or(true) {
+            return;
+        }
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) => return,
+        };
+
+        let metrics = measure(&content, &language);
+        stats.lines += metrics.lines;
+        stats.code_lines += metrics.code_lines;
+
+        if NON_CODE_LANGUAGES.contains(&language.as_str()) {
+            return;
+        }
+        if language == "rust" && !is_test_path(relative) && content.contains("#[test]") {
+            self.test_files += 1;
+        }
+        for (framework, pattern) in SOURCE_SIGNALS.iter() {
+            if pattern.is_match(&content) {
+                self.framework_evidence.add(*framework, SOURCE_WEIGHT, "imported from source files");
+            }
+        }
+        self.files.push((relative.to_string_lossy().to_string(), metrics));
+    }
+
+    /// 0-100 blend of decision density, average nesting, share of deeply nested files and code size
+    fn complexity_score(&self) -> f64 {
+        let code_lines: u32 = self.files.iter().map(|(_, m)| m.code_lines).sum();
+        if code_lines == 0 {
+            return 0.0;
+        }
+        let decisions: u32 = self.files.iter().map(|(_, m)| m.decision_points).sum();
+        let depth_sum: u64 = self.files.iter().map(|(_, m)| m.depth_sum).sum();
+        let deep_files = self.files.iter().filter(|(_, m)| m.max_nesting >= DEEP_NESTING).count();
+
+        let density = decisions as f64 * 100.0 / code_lines as f64;
+        let average_depth = depth_sum as f64 / code_lines as f64;
+        let deep_ratio = deep_files as f64 / self.files.len() as f64;
+        let size = (code_lines as f64 + 1.0).log10();
+
+        let score = 35.0 * (density / 20.0).min(1.0)
+            + 25.0 * (average_depth / 4.0).min(1.0)
+            + 20.0 * deep_ratio
+            + 20.0 * (size / 6.0).min(1.0);
+        (score * 10.0).round() / 10.0
+    }
+
+    fn hotspots(&self) -> Vec<ComplexityHotspot> {
+        let mut ranked: Vec<&(String, FileMetrics)> = self.files.iter().filter(|(_, m)| m.decision_points > 0).collect();
+        ranked.sort_by_key(|(_, m)| std::cmp::Reverse(m.decision_points * m.max_nesting.max(1)));
+        ranked
+            .into_iter()
+            .take(HOTSPOT_COUNT)
+            .map(|(path, metrics)| ComplexityHotspot {
+                path: path.clone(),
+                code_lines: metrics.code_lines,
+                decision_points: metrics.decision_points,
+                max_nesting: metrics.max_nesting,
+            })
+            .collect()
+    }
+}
+
+// ================================
+// FILE METRICS
+// ================================
+
+#[derive(Debug, Default, Clone)]
+struct FileMetrics {
+    lines: u32,
+    code_lines: u32,
+    decision_points: u32,
+    max_nesting: u32,
+    /// Nesting depth summed over code lines, for the average
+    depth_sum: u64,
+}
+
+/// Line counts, decision points and nesting; braces give depth, except in indentation-scoped languages
+fn measure(content: &str, language: &str) -> FileMetrics {
+    let mut metrics = FileMetrics::default();
+    let indented = matches!(language, "python" | "ruby" | "yaml");
+    let hash_comments = matches!(language, "python" | "ruby" | "shell" | "yaml" | "toml" | "makefile" | "dockerfile");
+    let indent_unit = if indented { indent_unit(content) } else { 1 };
+
+    let mut depth: u32 = 0;
+    let mut in_block_comment = false;
+
+    for line in content.lines() {
+        metrics.lines += 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if in_block_comment {
+            in_block_comment = !trimmed.contains("*/");
+            continue;
+        }
+        if trimmed.starts_with("/*") {
+            in_block_comment = !trimmed.contains("*/");
+            continue;
+        }
+        let is_comment = trimmed.starts_with("//")
+            || trimmed.starts_with("<!--")
+            || (!hash_comments && trimmed.starts_with('*') && !trimmed.starts_with("*="))
+            || (hash_comments && trimmed.starts_with('#'))
+            || (language == "sql" && trimmed.starts_with("--"));
+        if is_comment {
+            continue;
+        }
+        metrics.code_lines += 1;
+
+        // String contents would otherwise count as keywords and braces
+        let code = STRING_PATTERN.replace_all(trimmed, "\"\"");
+        let line_depth = if indented {
+            leading_width(line) / indent_unit
+        } else {
+            let closing = code.chars().take_while(|c| *c == '}').count() as u32;
+            let line_depth = depth.saturating_sub(closing);
+            let opens = code.matches('{').count() as u32;
+            let closes = code.matches('}').count() as u32;
+            depth = (depth + opens).saturating_sub(closes);
+            line_depth
+        };
+        metrics.max_nesting = metrics.max_nesting.max(line_depth);
+        metrics.depth_sum += line_depth as u64;
+
+        metrics.decision_points += DECISION_PATTERN.find_iter(&code).count() as u32;
+        if language == "rust" {
+            metrics.decision_points += RUST_ARM_PATTERN.find_iter(&code).count() as u32;
+        }
+    }
+    metrics
+}
+
+fn leading_width(line: &str) -> u32 {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Smallest non-zero indentation in the file, which is one nesting level
+fn indent_unit(content: &str) -> u32 {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(leading_width)
+        .filter(|width| *width > 0)
+        .min()
+        .unwrap_or(4)
+}
+
+fn is_test_path(relative: &Path) -> bool {
+    let in_test_dir = relative.parent().is_some_and(|parent| {
+        parent.components().any(|component| {
+            matches!(component.as_os_str().to_str(), Some("test" | "tests" | "__tests__" | "spec" | "specs"))
+        })
+    });
+    if in_test_dir {
+        return true;
+    }
+
+    let name = relative.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    (name.starts_with("test_") && name.ends_with(".py"))
+        || [
+            "_test.py", "_test.go", "_spec.rb", "_test.rb", "Test.java", "Tests.java", "Tests.cs", "Test.cs", "Test.php",
+        ]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+        || [".test.", ".spec."].iter().any(|marker| name.contains(marker))
+}
+
+// ================================
+// FRAMEWORK EVIDENCE
+// ================================
+
+/// Weighted signals per framework; a signal type only counts once per framework
+#[derive(Default)]
+struct FrameworkEvidence {
+    entries: Vec<(Framework, f64, Vec<String>)>,
+}
+
+impl FrameworkEvidence {
+    fn add(&mut self, framework: Framework, weight: f64, signal: &str) {
+        let position = match self.entries.iter().position(|(f, _, _)| *f == framework) {
+            Some(position) => position,
+            None => {
+                self.entries.push((framework, 0.0, Vec::new()));
+                self.entries.len() - 1
+            }
+        };
+        let (_, score, signals) = &mut self.entries[position];
+        if !signals.iter().any(|s| s == signal) {
+            *score += weight;
+            signals.push(signal.to_string());
+        }
+    }
+
+    /// The strongest framework; frameworks built on another (Next.js on React) collect more signals and win
+    fn best(&self) -> Option<(Framework, f64, Vec<String>)> {
+        self.entries
+            .iter()
+            .fold(None::<&(Framework, f64, Vec<String>)>, |best, entry| match best {
+                Some(current) if current.1 >= entry.1 => Some(current),
+                _ => Some(entry),
+            })
+            .map(|(framework, score, signals)| (*framework, score.min(1.0), signals.clone()))
+    }
+}
+
+fn collect_manifest_signals(root: &Path, evidence: &mut FrameworkEvidence) {
+    // package.json dependencies
+    if let Some(package) = read(root, "package.json").and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok()) {
+        let mut names: Vec<String> = Vec::new();
+        for section in ["dependencies", "devDependencies"] {
+            if let Some(deps) = package.get(section).and_then(|d| d.as_object()) {
+                names.extend(deps.keys().cloned());
+            }
+        }
+        for (dependency, framework) in [
+            ("next", Framework::NextJs),
+            ("react", Framework::React),
+            ("vue", Framework::Vue),
+            ("@angular/core", Framework::Angular),
+            ("svelte", Framework::Svelte),
+            ("nuxt", Framework::Nuxt),
+            ("express", Framework::Express),
+            ("@tauri-apps/api", Framework::Tauri),
+        ] {
+            if names.iter().any(|name| name == dependency) {
+                evidence.add(framework, DEPENDENCY_WEIGHT, &format!("{} in package.json", dependency));
+            }
+        }
+    }
+
+    // Python manifests
+    for manifest in ["requirements.txt", "pyproject.toml", "Pipfile"] {
+        if let Some(content) = read(root, manifest) {
+            for (package, framework) in [("django", Framework::Django), ("flask", Framework::Flask)] {
+                if mentions_package(&content, package) {
+                    evidence.add(framework, DEPENDENCY_WEIGHT, &format!("{} in {}", package, manifest));
+                }
+            }
+        }
+    }
+
+    // JVM build files
+    for manifest in ["pom.xml", "build.gradle", "build.gradle.kts"] {
+        if read(root, manifest).is_some_and(|content| content.contains("spring-boot")) {
+            evidence.add(Framework::SpringBoot, DEPENDENCY_WEIGHT, &format!("spring-boot in {}", manifest));
+        }
+    }
+
+    // Tauri apps keep their Rust manifest at the root or in src-tauri
+    for manifest in ["Cargo.toml", "src-tauri/Cargo.toml"] {
+        if read(root, manifest).is_some_and(|content| mentions_package(&content, "tauri")) {
+            evidence.add(Framework::Tauri, DEPENDENCY_WEIGHT, &format!("tauri in {}", manifest));
+        }
+    }
+
+    for (file, framework) in [
+        ("next.config.js", Framework::NextJs),
+        ("next.config.mjs", Framework::NextJs),
+        ("next.config.ts", Framework::NextJs),
+        ("nuxt.config.js", Framework::Nuxt),
+        ("nuxt.config.ts", Framework::Nuxt),
+        ("angular.json", Framework::Angular),
+        ("svelte.config.js", Framework::Svelte),
+        ("vue.config.js", Framework::Vue),
+        ("tauri.conf.json", Framework::Tauri),
+        ("src-tauri/tauri.conf.json", Framework::Tauri),
+        ("manage.py", Framework::Django),
+        ("src/main/resources/application.properties", Framework::SpringBoot),
+        ("src/main/resources/application.yml", Framework::SpringBoot),
+    ] {
+        if root.join(file).is_file() {
+            evidence.add(framework, CONFIG_WEIGHT, &format!("{} present", file));
+        }
+    }
+
+    // Next.js routes by directory layout
+    if ["pages", "app", "src/pages", "src/app"].iter().any(|dir| root.join(dir).is_dir())
+        && evidence.entries.iter().any(|(f, _, _)| *f == Framework::NextJs)
+    {
+        evidence.add(Framework::NextJs, LAYOUT_WEIGHT, "pages or app router directory");
+    }
+}
+
+fn read(root: &Path, file: &str) -> Option<String> {
+    std::fs::read_to_string(root.join(file)).ok()
+}
+
+/// Whether a manifest names a package as a whole word, e.g. `django>=4` but not `django-extensions`
+fn mentions_package(content: &str, package: &str) -> bool {
+    let pattern = format!(r#"(?im)(^|[\s"'\[,{{]){}($|[\s"'<>=~!\[;,.])"#, regex::escape(package));
+    Regex::new(&pattern).is_ok_and(|re| re.is_match(content))
+}
+
+// ================================
+// SUGGESTIONS
+// ================================
+
+fn suggestions(root: &Path, test_files: u32, hotspots: &[ComplexityHotspot]) -> Vec<String> {
+    let mut suggestions = Vec::new();
+    let root_names: Vec<String> = std::fs::read_dir(root)
+        .map(|entries| entries.flatten().map(|e| e.file_name().to_string_lossy().to_lowercase()).collect())
+        .unwrap_or_default();
+    let has_root_file = |prefix: &str| root_names.iter().any(|name| name.starts_with(prefix));
+
+    if !has_root_file("readme") {
+        suggestions.push("Add a README describing what the project does and how to build it".to_string());
+    }
+    if test_files == 0 {
+        suggestions.push("No tests were found; add tests for the core logic".to_string());
+    }
+    for (manifest, lockfiles) in LOCKFILES {
+        if root.join(manifest).is_file() && !lockfiles.iter().any(|lockfile| root.join(lockfile).exists()) {
+            suggestions.push(format!(
+                "{} has no lockfile ({}); commit one for reproducible builds",
+                manifest,
+                lockfiles.join(" or ")
+            ));
+        }
+    }
+    if root.join(".git").exists() && !root.join(".gitignore").exists() {
+        suggestions.push("Add a .gitignore so build output and dependencies stay out of version control".to_string());
+    }
+    if !has_root_file("license") && !has_root_file("licence") {
+        suggestions.push("Add a LICENSE file stating how the code may be used".to_string());
+    }
+    for hotspot in hotspots.iter().filter(|h| h.max_nesting >= DEEP_NESTING).take(3) {
+        suggestions.push(format!(
+            "Reduce nesting in {} (depth {}, {} decision points)",
+            hotspot.path, hotspot.max_nesting, hotspot.decision_points
+        ));
+    }
+    suggestions
+}
@@ -0,0 +1,300 @@
+// Syntari AI IDE - Advisory Database
+// Loads RustSec advisories (TOML front matter in markdown) and OSV JSON records synced to disk
+
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::project::audit::types::{AffectedVersions, Advisory, RangeEvent, Severity};
+
+/// Read every advisory under `root`; files that are not advisories are skipped
+pub fn load_advisories(root: &Path) -> Vec<Advisory> {
+    let mut advisories = Vec::new();
+    for entry in WalkDir::new(root).follow_links(true).into_iter().flatten() {
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.components().any(|c| c.as_os_str() == ".git") {
+            continue;
+        }
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        if !matches!(extension, "md" | "toml" | "json") {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let parsed = match extension {
+            "json" => parse_osv(&content),
+            _ => parse_rustsec(&content),
+        };
+        match parsed {
+            Ok(found) => advisories.extend(found),
+            Err(reason) => tracing::debug!("Skipping advisory file {}: {}", path.display(), reason),
+        }
+    }
+    advisories
+}
+
+// ================================
+// RUSTSEC
+// ================================
+
+/// `crates/<name>/RUSTSEC-YYYY-NNNN.md`: a fenced TOML block, then `# Title` and the description
+fn parse_rustsec(content: &str) -> Result<Vec<Advisory>, String> {
+    let (front_matter, body) = match content.trim_start().strip_prefix("```toml") {
+        Some(rest) => rest.split_once("```").ok_or("unterminated front matter")?,
+        // Older databases store the whole advisory as TOML
+        None => (content, ""),
+    };
+    let manifest: toml::Table = front_matter.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+    let advisory = manifest.get("advisory").and_then(|a| a.as_table()).ok_or("no [advisory] table")?;
+    if advisory.contains_key("withdrawn") {
+        return Ok(Vec::new());
+    }
+    let field = |key: &str| advisory.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let strings = |table: Option<&toml::Value>, key: &str| -> Vec<String> {
+        table
+            .and_then(|t| t.get(key))
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|i| i.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    };
+    let (Some(id), Some(package)) = (field("id"), field("package")) else {
+        return Err("advisory without id or package".to_string());
+    };
+
+    let versions = manifest.get("versions");
+    let patched = strings(versions, "patched");
+    let unaffected = strings(versions, "unaffected");
+    let requirements = |items: &[String]| -> Vec<semver::VersionReq> {
+        items.iter().filter_map(|req| semver::VersionReq::parse(req).ok()).collect()
+    };
+    let summary = body
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("# "))
+        .map(str::to_string)
+        .or_else(|| field("title"))
+        .unwrap_or_else(|| id.clone());
+    let cvss = field("cvss");
+    let score = cvss.as_deref().and_then(cvss_base_score);
+    let affected_ranges = if patched.is_empty() && unaffected.is_empty() {
+        vec!["all versions".to_string()]
+    } else {
+        vec![format!("every version except {}", patched.iter().chain(&unaffected).cloned().collect::<Vec<_>>().join(" or "))]
+    };
+
+    Ok(vec![Advisory {
+        id: id.clone(),
+        aliases: strings(manifest.get("advisory"), "aliases"),
+        ecosystem: "crates.io".to_string(),
+        package,
+        summary,
+        severity: score.map(Severity::from_score).unwrap_or(Severity::Unknown),
+        cvss,
+        score,
+        url: field("url").or_else(|| Some(format!("https://rustsec.org/advisories/{}.html", id))),
+        informational: field("informational"),
+        affected: AffectedVersions::RustSec { patched: requirements(&patched), unaffected: requirements(&unaffected) },
+        affected_ranges,
+        patched,
+    }])
+}
+
+// ================================
+// OSV
+// ================================
+
+/// One OSV record; each `affected` entry becomes its own advisory
+fn parse_osv(content: &str) -> Result<Vec<Advisory>, String> {
+    let record: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let id = record.get("id").and_then(|v| v.as_str()).ok_or("no id")?.to_string();
+    let affected = record.get("affected").and_then(|a| a.as_array()).ok_or("no affected packages")?;
+    if record.get("withdrawn").is_some() {
+        return Ok(Vec::new());
+    }
+
+    let text = |value: Option<&serde_json::Value>| value.and_then(|v| v.as_str()).map(str::to_string);
+    let aliases: Vec<String> = record
+        .get("aliases")
+        .and_then(|a| a.as_array())
+        .map(|items| items.iter().filter_map(|i| i.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    let summary = text(record.get("summary"))
+        .or_else(|| text(record.get("details")).and_then(|d| d.lines().next().map(str::to_string)))
+        .unwrap_or_else(|| id.clone());
+    let references = record.get("references").and_then(|r| r.as_array()).cloned().unwrap_or_default();
+    let url = references
+        .iter()
+        .find(|r| r.get("type").and_then(|t| t.as_str()) == Some("ADVISORY"))
+        .or_else(|| references.first())
+        .and_then(|r| text(r.get("url")));
+    let cvss = record
+        .get("severity")
+        .and_then(|s| s.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|s| s.get("type").and_then(|t| t.as_str()) == Some("CVSS_V3"))
+        .find_map(|s| text(s.get("score")));
+    let score = cvss.as_deref().and_then(cvss_base_score);
+    let rated = text(record.get("database_specific").and_then(|d| d.get("severity"))).map(|label| Severity::from_label(&label));
+
+    let mut advisories = Vec::new();
+    for entry in affected {
+        let package = entry.get("package");
+        let (Some(ecosystem), Some(name)) = (text(package.and_then(|p| p.get("ecosystem"))), text(package.and_then(|p| p.get("name")))) else {
+            continue;
+        };
+        let versions: Vec<String> = entry
+            .get("versions")
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|i| i.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        // Git ranges are commit hashes and cannot be compared with released versions
+        let ranges: Vec<Vec<RangeEvent>> = entry
+            .get("ranges")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|range| range.get("type").and_then(|t| t.as_str()) != Some("GIT"))
+            .map(|range| {
+                range
+                    .get("events")
+                    .and_then(|e| e.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|event| {
+                        if let Some(v) = text(event.get("introduced")) {
+                            Some(RangeEvent::Introduced(v))
+                        } else if let Some(v) = text(event.get("fixed")) {
+                            Some(RangeEvent::Fixed(v))
+                        } else {
+                            text(event.get("last_affected")).map(RangeEvent::LastAffected)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let entry_rating = text(entry.get("ecosystem_specific").and_then(|e| e.get("severity"))).map(|label| Severity::from_label(&label));
+
+        advisories.push(Advisory {
+            id: id.clone(),
+            aliases: aliases.clone(),
+            // `Debian:11` style ecosystems carry a release suffix
+            ecosystem: ecosystem.split(':').next().unwrap_or(&ecosystem).to_string(),
+            package: name,
+            summary: summary.clone(),
+            severity: score.map(Severity::from_score).or(rated).or(entry_rating).unwrap_or(Severity::Unknown),
+            cvss: cvss.clone(),
+            score,
+            url: url.clone(),
+            informational: None,
+            affected_ranges: describe_ranges(&ranges, &versions),
+            patched: ranges
+                .iter()
+                .flatten()
+                .filter_map(|event| match event {
+                    RangeEvent::Fixed(v) => Some(v.clone()),
+                    _ => None,
+                })
+                .collect(),
+            affected: AffectedVersions::Osv { versions, ranges },
+        });
+    }
+    Ok(advisories)
+}
+
+/// `>= 1.0.0, < 1.2.3` style text for each range
+fn describe_ranges(ranges: &[Vec<RangeEvent>], versions: &[String]) -> Vec<String> {
+    let mut described = Vec::new();
+    for events in ranges {
+        let mut lower: Option<&str> = None;
+        for event in events {
+            match event {
+                RangeEvent::Introduced(v) => lower = Some(v),
+                RangeEvent::Fixed(v) | RangeEvent::LastAffected(v) => {
+                    let upper = if matches!(event, RangeEvent::Fixed(_)) { format!("< {}", v) } else { format!("<= {}", v) };
+                    described.push(match lower.take() {
+                        Some("0") | None => upper,
+                        Some(low) => format!(">= {}, {}", low, upper),
+                    });
+                }
+            }
+        }
+        if let Some(low) = lower {
+            described.push(if low == "0" { "all versions".to_string() } else { format!(">= {}", low) });
+        }
+    }
+    if described.is_empty() && !versions.is_empty() {
+        described.push(versions.join(", "));
+    }
+    described
+}
+
+// ================================
+// CVSS
+// ================================
+
+/// CVSS v3.x base score from a vector such as `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`
+pub fn cvss_base_score(vector: &str) -> Option<f64> {
+    if !vector.starts_with("CVSS:3.") {
+        return None;
+    }
+    let metric = |name: &str| -> Option<&str> {
+        vector.split('/').find_map(|part| part.strip_prefix(name).and_then(|rest| rest.strip_prefix(':')))
+    };
+    let changed = metric("S")? == "C";
+    let attack_vector = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let attack_complexity = match metric("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let privileges = match (metric("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let interaction = match metric("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact_weight = |name: &str| -> Option<f64> {
+        match metric(name)? {
+            "H" => Some(0.56),
+            "L" => Some(0.22),
+            "N" => Some(0.0),
+            _ => None,
+        }
+    };
+    let (c, i, a) = (impact_weight("C")?, impact_weight("I")?, impact_weight("A")?);
+
+    let base_impact = 1.0 - (1.0 - c) * (1.0 - i) * (1.0 - a);
+    let impact = if changed {
+        7.52 * (base_impact - 0.029) - 3.25 * (base_impact - 0.02f64).powi(15)
+    } else {
+        6.42 * base_impact
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * attack_vector * attack_complexity * privileges * interaction;
+    let total = if changed { 1.08 * (impact + exploitability) } else { impact + exploitability };
+    Some(round_up(total.min(10.0)))
+}
+
+/// CVSS v3.1 round-up to one decimal, robust to floating point error
+fn round_up(value: f64) -> f64 {
+    let scaled = (value * 100_000.0).round() as i64;
+    if scaled % 10_000 == 0 {
+        scaled as f64 / 100_000.0
+    } else {
+        ((scaled / 10_000) + 1) as f64 / 10.0
+    }
+}
@@ -0,0 +1,107 @@
+// Syntari AI IDE - Offline Dependency Audit
+// Matches resolved lockfile packages against advisory databases synced to disk
+
+pub mod types;
+pub mod database;
+pub mod versions;
+
+pub use types::{AuditReport, Severity, VulnerabilityFinding};
+
+use std::collections::HashMap;
+use std::path::Path;
+use crate::project::audit::types::Advisory;
+use crate::project::lockfile::{DependencyGraph, LockfileKind};
+
+/// App data subdirectory holding synced databases, e.g. `advisories/advisory-db` and `advisories/osv/npm`
+pub const ADVISORY_DIR: &str = "advisories";
+
+/// OSV ecosystem name for the packages a lockfile resolves
+pub fn ecosystem(kind: LockfileKind) -> &'static str {
+    match kind {
+        LockfileKind::Cargo => "crates.io",
+        LockfileKind::Npm | LockfileKind::Yarn | LockfileKind::Pnpm => "npm",
+        LockfileKind::Poetry => "PyPI",
+        LockfileKind::Go => "Go",
+    }
+}
+
+/// PyPI names compare after PEP 503 normalization; other registries are exact
+fn package_key(ecosystem: &str, name: &str) -> String {
+    match ecosystem {
+        "PyPI" => name.to_lowercase().replace(['_', '.'], "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// Check every resolved package of the graphs against the advisories
+pub fn audit(graphs: &[DependencyGraph], advisories: &[Advisory], database_path: &Path) -> AuditReport {
+    let mut index: HashMap<(&str, String), Vec<&Advisory>> = HashMap::new();
+    for advisory in advisories {
+        index
+            .entry((advisory.ecosystem.as_str(), package_key(&advisory.ecosystem, &advisory.package)))
+            .or_default()
+            .push(advisory);
+    }
+
+    let mut findings = Vec::new();
+    let mut packages_scanned = 0;
+    for graph in graphs {
+        let ecosystem = ecosystem(graph.kind);
+        // The project's own packages are not published releases
+        for package in graph.packages.iter().filter(|p| !graph.roots.contains(&p.id)) {
+            packages_scanned += 1;
+            let Some(candidates) = index.get(&(ecosystem, package_key(ecosystem, &package.name))) else {
+                continue;
+            };
+            for advisory in candidates.iter().filter(|a| versions::is_affected(ecosystem, &package.version, &a.affected)) {
+                findings.push(VulnerabilityFinding {
+                    advisory_id: advisory.id.clone(),
+                    aliases: advisory.aliases.clone(),
+                    kind: graph.kind,
+                    package: package.name.clone(),
+                    version: package.version.clone(),
+                    severity: advisory.severity,
+                    cvss: advisory.cvss.clone(),
+                    score: advisory.score,
+                    summary: advisory.summary.clone(),
+                    url: advisory.url.clone(),
+                    informational: advisory.informational.clone(),
+                    affected_ranges: advisory.affected_ranges.clone(),
+                    patched: advisory.patched.clone(),
+                    paths: graph.why(&package.id).into_iter().map(|p| p.path).collect(),
+                });
+            }
+        }
+    }
+
+    // The same issue is often published under several ids; keep one finding per package version
+    findings.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.package.cmp(&b.package))
+            .then_with(|| a.version.cmp(&b.version))
+            .then_with(|| a.advisory_id.cmp(&b.advisory_id))
+    });
+    let identifiers = |f: &VulnerabilityFinding| -> Vec<String> {
+        std::iter::once(f.advisory_id.clone()).chain(f.aliases.iter().cloned()).collect()
+    };
+    let mut kept: Vec<VulnerabilityFinding> = Vec::new();
+    for finding in findings {
+        let ids = identifiers(&finding);
+        let duplicate = kept.iter().any(|k| {
+            k.package == finding.package && k.version == finding.version && identifiers(k).iter().any(|id| ids.contains(id))
+        });
+        if !duplicate {
+            kept.push(finding);
+        }
+    }
+
+    AuditReport {
+        findings: kept,
+        packages_scanned,
+        advisories_loaded: advisories.len(),
+        lockfiles: graphs.iter().map(|g| g.lockfile.clone()).collect(),
+        database_path: database_path.to_string_lossy().to_string(),
+        generated_at: crate::core::current_timestamp(),
+    }
+}
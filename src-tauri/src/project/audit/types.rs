@@ -0,0 +1,123 @@
+// Syntari AI IDE - Dependency Audit Types
+// Advisories, findings and the audit report
+
+use serde::{Deserialize, Serialize};
+use crate::project::lockfile::LockfileKind;
+
+// ================================
+// ADVISORY TYPES
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// No CVSS vector or rating the audit could read
+    Unknown,
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// CVSS v3 qualitative rating for a base score
+    pub fn from_score(score: f64) -> Self {
+        match score {
+            s if s >= 9.0 => Self::Critical,
+            s if s >= 7.0 => Self::High,
+            s if s >= 4.0 => Self::Medium,
+            s if s > 0.0 => Self::Low,
+            _ => Self::None,
+        }
+    }
+
+    pub fn from_label(label: &str) -> Self {
+        match label.to_lowercase().as_str() {
+            "critical" => Self::Critical,
+            "high" => Self::High,
+            "moderate" | "medium" => Self::Medium,
+            "low" => Self::Low,
+            "none" => Self::None,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Which versions an advisory covers, in its database's own terms
+#[derive(Debug, Clone)]
+pub enum AffectedVersions {
+    /// RustSec: every version not matched by a patched or unaffected requirement
+    RustSec {
+        patched: Vec<semver::VersionReq>,
+        unaffected: Vec<semver::VersionReq>,
+    },
+    /// OSV: exact versions plus introduced/fixed event ranges
+    Osv {
+        versions: Vec<String>,
+        ranges: Vec<Vec<RangeEvent>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeEvent {
+    Introduced(String),
+    Fixed(String),
+    LastAffected(String),
+}
+
+/// One advisory for one package, normalized from RustSec or OSV
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    pub aliases: Vec<String>,
+    /// OSV ecosystem name, e.g. `crates.io`, `npm`, `PyPI`, `Go`
+    pub ecosystem: String,
+    pub package: String,
+    pub summary: String,
+    pub severity: Severity,
+    pub cvss: Option<String>,
+    pub score: Option<f64>,
+    pub url: Option<String>,
+    /// RustSec `informational` kind, e.g. `unmaintained`
+    pub informational: Option<String>,
+    pub affected: AffectedVersions,
+    /// Human-readable affected ranges
+    pub affected_ranges: Vec<String>,
+    /// Versions or requirements that contain the fix
+    pub patched: Vec<String>,
+}
+
+// ================================
+// REPORT TYPES
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulnerabilityFinding {
+    pub advisory_id: String,
+    pub aliases: Vec<String>,
+    pub kind: LockfileKind,
+    pub package: String,
+    pub version: String,
+    pub severity: Severity,
+    pub cvss: Option<String>,
+    pub score: Option<f64>,
+    pub summary: String,
+    pub url: Option<String>,
+    pub informational: Option<String>,
+    pub affected_ranges: Vec<String>,
+    pub patched: Vec<String>,
+    /// Dependency paths from the project's own packages to the vulnerable one
+    pub paths: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditReport {
+    /// Most severe first
+    pub findings: Vec<VulnerabilityFinding>,
+    pub packages_scanned: usize,
+    pub advisories_loaded: usize,
+    pub lockfiles: Vec<String>,
+    pub database_path: String,
+    pub generated_at: u64,
+}
@@ -0,0 +1,232 @@
+// Syntari AI IDE - Ecosystem Version Ordering
+// Semver for crates.io, npm and Go; PEP 440 for PyPI; segment ordering for the rest
+
+use std::cmp::Ordering;
+use crate::project::audit::types::{AffectedVersions, RangeEvent};
+
+/// Order two versions under the ecosystem's own rules
+pub fn compare_versions(ecosystem: &str, a: &str, b: &str) -> Ordering {
+    match ecosystem {
+        "PyPI" => Pep440::parse(a).cmp(&Pep440::parse(b)),
+        "crates.io" | "npm" | "Go" => match (parse_semver(a), parse_semver(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => compare_segments(a, b),
+        },
+        _ => compare_segments(a, b),
+    }
+}
+
+/// Whether `version` of a package falls in an advisory's affected set
+pub fn is_affected(ecosystem: &str, version: &str, affected: &AffectedVersions) -> bool {
+    match affected {
+        AffectedVersions::RustSec { patched, unaffected } => {
+            let Some(version) = parse_semver(version) else {
+                return false;
+            };
+            !patched.iter().chain(unaffected).any(|req| req.matches(&version))
+        }
+        AffectedVersions::Osv { versions, ranges } => {
+            versions.iter().any(|v| compare_versions(ecosystem, v, version) == Ordering::Equal)
+                || ranges.iter().any(|events| in_range(ecosystem, version, events))
+        }
+    }
+}
+
+/// Walk OSV range events in version order, toggling between affected and not affected
+fn in_range(ecosystem: &str, version: &str, events: &[RangeEvent]) -> bool {
+    let bound = |event: &RangeEvent| -> String {
+        match event {
+            RangeEvent::Introduced(v) | RangeEvent::Fixed(v) | RangeEvent::LastAffected(v) => v.clone(),
+        }
+    };
+    let mut sorted: Vec<&RangeEvent> = events.iter().collect();
+    // `introduced: "0"` means from the very first version
+    sorted.sort_by(|a, b| match (bound(a).as_str(), bound(b).as_str()) {
+        ("0", "0") => Ordering::Equal,
+        ("0", _) => Ordering::Less,
+        (_, "0") => Ordering::Greater,
+        (x, y) => compare_versions(ecosystem, x, y),
+    });
+
+    let mut affected = false;
+    for event in sorted {
+        let event_version = bound(event);
+        let order = if event_version == "0" { Ordering::Greater } else { compare_versions(ecosystem, version, &event_version) };
+        match event {
+            RangeEvent::Introduced(_) if order != Ordering::Less => affected = true,
+            RangeEvent::Fixed(_) if order != Ordering::Less => affected = false,
+            RangeEvent::LastAffected(_) if order == Ordering::Greater => affected = false,
+            _ => break,
+        }
+    }
+    affected
+}
+
+/// Lenient semver: a leading `v`, missing minor/patch components and build metadata are accepted
+pub fn parse_semver(version: &str) -> Option<semver::Version> {
+    let version = version.trim().trim_start_matches('v');
+    if let Ok(parsed) = semver::Version::parse(version) {
+        return Some(parsed);
+    }
+    let (core, rest) = match version.find(['-', '+']) {
+        Some(index) => version.split_at(index),
+        None => (version, ""),
+    };
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    semver::Version::parse(&format!("{}{}", parts.join("."), rest)).ok()
+}
+
+// ================================
+// PEP 440
+// ================================
+
+/// Sort key for a PEP 440 version: dev releases sort before pre-releases, which sort before the final and post releases
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Pep440 {
+    epoch: u64,
+    release: Vec<u64>,
+    /// (-1, 0, 0) for a bare dev release, (0, phase, n) for a pre-release, (1, 0, 0) otherwise
+    pre: (i8, u8, u64),
+    post: Option<u64>,
+    dev: DevKey,
+}
+
+/// A release without `.devN` follows its dev releases
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum DevKey {
+    Dev(u64),
+    Release,
+}
+
+impl Pep440 {
+    fn parse(version: &str) -> Self {
+        let mut rest = version.trim().to_lowercase();
+        if let Some(stripped) = rest.strip_prefix('v') {
+            rest = stripped.to_string();
+        }
+        // Local versions (`+cpu`) do not affect range checks
+        if let Some(index) = rest.find('+') {
+            rest.truncate(index);
+        }
+
+        let mut epoch = 0;
+        if let Some((e, r)) = rest.split_once('!') {
+            epoch = e.parse().unwrap_or(0);
+            rest = r.to_string();
+        }
+
+        let release_end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let mut release: Vec<u64> = rest[..release_end].split('.').filter_map(|p| p.parse().ok()).collect();
+        while release.len() > 1 && release.last() == Some(&0) {
+            release.pop();
+        }
+        let suffix = rest[release_end..].replace(['-', '_', '.'], "");
+
+        let number_after = |text: &str, marker: &str| -> Option<u64> {
+            let start = text.find(marker)? + marker.len();
+            let digits: String = text[start..].chars().take_while(|c| c.is_ascii_digit()).collect();
+            Some(digits.parse().unwrap_or(0))
+        };
+        let dev = number_after(&suffix, "dev");
+        let post = number_after(&suffix, "post").or_else(|| number_after(&suffix, "rev")).or_else(|| {
+            // `1.0-1` is an implicit post release
+            rest[release_end..].strip_prefix('-').and_then(|n| n.parse().ok())
+        });
+        let pre_phases = [("alpha", 0), ("preview", 2), ("beta", 1), ("pre", 2), ("rc", 2), ("a", 0), ("b", 1), ("c", 2)];
+        let pre_source = suffix.split("post").next().unwrap_or("").split("dev").next().unwrap_or("");
+        let pre = pre_phases
+            .iter()
+            .find(|(marker, _)| pre_source.starts_with(marker))
+            .map(|(marker, phase)| (0, *phase, number_after(pre_source, marker).unwrap_or(0)));
+
+        Self {
+            epoch,
+            release,
+            pre: match pre {
+                Some(pre) => pre,
+                None if dev.is_some() && post.is_none() => (-1, 0, 0),
+                None => (1, 0, 0),
+            },
+            post,
+            dev: dev.map(DevKey::Dev).unwrap_or(DevKey::Release),
+        }
+    }
+}
+
+// ================================
+// SEGMENT ORDERING
+// ================================
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    Number(u64),
+    Text(String),
+}
+
+/// Maven, RubyGems and Composer style: numeric segments compare numerically and textual segments mark pre-releases
+fn compare_segments(a: &str, b: &str) -> Ordering {
+    let (a, b) = (segments(a), segments(b));
+    for index in 0..a.len().max(b.len()) {
+        let order = match (a.get(index), b.get(index)) {
+            (Some(Segment::Number(x)), Some(Segment::Number(y))) => x.cmp(y),
+            (Some(Segment::Text(x)), Some(Segment::Text(y))) => qualifier_rank(x).cmp(&qualifier_rank(y)).then_with(|| x.cmp(y)),
+            (Some(Segment::Number(_)), Some(Segment::Text(_))) => Ordering::Greater,
+            (Some(Segment::Text(_)), Some(Segment::Number(_))) => Ordering::Less,
+            // A trailing pre-release qualifier sorts before the bare release; trailing zeros change nothing
+            (Some(Segment::Text(x)), None) => qualifier_rank(x).cmp(&qualifier_rank("")),
+            (None, Some(Segment::Text(y))) => qualifier_rank("").cmp(&qualifier_rank(y)),
+            (Some(Segment::Number(x)), None) => x.cmp(&0),
+            (None, Some(Segment::Number(y))) => 0.cmp(y),
+            (None, None) => Ordering::Equal,
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+    Ordering::Equal
+}
+
+fn segments(version: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let flush = |current: &mut String, segments: &mut Vec<Segment>| {
+        if !current.is_empty() {
+            segments.push(match current.parse() {
+                Ok(number) => Segment::Number(number),
+                Err(_) => Segment::Text(current.to_lowercase()),
+            });
+            current.clear();
+        }
+    };
+    for c in version.trim().trim_start_matches('v').chars() {
+        if matches!(c, '.' | '-' | '_' | '+') {
+            flush(&mut current, &mut segments);
+        } else {
+            if current.chars().last().is_some_and(|last| last.is_ascii_digit() != c.is_ascii_digit()) {
+                flush(&mut current, &mut segments);
+            }
+            current.push(c);
+        }
+    }
+    flush(&mut current, &mut segments);
+    segments
+}
+
+fn qualifier_rank(qualifier: &str) -> u8 {
+    match qualifier {
+        "alpha" | "a" => 1,
+        "beta" | "b" => 2,
+        "milestone" | "m" => 3,
+        "rc" | "cr" => 4,
+        "snapshot" => 5,
+        "" | "ga" | "final" | "release" => 6,
+        "sp" | "p" | "pl" | "patch" => 7,
+        _ => 0,
+    }
+}
@@ -0,0 +1,550 @@
+// Syntari AI IDE - Cargo Workspace Model
+// Parses Cargo manifests into packages, dependencies, targets and features
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+use crate::core::{AppError, AppResult};
+use crate::project::types::{Dependency, DependencyScope};
+
+pub const MANIFEST_FILE: &str = "Cargo.toml";
+
+// ================================
+// MODEL TYPES
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CargoWorkspace {
+    /// Directory holding the workspace (or lone package) manifest
+    pub root: String,
+    pub manifest_path: String,
+    /// True when the root manifest has `[workspace]` but no `[package]`
+    pub is_virtual: bool,
+    pub packages: Vec<CargoPackage>,
+    /// `[workspace.dependencies]`, inherited by members with `workspace = true`
+    pub workspace_dependencies: Vec<CargoDependency>,
+    pub default_members: Vec<String>,
+}
+
+impl CargoWorkspace {
+    pub fn package(&self, name: &str) -> Option<&CargoPackage> {
+        self.packages.iter().find(|package| package.name == name)
+    }
+
+    /// The package whose directory contains `path`, preferring the most specific
+    pub fn package_for_path(&self, path: &Path) -> Option<&CargoPackage> {
+        self.packages
+            .iter()
+            .filter(|package| path.starts_with(&package.root))
+            .max_by_key(|package| package.root.len())
+    }
+
+    /// Every dependency across all packages as records, inherited requirements resolved from the workspace
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        let mut dependencies: Vec<Dependency> = Vec::new();
+        for package in &self.packages {
+            let manifest = Path::new(&package.manifest_path)
+                .strip_prefix(&self.root)
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_else(|_| package.manifest_path.clone());
+            for dep in &package.dependencies {
+                let version_req = dep.version_req.clone().or_else(|| {
+                    self.workspace_dependencies
+                        .iter()
+                        .find(|shared| dep.inherited && shared.name == dep.name)
+                        .and_then(|shared| shared.version_req.clone())
+                });
+                let scope = match dep.kind {
+                    DependencyKind::Normal => DependencyScope::Runtime,
+                    DependencyKind::Dev => DependencyScope::Dev,
+                    DependencyKind::Build => DependencyScope::Build,
+                };
+                let record = Dependency::new(dep.package_name(), version_req, scope, &manifest);
+                if !dependencies.iter().any(|d| d.name == record.name && d.scope == record.scope) {
+                    dependencies.push(record);
+                }
+            }
+        }
+        dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+        dependencies
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CargoPackage {
+    pub name: String,
+    pub version: Option<String>,
+    pub edition: Option<String>,
+    pub root: String,
+    pub manifest_path: String,
+    pub dependencies: Vec<CargoDependency>,
+    pub targets: Vec<CargoTarget>,
+    /// `[features]`, including `default`
+    pub features: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DependencySource {
+    Registry { registry: Option<String> },
+    Path { path: String },
+    Git { url: String, reference: Option<String> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CargoDependency {
+    /// Name used in code; differs from the crate name when renamed with `package = "..."`
+    pub name: String,
+    pub package: Option<String>,
+    pub kind: DependencyKind,
+    /// `cfg(...)` or target triple for `[target.'...'.dependencies]`
+    pub target: Option<String>,
+    pub version_req: Option<String>,
+    pub source: DependencySource,
+    pub optional: bool,
+    pub default_features: bool,
+    pub features: Vec<String>,
+    /// Declared with `workspace = true`
+    pub inherited: bool,
+}
+
+impl CargoDependency {
+    pub fn package_name(&self) -> &str {
+        self.package.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetKind {
+    Lib,
+    Bin,
+    Test,
+    Example,
+    Bench,
+    BuildScript,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CargoTarget {
+    pub name: String,
+    pub kind: TargetKind,
+    pub path: String,
+    pub required_features: Vec<String>,
+    /// Cargo arguments for the natural action on this target, e.g. `run -p app --bin app`
+    pub command: Vec<String>,
+}
+
+// ================================
+// LOADING
+// ================================
+
+/// Load the workspace containing `dir`: the manifest there if it declares `[workspace]`,
+/// otherwise the nearest ancestor workspace listing it, otherwise the package alone
+pub fn load_workspace(dir: &Path) -> AppResult<CargoWorkspace> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let manifest = read_manifest(&manifest_path)?;
+
+    if manifest.contains_key("workspace") {
+        return load_from_root(dir, &manifest);
+    }
+
+    let explicit_root = manifest
+        .get("package")
+        .and_then(|p| p.get("workspace"))
+        .and_then(Value::as_str)
+        .map(|relative| dir.join(relative));
+    let candidates: Vec<PathBuf> = match explicit_root {
+        Some(root) => vec![root],
+        None => dir.ancestors().skip(1).map(Path::to_path_buf).collect(),
+    };
+    for candidate in candidates {
+        let Ok(root_manifest) = read_manifest(&candidate.join(MANIFEST_FILE)) else {
+            continue;
+        };
+        if !root_manifest.contains_key("workspace") {
+            continue;
+        }
+        let workspace = load_from_root(&candidate, &root_manifest)?;
+        if workspace.packages.iter().any(|package| Path::new(&package.root) == dir) {
+            return Ok(workspace);
+        }
+        break;
+    }
+
+    let package = load_package(dir, &manifest, None)?;
+    Ok(CargoWorkspace {
+        root: dir.to_string_lossy().to_string(),
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+        is_virtual: false,
+        packages: vec![package],
+        workspace_dependencies: Vec::new(),
+        default_members: Vec::new(),
+    })
+}
+
+fn load_from_root(root: &Path, manifest: &Table) -> AppResult<CargoWorkspace> {
+    let workspace = manifest.get("workspace").and_then(Value::as_table).cloned().unwrap_or_default();
+    let workspace_dependencies = workspace
+        .get("dependencies")
+        .and_then(Value::as_table)
+        .map(|deps| parse_dependency_table(deps, DependencyKind::Normal, None, root, None))
+        .unwrap_or_default();
+    let inherited = Inherited {
+        package: workspace.get("package").and_then(Value::as_table).cloned().unwrap_or_default(),
+        dependencies: workspace_dependencies.clone(),
+    };
+
+    let excluded: Vec<PathBuf> = string_list(workspace.get("exclude")).iter().map(|e| root.join(e)).collect();
+    let mut member_dirs = Vec::new();
+    if manifest.contains_key("package") {
+        member_dirs.push(root.to_path_buf());
+    }
+    for pattern in string_list(workspace.get("members")) {
+        for dir in expand_member(root, &pattern) {
+            if !excluded.iter().any(|excluded| dir.starts_with(excluded)) && !member_dirs.contains(&dir) {
+                member_dirs.push(dir);
+            }
+        }
+    }
+
+    let mut packages = Vec::new();
+    for dir in member_dirs {
+        let result = if dir == root {
+            load_package(root, manifest, Some(&inherited))
+        } else {
+            read_manifest(&dir.join(MANIFEST_FILE)).and_then(|member| load_package(&dir, &member, Some(&inherited)))
+        };
+        match result {
+            Ok(package) => packages.push(package),
+            Err(e) => e.log_warn(),
+        }
+    }
+
+    Ok(CargoWorkspace {
+        root: root.to_string_lossy().to_string(),
+        manifest_path: root.join(MANIFEST_FILE).to_string_lossy().to_string(),
+        is_virtual: !manifest.contains_key("package"),
+        packages,
+        workspace_dependencies,
+        default_members: string_list(workspace.get("default-members")),
+    })
+}
+
+/// Member patterns are paths relative to the workspace root and may use glob syntax
+fn expand_member(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let full = root.join(pattern);
+    let mut dirs: Vec<PathBuf> = match glob::glob(&full.to_string_lossy()) {
+        // Normalizing drops `.` segments so `members = ["."]` matches the root itself
+        Ok(paths) => paths.flatten().map(|path| path.components().collect()).collect(),
+        Err(e) => {
+            tracing::warn!("Invalid workspace member pattern '{}': {}", pattern, e);
+            Vec::new()
+        }
+    };
+    dirs.retain(|dir| dir.join(MANIFEST_FILE).is_file());
+    dirs.sort();
+    dirs
+}
+
+fn read_manifest(path: &Path) -> AppResult<Table> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AppError::filesystem_with_path(
+            "MANIFEST_READ_FAILED".to_string(),
+            format!("Cannot read Cargo manifest: {}", e),
+            path.to_string_lossy().to_string(),
+        )
+    })?;
+    content.parse::<Table>().map_err(|e| {
+        AppError::project_with_path(
+            "MANIFEST_PARSE_FAILED".to_string(),
+            format!("Invalid Cargo manifest: {}", e.message()),
+            path.to_string_lossy().to_string(),
+        )
+    })
+}
+
+// ================================
+// PACKAGES
+// ================================
+
+/// Workspace values members can inherit
+struct Inherited {
+    package: Table,
+    dependencies: Vec<CargoDependency>,
+}
+
+fn load_package(dir: &Path, manifest: &Table, inherited: Option<&Inherited>) -> AppResult<CargoPackage> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let package = manifest.get("package").and_then(Value::as_table).ok_or_else(|| {
+        AppError::project_with_path(
+            "NOT_A_PACKAGE".to_string(),
+            "Cargo manifest has no [package] section".to_string(),
+            manifest_path.to_string_lossy().to_string(),
+        )
+    })?;
+    let name = package.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+
+    // `version.workspace = true` and friends read from `[workspace.package]`
+    let field = |key: &str| -> Option<String> {
+        match package.get(key)? {
+            Value::String(value) => Some(value.clone()),
+            Value::Table(table) if table.get("workspace").and_then(Value::as_bool) == Some(true) => {
+                inherited?.package.get(key).and_then(Value::as_str).map(str::to_string)
+            }
+            _ => None,
+        }
+    };
+
+    let inherited_deps = inherited.map(|i| i.dependencies.as_slice()).unwrap_or_default();
+    let mut dependencies = Vec::new();
+    for (section, kind) in [
+        ("dependencies", DependencyKind::Normal),
+        ("dev-dependencies", DependencyKind::Dev),
+        ("dev_dependencies", DependencyKind::Dev),
+        ("build-dependencies", DependencyKind::Build),
+        ("build_dependencies", DependencyKind::Build),
+    ] {
+        if let Some(table) = manifest.get(section).and_then(Value::as_table) {
+            dependencies.extend(parse_dependency_table(table, kind, None, dir, Some(inherited_deps)));
+        }
+        if let Some(targets) = manifest.get("target").and_then(Value::as_table) {
+            for (target, spec) in targets {
+                if let Some(table) = spec.get(section).and_then(Value::as_table) {
+                    dependencies.extend(parse_dependency_table(table, kind, Some(target), dir, Some(inherited_deps)));
+                }
+            }
+        }
+    }
+
+    let features = manifest
+        .get("features")
+        .and_then(Value::as_table)
+        .map(|table| table.iter().map(|(feature, enables)| (feature.clone(), string_list(Some(enables)))).collect())
+        .unwrap_or_default();
+
+    Ok(CargoPackage {
+        targets: discover_targets(dir, &name, package, manifest),
+        version: field("version"),
+        edition: field("edition"),
+        root: dir.to_string_lossy().to_string(),
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+        dependencies,
+        features,
+        name,
+    })
+}
+
+fn parse_dependency_table(
+    table: &Table,
+    kind: DependencyKind,
+    target: Option<&str>,
+    base: &Path,
+    inherited: Option<&[CargoDependency]>,
+) -> Vec<CargoDependency> {
+    table
+        .iter()
+        .map(|(name, spec)| {
+            let mut dependency = CargoDependency {
+                name: name.clone(),
+                package: None,
+                kind,
+                target: target.map(str::to_string),
+                version_req: None,
+                source: DependencySource::Registry { registry: None },
+                optional: false,
+                default_features: true,
+                features: Vec::new(),
+                inherited: false,
+            };
+
+            match spec {
+                Value::String(version) => dependency.version_req = Some(version.clone()),
+                Value::Table(spec) => {
+                    let inherits = spec.get("workspace").and_then(Value::as_bool) == Some(true);
+                    if let Some(base_dependency) = inherited.filter(|_| inherits).and_then(|deps| deps.iter().find(|d| d.name == *name)) {
+                        dependency.package = base_dependency.package.clone();
+                        dependency.version_req = base_dependency.version_req.clone();
+                        dependency.source = base_dependency.source.clone();
+                        dependency.default_features = base_dependency.default_features;
+                        dependency.features = base_dependency.features.clone();
+                    }
+                    dependency.inherited = inherits;
+
+                    if let Some(version) = spec.get("version").and_then(Value::as_str) {
+                        dependency.version_req = Some(version.to_string());
+                    }
+                    if let Some(package) = spec.get("package").and_then(Value::as_str) {
+                        dependency.package = Some(package.to_string());
+                    }
+                    if let Some(path) = spec.get("path").and_then(Value::as_str) {
+                        dependency.source = DependencySource::Path { path: base.join(path).to_string_lossy().to_string() };
+                    } else if let Some(url) = spec.get("git").and_then(Value::as_str) {
+                        let reference = ["rev", "tag", "branch"]
+                            .iter()
+                            .find_map(|key| spec.get(*key).and_then(Value::as_str).map(|value| format!("{}={}", key, value)));
+                        dependency.source = DependencySource::Git { url: url.to_string(), reference };
+                    } else if let Some(registry) = spec.get("registry").and_then(Value::as_str) {
+                        dependency.source = DependencySource::Registry { registry: Some(registry.to_string()) };
+                    }
+                    dependency.optional = spec.get("optional").and_then(Value::as_bool).unwrap_or(false);
+                    if let Some(default_features) = spec
+                        .get("default-features")
+                        .or_else(|| spec.get("default_features"))
+                        .and_then(Value::as_bool)
+                    {
+                        dependency.default_features = default_features;
+                    }
+                    // Member features add to the ones the workspace already enables
+                    for feature in string_list(spec.get("features")) {
+                        if !dependency.features.contains(&feature) {
+                            dependency.features.push(feature);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            dependency
+        })
+        .collect()
+}
+
+// ================================
+// TARGETS
+// ================================
+
+/// Explicit `[lib]`/`[[bin]]`/... entries plus Cargo's automatic target discovery
+fn discover_targets(dir: &Path, package_name: &str, package: &Table, manifest: &Table) -> Vec<CargoTarget> {
+    let mut targets = Vec::new();
+    let auto = |key: &str| package.get(key).and_then(Value::as_bool).unwrap_or(true);
+    let crate_name = package_name.replace('-', "_");
+
+    // Library
+    let lib = manifest.get("lib").and_then(Value::as_table);
+    let lib_path = lib
+        .and_then(|lib| lib.get("path").and_then(Value::as_str).map(|p| dir.join(p)))
+        .unwrap_or_else(|| dir.join("src/lib.rs"));
+    if lib.is_some() || lib_path.is_file() {
+        let name = lib.and_then(|lib| lib.get("name").and_then(Value::as_str)).unwrap_or(&crate_name);
+        targets.push(target(name, TargetKind::Lib, &lib_path, Vec::new(), package_name));
+    }
+
+    for (kind, section, directory, autokey) in [
+        (TargetKind::Bin, "bin", "src/bin", "autobins"),
+        (TargetKind::Test, "test", "tests", "autotests"),
+        (TargetKind::Example, "example", "examples", "autoexamples"),
+        (TargetKind::Bench, "bench", "benches", "autobenches"),
+    ] {
+        let mut found: Vec<CargoTarget> = Vec::new();
+
+        for entry in manifest.get(section).and_then(Value::as_array).into_iter().flatten() {
+            let Some(name) = entry.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let path = entry
+                .get("path")
+                .and_then(Value::as_str)
+                .map(|p| dir.join(p))
+                .unwrap_or_else(|| default_target_path(dir, kind, directory, name, package_name));
+            found.push(target(name, kind, &path, string_list(entry.get("required-features")), package_name));
+        }
+
+        if auto(autokey) {
+            let mut discovered = Vec::new();
+            if kind == TargetKind::Bin && dir.join("src/main.rs").is_file() {
+                discovered.push((package_name.to_string(), dir.join("src/main.rs")));
+            }
+            discovered.extend(scan_target_dir(&dir.join(directory)));
+            for (name, path) in discovered {
+                // Explicit entries win, matched by name or by path
+                if !found.iter().any(|t| t.name == name || Path::new(&t.path) == path) {
+                    found.push(target(&name, kind, &path, Vec::new(), package_name));
+                }
+            }
+        }
+        targets.extend(found);
+    }
+
+    let build_script = match package.get("build") {
+        Some(Value::String(path)) => Some(dir.join(path)),
+        Some(Value::Boolean(false)) => None,
+        _ => Some(dir.join("build.rs")).filter(|path| path.is_file()),
+    };
+    if let Some(path) = build_script {
+        targets.push(target("build-script-build", TargetKind::BuildScript, &path, Vec::new(), package_name));
+    }
+    targets
+}
+
+fn default_target_path(dir: &Path, kind: TargetKind, directory: &str, name: &str, package_name: &str) -> PathBuf {
+    if kind == TargetKind::Bin && name == package_name {
+        return dir.join("src/main.rs");
+    }
+    let file = dir.join(directory).join(format!("{}.rs", name));
+    if file.is_file() {
+        file
+    } else {
+        dir.join(directory).join(name).join("main.rs")
+    }
+}
+
+/// `<dir>/*.rs` and `<dir>/*/main.rs`, named after the file or directory
+fn scan_target_dir(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                Some((path.file_stem()?.to_string_lossy().to_string(), path))
+            } else if path.join("main.rs").is_file() {
+                Some((path.file_name()?.to_string_lossy().to_string(), path.join("main.rs")))
+            } else {
+                None
+            }
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+fn target(name: &str, kind: TargetKind, path: &Path, required_features: Vec<String>, package_name: &str) -> CargoTarget {
+    let mut command: Vec<String> = match kind {
+        TargetKind::Lib => vec!["build".into(), "-p".into(), package_name.into(), "--lib".into()],
+        TargetKind::Bin => vec!["run".into(), "-p".into(), package_name.into(), "--bin".into(), name.into()],
+        TargetKind::Test => vec!["test".into(), "-p".into(), package_name.into(), "--test".into(), name.into()],
+        TargetKind::Example => vec!["run".into(), "-p".into(), package_name.into(), "--example".into(), name.into()],
+        TargetKind::Bench => vec!["bench".into(), "-p".into(), package_name.into(), "--bench".into(), name.into()],
+        TargetKind::BuildScript => vec!["build".into(), "-p".into(), package_name.into()],
+    };
+    if !required_features.is_empty() {
+        command.push("--features".into());
+        command.push(required_features.join(","));
+    }
+
+    CargoTarget {
+        name: name.to_string(),
+        kind,
+        path: path.to_string_lossy().to_string(),
+        required_features,
+        command,
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
@@ -3,11 +3,25 @@
 
 use tauri::State;
 use std::path::Path;
-use crate::core::{AppState, TauriResult, FileInfo, create_file_info_with_content, validate_directory};
-use crate::project::types::{ProjectContext, ProjectType, Framework};
+use crate::core::{AppError, AppResult, AppState, app_data_subdir, TauriResult, FileInfo, create_file_info_with_content, validate_directory};
+use crate::project::analyzer::ProjectAnalyzer;
+use crate::project::audit::{self, AuditReport, ADVISORY_DIR};
+use crate::project::cargo::{self, CargoWorkspace};
+use crate::project::dependencies::detect_dependencies;
+use crate::project::diagnostics::{DiagnosticTool, DiagnosticsManager, DiagnosticsSnapshot};
+use crate::project::lockfile::{self, DependencyGraph, DependencyPath, DuplicatePackage};
+use crate::project::monorepo::detect_project_tree;
+use crate::project::tasks::{self, TaskDefinition, TaskRun, TaskRunner};
+use crate::project::testing::{self, TestExplorer, TestNode, TestRun};
+use crate::project::types::{ProjectAnalysis, ProjectContext, ProjectType, Framework, SubProject};
+use crate::project::workspace::{RecentProject, RestoredWorkspace, WorkspaceState, WorkspaceStore};
 
 #[tauri::command]
-pub async fn open_project(path: String, state: State<'_, AppState>) -> std::result::Result<TauriResult<ProjectContext>, String> {
+pub async fn open_project(
+    path: String,
+    state: State<'_, AppState>,
+    workspaces: State<'_, WorkspaceStore>,
+) -> std::result::Result<TauriResult<ProjectContext>, String> {
     tracing::info!("Opening project at: {}", path);
     
     let project_path = Path::new(&path);
@@ -18,24 +32,582 @@ pub async fn open_project(path: String, state: State<'_, AppState>) -> std::resu
         return Ok(TauriResult::error(e.message().to_string()));
     }
     
+    let project_context = build_project_context(&path).await;
+    
+    // Update application state
+    if let Err(e) = state.set_current_project(project_context.clone()).await {
+        tracing::error!("Failed to update application state: {:?}", e);
+        return Ok(TauriResult::error("Failed to update application state".to_string()));
+    }
+    
+    // A failed MRU write should not block opening the project
+    if let Err(e) = workspaces.record_opened(&project_context) {
+        e.log_warn();
+    }
+    
+    tracing::info!("Successfully opened project: {} (type: {})", path, project_context.project_type);
+    Ok(TauriResult::success(project_context))
+}
+
+/// Recently opened projects, newest first; `exists` is false for projects whose directory is gone
+#[tauri::command]
+pub async fn list_recent_projects(
+    limit: Option<usize>,
+    workspaces: State<'_, WorkspaceStore>,
+) -> std::result::Result<TauriResult<Vec<RecentProject>>, String> {
+    Ok(TauriResult::success(workspaces.recent_projects(limit)))
+}
+
+/// Drop a project from the recent list together with its saved workspace state
+#[tauri::command]
+pub async fn remove_recent_project(
+    path: String,
+    workspaces: State<'_, WorkspaceStore>,
+) -> std::result::Result<TauriResult<()>, String> {
+    let result = workspaces.remove(&path);
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Persist tabs, cursors, expanded folders, terminals and the last chat session of a project
+#[tauri::command]
+pub async fn save_workspace_state(
+    workspace: WorkspaceState,
+    workspaces: State<'_, WorkspaceStore>,
+) -> std::result::Result<TauriResult<()>, String> {
+    let result = if workspace.project_path.is_empty() {
+        Err(AppError::validation_with_field("MISSING_PROJECT_PATH", "Workspace state needs a project path", "project_path"))
+    } else {
+        workspaces.save_state(&workspace)
+    };
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Open a project and return its saved workspace state, minus anything that no longer exists
+#[tauri::command]
+pub async fn restore_workspace(
+    path: String,
+    state: State<'_, AppState>,
+    workspaces: State<'_, WorkspaceStore>,
+    chat_store: State<'_, crate::chat::ChatStore>,
+) -> std::result::Result<TauriResult<RestoredWorkspace>, String> {
+    let result: AppResult<RestoredWorkspace> = async {
+        validate_directory(Path::new(&path))?;
+        let mut project = build_project_context(&path).await;
+        
+        let mut workspace = workspaces.load_state(&path);
+        let missing_paths = workspace.prune_missing();
+        if workspace.last_chat_session.as_deref().is_some_and(|id| !chat_store.contains(id)) {
+            workspace.last_chat_session = None;
+        }
+        if let Some(file) = &workspace.active_file {
+            let file_path = Path::new(&project.root_path).join(file).to_string_lossy().to_string();
+            project.set_active_file(&file_path);
+        }
+        
+        state.set_current_project(project.clone()).await?;
+        if let Err(e) = workspaces.record_opened(&project) {
+            e.log_warn();
+        }
+        
+        tracing::info!(
+            "🗂️ Restored workspace for {}: {} tabs, {} missing paths",
+            path,
+            workspace.open_tabs.len(),
+            missing_paths.len()
+        );
+        Ok(RestoredWorkspace { project, state: workspace, missing_paths })
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Walk the project and compute language statistics, complexity, framework confidence and suggestions
+#[tauri::command]
+pub async fn analyze_project(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<ProjectAnalysis>, String> {
+    let result = async {
+        let root = state.resolve_project_root(project_path).await?;
+        let context = match state.get_current_project().await {
+            Some(project) if project.root_path == root => project,
+            _ => build_project_context(&root).await,
+        };
+        
+        // The walk reads every source file, so keep it off the async runtime
+        tokio::task::spawn_blocking(move || ProjectAnalyzer::new().analyze(Path::new(&root), context))
+            .await
+            .map_err(|e| AppError::internal("ANALYSIS_FAILED".to_string(), format!("Project analysis did not complete: {}", e)))?
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Packages, dependencies, targets and features of the Cargo workspace at (or containing) a directory
+#[tauri::command]
+pub async fn get_cargo_workspace(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<CargoWorkspace>, String> {
+    let result = async {
+        let root = state.resolve_project_root(project_path).await?;
+        cargo::load_workspace(Path::new(&root))
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Resolved packages and the edges between them from every lockfile in the project
+#[tauri::command]
+pub async fn get_dependency_graph(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<Vec<DependencyGraph>>, String> {
+    let result = load_dependency_graphs(&state, project_path).await;
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Reverse dependency paths explaining why a package is installed
+#[tauri::command]
+pub async fn why_dependency(
+    name: String,
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<Vec<DependencyPath>>, String> {
+    let result = async {
+        let graphs = load_dependency_graphs(&state, project_path).await?;
+        let paths: Vec<DependencyPath> = graphs.iter().flat_map(|graph| graph.why(&name)).collect();
+        if paths.is_empty() {
+            return Err(AppError::validation_with_field(
+                "PACKAGE_NOT_LOCKED",
+                &format!("'{}' is not in any lockfile", name),
+                "name",
+            ));
+        }
+        Ok(paths)
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Packages resolved at more than one version
+#[tauri::command]
+pub async fn find_duplicate_dependencies(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<Vec<DuplicatePackage>>, String> {
+    let result: AppResult<Vec<DuplicatePackage>> = async {
+        let graphs = load_dependency_graphs(&state, project_path).await?;
+        Ok(graphs.iter().flat_map(DependencyGraph::duplicates).collect())
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Match locked packages against advisory databases on disk; nothing leaves the machine
+#[tauri::command]
+pub async fn audit_dependencies(
+    project_path: Option<String>,
+    database_path: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<AuditReport>, String> {
+    let result: AppResult<AuditReport> = async {
+        let database = match database_path {
+            Some(path) => {
+                validate_directory(Path::new(&path))?;
+                std::path::PathBuf::from(path)
+            }
+            None => app_data_subdir(&app_handle, ADVISORY_DIR)?,
+        };
+        let graphs = load_dependency_graphs(&state, project_path).await?;
+        
+        let report = tokio::task::spawn_blocking(move || {
+            let advisories = audit::database::load_advisories(&database);
+            if advisories.is_empty() {
+                return Err(AppError::project_with_path(
+                    "NO_ADVISORIES".to_string(),
+                    "No advisories found; sync the RustSec advisory-db or OSV exports into this directory".to_string(),
+                    database.to_string_lossy().to_string(),
+                ));
+            }
+            Ok(audit::audit(&graphs, &advisories, &database))
+        })
+        .await
+        .map_err(|e| AppError::internal("AUDIT_FAILED".to_string(), format!("Dependency audit did not complete: {}", e)))??;
+        
+        tracing::info!("🛡️ Audited {} packages: {} findings", report.packages_scanned, report.findings.len());
+        Ok(report)
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Runnable tasks of the project and its sub-projects: scripts, Cargo targets, Make and just recipes
+#[tauri::command]
+pub async fn list_tasks(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<Vec<TaskDefinition>>, String> {
+    let result = discover_project_tasks(&state, project_path).await.map(|(_, tasks)| tasks);
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Run a task, after its dependencies, in a dedicated terminal session; progress arrives as `task-status` events
+#[tauri::command]
+pub async fn run_task(
+    task_id: String,
+    project_path: Option<String>,
+    cols: Option<u16>,
+    rows: Option<u16>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    runner: State<'_, TaskRunner>,
+) -> std::result::Result<TauriResult<TaskRun>, String> {
+    let result: AppResult<TaskRun> = async {
+        let (root, tasks) = discover_project_tasks(&state, project_path).await?;
+        runner.start(&app_handle, &root, &tasks, &task_id, (cols.unwrap_or(100), rows.unwrap_or(30)))
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Run the most recently started task again, rediscovering it so edited scripts are picked up
+#[tauri::command]
+pub async fn rerun_last_task(
+    cols: Option<u16>,
+    rows: Option<u16>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    runner: State<'_, TaskRunner>,
+) -> std::result::Result<TauriResult<TaskRun>, String> {
+    let result: AppResult<TaskRun> = async {
+        let last = runner.last_task().ok_or_else(|| AppError::project("NO_LAST_TASK", "No task has been run yet"))?;
+        let (root, tasks) = discover_project_tasks(&state, Some(last.project_path)).await?;
+        runner.start(&app_handle, &root, &tasks, &last.task_id, (cols.unwrap_or(100), rows.unwrap_or(30)))
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn cancel_task(
+    run_id: String,
+    app_handle: tauri::AppHandle,
+    runner: State<'_, TaskRunner>,
+) -> std::result::Result<TauriResult<()>, String> {
+    let result = runner.cancel(&app_handle, &run_id);
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Recent task runs with status, exit code and duration, newest first
+#[tauri::command]
+pub async fn get_task_history(
+    limit: Option<usize>,
+    runner: State<'_, TaskRunner>,
+) -> std::result::Result<TauriResult<Vec<TaskRun>>, String> {
+    Ok(TauriResult::success(runner.history(limit)))
+}
+
+/// Run cargo check, tsc, eslint and ruff where configured (or only `tools`); each tool's results also arrive as `diagnostics-updated` events
+#[tauri::command]
+pub async fn run_diagnostics(
+    project_path: Option<String>,
+    tools: Option<Vec<DiagnosticTool>>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    diagnostics: State<'_, DiagnosticsManager>,
+) -> std::result::Result<TauriResult<DiagnosticsSnapshot>, String> {
+    let result: AppResult<DiagnosticsSnapshot> = async {
+        let (root, sub_projects) = project_directories(&state, project_path).await?;
+        diagnostics.run(&app_handle, &root, sub_projects, tools.as_deref()).await
+    }
+    .await;
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Latest diagnostics of the project, optionally of one file, without running anything
+#[tauri::command]
+pub async fn get_diagnostics(
+    project_path: Option<String>,
+    file: Option<String>,
+    state: State<'_, AppState>,
+    diagnostics: State<'_, DiagnosticsManager>,
+) -> std::result::Result<TauriResult<DiagnosticsSnapshot>, String> {
+    let result = state
+        .resolve_project_root(project_path)
+        .await
+        .map(|root| diagnostics.snapshot(&root, file.as_deref()));
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Rerun the affected tools whenever a watched file of the project is saved
+#[tauri::command]
+pub async fn set_diagnostics_on_save(
+    enabled: bool,
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+    diagnostics: State<'_, DiagnosticsManager>,
+) -> std::result::Result<TauriResult<()>, String> {
+    let result = project_directories(&state, project_path)
+        .await
+        .map(|(root, sub_projects)| diagnostics.set_on_save(&root, sub_projects, enabled));
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// List the tests of every framework in the project as a tree of suites, files, modules and tests
+#[tauri::command]
+pub async fn discover_tests(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+    explorer: State<'_, TestExplorer>,
+) -> std::result::Result<TauriResult<Vec<TestNode>>, String> {
+    let result: AppResult<Vec<TestNode>> = async {
+        let (root, sub_projects) = project_directories(&state, project_path).await?;
+        let suites = testing::discover_suites(Path::new(&root), &sub_projects).await;
+        explorer.set_suites(&root, suites);
+        Ok(explorer.tree(&root))
+    }
+    .await;
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// The last discovered tree with the latest outcomes, without listing tests again
+#[tauri::command]
+pub async fn get_test_tree(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+    explorer: State<'_, TestExplorer>,
+) -> std::result::Result<TauriResult<Vec<TestNode>>, String> {
+    let result = state.resolve_project_root(project_path).await.map(|root| explorer.tree(&root));
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Run the tests under the given tree nodes (all when empty), or only those that failed last time;
+/// results arrive as `test-result` events and the run's progress as `test-run` events
+#[tauri::command]
+pub async fn run_tests(
+    node_ids: Option<Vec<String>>,
+    only_failed: Option<bool>,
+    project_path: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    explorer: State<'_, TestExplorer>,
+) -> std::result::Result<TauriResult<TestRun>, String> {
+    let result: AppResult<TestRun> = async {
+        let (root, sub_projects) = project_directories(&state, project_path).await?;
+        if !explorer.has_suites(&root) {
+            let suites = testing::discover_suites(Path::new(&root), &sub_projects).await;
+            explorer.set_suites(&root, suites);
+        }
+        explorer.start(&app_handle, &root, node_ids.unwrap_or_default(), only_failed.unwrap_or(false))
+    }
+    .await;
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+#[tauri::command]
+pub async fn cancel_test_run(
+    run_id: String,
+    explorer: State<'_, TestExplorer>,
+) -> std::result::Result<TauriResult<()>, String> {
+    let result = explorer.cancel(&run_id);
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Recent test runs with their results, newest first
+#[tauri::command]
+pub async fn get_test_runs(
+    limit: Option<usize>,
+    explorer: State<'_, TestExplorer>,
+) -> std::result::Result<TauriResult<Vec<TestRun>>, String> {
+    Ok(TauriResult::success(explorer.runs(limit)))
+}
+
+/// The project root and the root directories of its sub-projects, from the open project's tree when it matches
+pub(crate) async fn project_directories(state: &AppState, project_path: Option<String>) -> AppResult<(String, Vec<String>)> {
+    let root = state.resolve_project_root(project_path).await?;
+    let tree = match state.get_current_project().await {
+        Some(project) if project.root_path == root && project.project_tree.is_some() => project.project_tree,
+        _ => Some(detect_project_tree(Path::new(&root)).await),
+    };
+    let sub_projects: Vec<String> = tree
+        .map(|tree| tree.flatten().into_iter().map(|p| p.root_path.clone()).collect())
+        .unwrap_or_default();
+    Ok((root, sub_projects))
+}
+
+async fn discover_project_tasks(state: &AppState, project_path: Option<String>) -> AppResult<(String, Vec<TaskDefinition>)> {
+    let (root, sub_projects) = project_directories(state, project_path).await?;
+    
+    // Cargo metadata and several manifests are read per directory, so keep it off the async runtime
+    let tasks = tokio::task::spawn_blocking({
+        let root = root.clone();
+        move || tasks::discover_tasks(Path::new(&root), &sub_projects)
+    })
+    .await
+    .map_err(|e| AppError::internal("TASK_DISCOVERY_FAILED".to_string(), format!("Task discovery did not complete: {}", e)))?;
+    Ok((root, tasks))
+}
+
+async fn load_dependency_graphs(state: &AppState, project_path: Option<String>) -> AppResult<Vec<DependencyGraph>> {
+    let (root, sub_projects) = project_directories(state, project_path).await?;
+    
+    // Lockfiles of large projects run to megabytes, so parse them off the async runtime
+    let graphs = tokio::task::spawn_blocking({
+        let root = root.clone();
+        move || lockfile::load_graphs(Path::new(&root), &sub_projects)
+    })
+    .await
+    .map_err(|e| AppError::internal("LOCKFILE_PARSE_FAILED".to_string(), format!("Lockfile parsing did not complete: {}", e)))?;
+    
+    if graphs.is_empty() {
+        return Err(AppError::project_with_path(
+            "NO_LOCKFILE".to_string(),
+            "No supported lockfile found".to_string(),
+            root,
+        ));
+    }
+    Ok(graphs)
+}
+
+/// Sub-projects of a directory, nested by location, with their workspace declarations
+#[tauri::command]
+pub async fn get_project_tree(
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<SubProject>, String> {
+    let result: AppResult<SubProject> = async {
+        let root = state.resolve_project_root(project_path).await?;
+        match state.get_current_project().await.and_then(|project| project.project_tree) {
+            Some(tree) if tree.root_path == root => Ok(tree),
+            _ => Ok(detect_project_tree(Path::new(&root)).await),
+        }
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Record the focused file so project-scoped features use its sub-project
+#[tauri::command]
+pub async fn set_active_file(
+    path: String,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<Option<SubProject>>, String> {
+    let result: AppResult<Option<SubProject>> = async {
+        let mut project = state.get_current_project().await
+            .ok_or_else(|| AppError::project("NO_PROJECT", "No project is open"))?;
+        let file_path = Path::new(&project.root_path).join(&path).to_string_lossy().to_string();
+        let sub_project = project.set_active_file(&file_path).cloned();
+        state.set_current_project(project).await?;
+        Ok(sub_project)
+    }.await;
+    
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Detect type, framework, branch, dependencies and key files for a project directory
+async fn build_project_context(path: &str) -> ProjectContext {
+    let project_path = Path::new(path);
+    
     // Analyze project structure
     let project_type = detect_project_type(project_path).await;
     let framework = detect_framework(project_path).await;
     let git_branch = detect_git_branch(project_path).await;
     let dependencies = detect_dependencies(project_path, &project_type).await;
+    let project_tree = detect_project_tree(project_path).await;
     
     // Read some initial files for context using shared utility
     let open_files = read_initial_project_files(project_path, &project_type).await;
     
     // Create project context
     let mut project_context = ProjectContext::new(
-        path.clone(),
+        path,
         project_type.to_string(),
     );
     
     project_context = project_context
         .with_files(open_files)
-        .with_dependencies(dependencies);
+        .with_dependencies(dependencies)
+        .with_project_tree(project_tree);
     
     if let Some(branch) = git_branch {
         project_context = project_context.with_git_branch(branch);
@@ -45,17 +617,10 @@ pub async fn open_project(path: String, state: State<'_, AppState>) -> std::resu
         project_context = project_context.with_framework(fw.to_string());
     }
     
-    // Update application state
-    if let Err(e) = state.set_current_project(project_context.clone()).await {
-        tracing::error!("Failed to update application state: {:?}", e);
-        return Ok(TauriResult::error("Failed to update application state".to_string()));
-    }
-    
-    tracing::info!("Successfully opened project: {} (type: {})", path, project_type.to_string());
-    Ok(TauriResult::success(project_context))
+    project_context
 }
 
-async fn detect_project_type(project_path: &Path) -> ProjectType {
+pub(crate) async fn detect_project_type(project_path: &Path) -> ProjectType {
     // Check for common project files
     let indicators = [
         ("Cargo.toml", ProjectType::Rust),
@@ -106,7 +671,7 @@ async fn detect_project_type(project_path: &Path) -> ProjectType {
     ProjectType::Unknown
 }
 
-async fn detect_framework(project_path: &Path) -> Option<Framework> {
+pub(crate) async fn detect_framework(project_path: &Path) -> Option<Framework> {
     // Check package.json for JS/TS frameworks
     let package_json_path = project_path.join("package.json");
     if package_json_path.exists() {
@@ -189,69 +754,6 @@ async fn detect_git_branch(project_path: &Path) -> Option<String> {
     None
 }
 
-async fn detect_dependencies(project_path: &Path, project_type: &ProjectType) -> Vec<String> {
-    let mut dependencies = Vec::new();
-    
-    match project_type {
-        ProjectType::Rust => {
-            let cargo_path = project_path.join("Cargo.toml");
-            if let Ok(content) = tokio::fs::read_to_string(&cargo_path).await {
-                // Parse basic TOML structure for dependencies
-                let lines: Vec<&str> = content.lines().collect();
-                let mut in_dependencies = false;
-                
-                for line in lines {
-                    let line = line.trim();
-                    if line == "[dependencies]" {
-                        in_dependencies = true;
-                        continue;
-                    }
-                    if line.starts_with('[') && line != "[dependencies]" {
-                        in_dependencies = false;
-                        continue;
-                    }
-                    if in_dependencies && line.contains('=') {
-                        if let Some(dep_name) = line.split('=').next() {
-                            dependencies.push(dep_name.trim().trim_matches('"').to_string());
-                        }
-                    }
-                }
-            }
-        }
-        ProjectType::JavaScript | ProjectType::TypeScript => {
-            let package_json_path = project_path.join("package.json");
-            if let Ok(content) = tokio::fs::read_to_string(&package_json_path).await {
-                if let Ok(package_json) = serde_json::from_str::<serde_json::Value>(&content) {
-                    if let Some(deps) = package_json.get("dependencies").and_then(|d| d.as_object()) {
-                        dependencies.extend(deps.keys().cloned());
-                    }
-                    if let Some(dev_deps) = package_json.get("devDependencies").and_then(|d| d.as_object()) {
-                        dependencies.extend(dev_deps.keys().cloned());
-                    }
-                }
-            }
-        }
-        ProjectType::Python => {
-            let requirements_path = project_path.join("requirements.txt");
-            if let Ok(content) = tokio::fs::read_to_string(&requirements_path).await {
-                for line in content.lines() {
-                    let line = line.trim();
-                    if !line.is_empty() && !line.starts_with('#') {
-                        if let Some(dep_name) = line.split('=').next().or_else(|| line.split('>').next()) {
-                            dependencies.push(dep_name.trim().to_string());
-                        }
-                    }
-                }
-            }
-        }
-        _ => {
-            // For other project types, we could add more detection logic
-        }
-    }
-    
-    dependencies
-}
-
 async fn read_initial_project_files(project_path: &Path, project_type: &ProjectType) -> Vec<FileInfo> {
     let mut files = Vec::new();
     
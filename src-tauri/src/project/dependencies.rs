@@ -0,0 +1,447 @@
+// Syntari AI IDE - Dependency Extraction
+// Reads declared dependencies from each ecosystem's manifests into structured records
+
+use std::collections::HashMap;
+use std::path::Path;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::project::cargo;
+use crate::project::types::{Dependency, DependencyScope, ProjectType};
+
+lazy_static! {
+    static ref POM_PROPERTY_PATTERN: Regex = Regex::new(r"<([A-Za-z0-9_.\-]+)>\s*([^<]*?)\s*</([A-Za-z0-9_.\-]+)>").unwrap();
+    static ref GRADLE_DEPENDENCY_PATTERN: Regex = Regex::new(
+        r#"(?m)^\s*([A-Za-z]+)\s*\(?\s*(?:platform\s*\(\s*)?["']([^"':]+):([^"':]+)(?::([^"':@]+))?[^"']*["']"#,
+    )
+    .unwrap();
+    static ref GEM_PATTERN: Regex = Regex::new(r#"^gem\s*\(?\s*["']([^"']+)["']((?:\s*,\s*["'][^"']*["'])*)(.*)$"#).unwrap();
+    static ref QUOTED_PATTERN: Regex = Regex::new(r#"["']([^"']*)["']"#).unwrap();
+}
+
+/// Dependencies declared by the manifests of a project directory
+pub async fn detect_dependencies(project_path: &Path, project_type: &ProjectType) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+
+    match project_type {
+        ProjectType::Rust if project_path.join(cargo::MANIFEST_FILE).exists() => {
+            match cargo::load_workspace(project_path) {
+                Ok(workspace) => dependencies.extend(workspace.dependencies()),
+                Err(e) => e.log_warn(),
+            }
+        }
+        ProjectType::JavaScript | ProjectType::TypeScript => {
+            if let Some(content) = read(project_path, "package.json").await {
+                dependencies.extend(parse_package_json(&content));
+            }
+        }
+        ProjectType::Python => {
+            if let Some(content) = read(project_path, "pyproject.toml").await {
+                dependencies.extend(parse_pyproject(&content));
+            }
+            if let Some(content) = read(project_path, "Pipfile").await {
+                dependencies.extend(parse_pipfile(&content));
+            }
+            for (file, scope) in [("requirements.txt", DependencyScope::Runtime), ("requirements-dev.txt", DependencyScope::Dev)] {
+                if let Some(content) = read(project_path, file).await {
+                    dependencies.extend(parse_requirements(&content, file, scope));
+                }
+            }
+        }
+        ProjectType::Go => {
+            if let Some(content) = read(project_path, "go.mod").await {
+                dependencies.extend(parse_go_mod(&content));
+            }
+        }
+        ProjectType::Java => {
+            if let Some(content) = read(project_path, "pom.xml").await {
+                dependencies.extend(parse_pom(&content));
+            }
+            for file in ["build.gradle", "build.gradle.kts"] {
+                if let Some(content) = read(project_path, file).await {
+                    dependencies.extend(parse_gradle(&content, file));
+                }
+            }
+        }
+        ProjectType::PHP => {
+            if let Some(content) = read(project_path, "composer.json").await {
+                dependencies.extend(parse_composer(&content));
+            }
+        }
+        ProjectType::Ruby => {
+            if let Some(content) = read(project_path, "Gemfile").await {
+                dependencies.extend(parse_gemfile(&content));
+            }
+        }
+        _ => {
+            // For other project types, we could add more detection logic
+        }
+    }
+
+    // The same package may be listed by several manifests; keep the first declaration per scope
+    let mut seen = std::collections::HashSet::new();
+    dependencies.retain(|dep| seen.insert((dep.name.clone(), dep.scope)));
+    dependencies
+}
+
+async fn read(project_path: &Path, file: &str) -> Option<String> {
+    tokio::fs::read_to_string(project_path.join(file)).await.ok()
+}
+
+// ================================
+// JAVASCRIPT
+// ================================
+
+pub(crate) fn parse_package_json(content: &str) -> Vec<Dependency> {
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let sections = [
+        ("dependencies", DependencyScope::Runtime),
+        ("optionalDependencies", DependencyScope::Runtime),
+        ("peerDependencies", DependencyScope::Runtime),
+        ("devDependencies", DependencyScope::Dev),
+    ];
+    json_sections(&package, &sections, "package.json", |_| true)
+}
+
+/// Name-to-requirement objects of a JSON manifest
+fn json_sections(
+    manifest: &serde_json::Value,
+    sections: &[(&str, DependencyScope)],
+    file: &str,
+    include: impl Fn(&str) -> bool,
+) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+    for (section, scope) in sections {
+        if let Some(entries) = manifest.get(section).and_then(|d| d.as_object()) {
+            for (name, version) in entries {
+                if include(name) {
+                    dependencies.push(Dependency::new(name, version.as_str().map(str::to_string), *scope, file));
+                }
+            }
+        }
+    }
+    dependencies
+}
+
+// ================================
+// PYTHON
+// ================================
+
+/// Split a PEP 508 requirement such as `requests[socks]>=2.31; python_version > "3.8"` into name and specifier
+fn parse_pep508(requirement: &str) -> Option<(String, Option<String>)> {
+    let requirement = requirement.split(';').next()?.trim();
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(requirement.len());
+    let name = &requirement[..end];
+    if name.is_empty() {
+        return None;
+    }
+    let mut rest = requirement[end..].trim();
+    if rest.starts_with('[') {
+        rest = rest.find(']').map(|i| rest[i + 1..].trim()).unwrap_or("");
+    }
+    // Direct references (`name @ url`) carry no version requirement
+    let specifier = rest.trim_matches(|c| c == '(' || c == ')').trim();
+    let version_req = (!specifier.is_empty() && !specifier.starts_with('@')).then(|| specifier.to_string());
+    Some((name.to_string(), version_req))
+}
+
+fn parse_requirements(content: &str, file: &str, scope: DependencyScope) -> Vec<Dependency> {
+    content
+        .lines()
+        .map(|line| line.split(" #").next().unwrap_or("").trim())
+        // Options such as `-r other.txt` or `--index-url` are not packages
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('-'))
+        .filter_map(parse_pep508)
+        .map(|(name, version_req)| Dependency::new(name, version_req, scope, file))
+        .collect()
+}
+
+pub(crate) fn parse_pyproject(content: &str) -> Vec<Dependency> {
+    const FILE: &str = "pyproject.toml";
+    let Ok(manifest) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let mut dependencies = Vec::new();
+
+    // PEP 621 metadata, PEP 735 dependency groups and the build backend
+    let project = manifest.get("project");
+    push_pep508(project.and_then(|p| p.get("dependencies")), DependencyScope::Runtime, FILE, &mut dependencies);
+    if let Some(extras) = project.and_then(|p| p.get("optional-dependencies")).and_then(|o| o.as_table()) {
+        for items in extras.values() {
+            push_pep508(Some(items), DependencyScope::Runtime, FILE, &mut dependencies);
+        }
+    }
+    if let Some(groups) = manifest.get("dependency-groups").and_then(|g| g.as_table()) {
+        for items in groups.values() {
+            push_pep508(Some(items), DependencyScope::Dev, FILE, &mut dependencies);
+        }
+    }
+    push_pep508(manifest.get("build-system").and_then(|b| b.get("requires")), DependencyScope::Build, FILE, &mut dependencies);
+
+    // Poetry
+    let poetry = manifest.get("tool").and_then(|t| t.get("poetry"));
+    if let Some(table) = poetry.and_then(|p| p.get("dependencies")).and_then(|d| d.as_table()) {
+        dependencies.extend(toml_table(table, DependencyScope::Runtime, FILE));
+    }
+    if let Some(table) = poetry.and_then(|p| p.get("dev-dependencies")).and_then(|d| d.as_table()) {
+        dependencies.extend(toml_table(table, DependencyScope::Dev, FILE));
+    }
+    if let Some(groups) = poetry.and_then(|p| p.get("group")).and_then(|g| g.as_table()) {
+        for group in groups.values() {
+            if let Some(table) = group.get("dependencies").and_then(|d| d.as_table()) {
+                dependencies.extend(toml_table(table, DependencyScope::Dev, FILE));
+            }
+        }
+    }
+    dependencies
+}
+
+fn push_pep508(items: Option<&toml::Value>, scope: DependencyScope, file: &str, dependencies: &mut Vec<Dependency>) {
+    for item in items.and_then(|i| i.as_array()).into_iter().flatten() {
+        if let Some((name, version_req)) = item.as_str().and_then(parse_pep508) {
+            dependencies.push(Dependency::new(name, version_req, scope, file));
+        }
+    }
+}
+
+fn parse_pipfile(content: &str) -> Vec<Dependency> {
+    let Ok(manifest) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let mut dependencies = Vec::new();
+    for (section, scope) in [("packages", DependencyScope::Runtime), ("dev-packages", DependencyScope::Dev)] {
+        if let Some(table) = manifest.get(section).and_then(|s| s.as_table()) {
+            dependencies.extend(toml_table(table, scope, "Pipfile"));
+        }
+    }
+    dependencies
+}
+
+/// `name = "req"` or `name = { version = "req", ... }` entries; the interpreter pin is skipped
+fn toml_table(table: &toml::Table, scope: DependencyScope, file: &str) -> Vec<Dependency> {
+    table
+        .iter()
+        .filter(|(name, _)| !name.eq_ignore_ascii_case("python"))
+        .map(|(name, value)| {
+            let version_req = match value {
+                toml::Value::String(version) => Some(version.clone()),
+                toml::Value::Table(spec) => spec.get("version").and_then(|v| v.as_str()).map(str::to_string),
+                _ => None,
+            };
+            Dependency::new(name, version_req, scope, file)
+        })
+        .collect()
+}
+
+// ================================
+// GO
+// ================================
+
+pub(crate) fn parse_go_mod(content: &str) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+    let mut in_require = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        let entry = if in_require {
+            if line == ")" {
+                in_require = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_require = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+
+        let mut parts = entry.split_whitespace();
+        if let Some(module) = parts.next() {
+            dependencies.push(Dependency::new(module, parts.next().map(str::to_string), DependencyScope::Runtime, "go.mod"));
+        }
+    }
+    dependencies
+}
+
+// ================================
+// JAVA
+// ================================
+
+/// Best-effort Maven parsing; `${property}` versions are resolved from `<properties>`
+fn parse_pom(content: &str) -> Vec<Dependency> {
+    const FILE: &str = "pom.xml";
+    let properties = pom_properties(content);
+    let resolve = |value: Option<String>| {
+        value.map(|v| match v.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+            Some(key) => properties.get(key).cloned().unwrap_or(v),
+            None => v,
+        })
+    };
+
+    // Managed versions are constraints for other modules, not dependencies of this one
+    let content = strip_elements(content, "dependencyManagement");
+    let content = strip_elements(&content, "pluginManagement");
+    let mut dependencies = Vec::new();
+
+    let plugins = elements(&content, "plugins").join("\n");
+    for plugin in elements(&plugins, "plugin") {
+        let plugin = strip_elements(&plugin, "dependencies");
+        if let Some(artifact) = tag_text(&plugin, "artifactId") {
+            let name = match tag_text(&plugin, "groupId") {
+                Some(group) => format!("{}:{}", group, artifact),
+                None => format!("org.apache.maven.plugins:{}", artifact),
+            };
+            dependencies.push(Dependency::new(name, resolve(tag_text(&plugin, "version")), DependencyScope::Build, FILE));
+        }
+    }
+
+    let content = strip_elements(&content, "plugins");
+    for dependency in elements(&content, "dependency") {
+        let (Some(group), Some(artifact)) = (tag_text(&dependency, "groupId"), tag_text(&dependency, "artifactId")) else {
+            continue;
+        };
+        let scope = match tag_text(&dependency, "scope").as_deref() {
+            Some("test") => DependencyScope::Dev,
+            Some("provided") | Some("system") => DependencyScope::Build,
+            _ => DependencyScope::Runtime,
+        };
+        dependencies.push(Dependency::new(format!("{}:{}", group, artifact), resolve(tag_text(&dependency, "version")), scope, FILE));
+    }
+    dependencies
+}
+
+fn pom_properties(content: &str) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    for block in elements(content, "properties") {
+        for captures in POM_PROPERTY_PATTERN.captures_iter(&block) {
+            if captures[1] == captures[3] {
+                properties.insert(captures[1].to_string(), captures[2].to_string());
+            }
+        }
+    }
+    if let Some(version) = tag_text(&strip_elements(&strip_elements(content, "parent"), "dependencies"), "version") {
+        properties.entry("project.version".to_string()).or_insert(version);
+    }
+    properties
+}
+
+/// Inner text of every `<tag>...</tag>`, outermost first
+pub(crate) fn elements(content: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut found = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(&open) {
+        let inner = &rest[start + open.len()..];
+        let Some(end) = inner.find(&close) else {
+            break;
+        };
+        found.push(inner[..end].to_string());
+        rest = &inner[end + close.len()..];
+    }
+    found
+}
+
+fn strip_elements(content: &str, tag: &str) -> String {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut stripped = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find(&open) {
+        stripped.push_str(&rest[..start]);
+        match rest[start..].find(&close) {
+            Some(end) => rest = &rest[start + end + close.len()..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Text of the first `<tag>` directly in this fragment
+pub(crate) fn tag_text(content: &str, tag: &str) -> Option<String> {
+    elements(content, tag).into_iter().next().map(|text| text.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+/// Best-effort Gradle parsing of `configuration "group:artifact:version"` declarations in Groovy or Kotlin DSL
+fn parse_gradle(content: &str, file: &str) -> Vec<Dependency> {
+    GRADLE_DEPENDENCY_PATTERN
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let configuration = &captures[1];
+            let scope = match configuration {
+                "implementation" | "api" | "compile" | "runtimeOnly" | "runtime" => DependencyScope::Runtime,
+                "compileOnly" | "annotationProcessor" | "kapt" | "ksp" | "classpath" => DependencyScope::Build,
+                c if c.starts_with("test") || c.starts_with("androidTest") => DependencyScope::Dev,
+                c if c.ends_with("Implementation") || c.ends_with("Api") => DependencyScope::Runtime,
+                _ => return None,
+            };
+            let name = format!("{}:{}", &captures[2], &captures[3]);
+            Some(Dependency::new(name, captures.get(4).map(|v| v.as_str().to_string()), scope, file))
+        })
+        .collect()
+}
+
+// ================================
+// PHP AND RUBY
+// ================================
+
+fn parse_composer(content: &str) -> Vec<Dependency> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    // The PHP version and extensions are platform requirements, not packages
+    json_sections(
+        &manifest,
+        &[("require", DependencyScope::Runtime), ("require-dev", DependencyScope::Dev)],
+        "composer.json",
+        |name| name != "php" && !name.starts_with("ext-") && !name.starts_with("lib-"),
+    )
+}
+
+fn parse_gemfile(content: &str) -> Vec<Dependency> {
+    let dev_groups = |groups: &str| groups.contains("development") || groups.contains("test");
+
+    let mut dependencies = Vec::new();
+    // Scope of each open `group ... do` block; other blocks push None so `end` pops correctly
+    let mut blocks: Vec<Option<DependencyScope>> = Vec::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if let Some(groups) = line.strip_prefix("group ") {
+            let scope = if dev_groups(groups) { DependencyScope::Dev } else { DependencyScope::Runtime };
+            blocks.push(Some(scope));
+            continue;
+        }
+        if line.ends_with(" do") || line.contains(" do |") {
+            blocks.push(None);
+            continue;
+        }
+        if line == "end" {
+            blocks.pop();
+            continue;
+        }
+        let Some(captures) = GEM_PATTERN.captures(line) else {
+            continue;
+        };
+        let requirements: Vec<String> = QUOTED_PATTERN.captures_iter(&captures[2]).map(|c| c[1].to_string()).collect();
+        let options = &captures[3];
+        let scope = if options.contains("group") && dev_groups(options) {
+            DependencyScope::Dev
+        } else {
+            blocks.iter().rev().find_map(|scope| *scope).unwrap_or(DependencyScope::Runtime)
+        };
+        let version_req = (!requirements.is_empty()).then(|| requirements.join(", "));
+        dependencies.push(Dependency::new(&captures[1], version_req, scope, "Gemfile"));
+    }
+    dependencies
+}
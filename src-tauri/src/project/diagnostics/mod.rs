@@ -0,0 +1,402 @@
+// Syntari AI IDE - Project Diagnostics
+// Runs cargo, tsc, eslint and ruff, keeps their latest diagnostics per project and reruns them on save
+
+pub mod types;
+pub mod parsers;
+
+pub use types::{Diagnostic, DiagnosticProvider, DiagnosticSeverity, DiagnosticTool, DiagnosticsSnapshot, DiagnosticsUpdate};
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+use crate::core::{AppError, AppResult, current_timestamp};
+use crate::project::cargo;
+use types::DiagnosticRunInfo;
+
+/// Emitted with a `DiagnosticsUpdate` after every provider run
+pub const DIAGNOSTICS_EVENT: &str = "diagnostics-updated";
+/// Saves closer together than this share one run
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(800);
+/// A cold `cargo check` of a large workspace takes minutes; anything beyond this is stuck
+const RUN_TIMEOUT: Duration = Duration::from_secs(600);
+/// Build output and dependency directories never affect diagnostics
+const IGNORED_DIRS: &[&str] = &["target", "node_modules", ".git", ".venv", "venv", "__pycache__", "dist"];
+
+const ESLINT_CONFIGS: &[&str] = &[
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "eslint.config.cjs",
+    "eslint.config.ts",
+    ".eslintrc",
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.json",
+    ".eslintrc.yml",
+    ".eslintrc.yaml",
+];
+
+// ================================
+// PROVIDER DETECTION
+// ================================
+
+/// Tools configured in the project root and each sub-project; JavaScript tools must be installed locally
+pub fn detect_providers(project_root: &Path, sub_projects: &[String]) -> Vec<DiagnosticProvider> {
+    let mut directories = vec![project_root.to_path_buf()];
+    for dir in sub_projects {
+        let dir = Path::new(dir);
+        if !directories.iter().any(|d| d == dir) {
+            directories.push(dir.to_path_buf());
+        }
+    }
+
+    let mut providers = Vec::new();
+    let mut cargo_roots = HashSet::new();
+    for dir in &directories {
+        if dir.join(cargo::MANIFEST_FILE).is_file() {
+            // Members of a workspace are checked together from its root
+            match cargo::load_workspace(dir) {
+                Ok(workspace) if cargo_roots.insert(workspace.root.clone()) => providers.push(provider(
+                    DiagnosticTool::Cargo,
+                    Path::new(&workspace.root),
+                    "cargo".to_string(),
+                    &["check", "--workspace", "--all-targets", "--message-format=json"],
+                )),
+                Ok(_) => {}
+                Err(e) => e.log_warn(),
+            }
+        }
+        if dir.join("tsconfig.json").is_file() {
+            if let Some(tsc) = node_bin(dir, project_root, "tsc") {
+                providers.push(provider(DiagnosticTool::Tsc, dir, tsc, &["-p", "tsconfig.json", "--noEmit", "--pretty", "false"]));
+            }
+        }
+        if has_eslint_config(dir) {
+            if let Some(eslint) = node_bin(dir, project_root, "eslint") {
+                providers.push(provider(DiagnosticTool::Eslint, dir, eslint, &["-f", "json", "."]));
+            }
+        }
+        if has_ruff_config(dir) {
+            // Ruff from the project's virtualenv when there is one, otherwise from PATH
+            let ruff = venv_bin(dir, project_root, "ruff").unwrap_or_else(|| "ruff".to_string());
+            providers.push(provider(DiagnosticTool::Ruff, dir, ruff, &["check", "--output-format=json", "."]));
+        }
+    }
+    providers
+}
+
+fn provider(tool: DiagnosticTool, cwd: &Path, program: String, args: &[&str]) -> DiagnosticProvider {
+    DiagnosticProvider {
+        tool,
+        cwd: cwd.to_string_lossy().to_string(),
+        program,
+        args: args.iter().map(|a| a.to_string()).collect(),
+    }
+}
+
+/// `node_modules/.bin/<name>` in `dir` or a parent up to the project root, as hoisting package managers leave it
+pub(crate) fn node_bin(dir: &Path, project_root: &Path, name: &str) -> Option<String> {
+    let file = if cfg!(windows) { format!("{}.cmd", name) } else { name.to_string() };
+    dir.ancestors()
+        .take_while(|d| d.starts_with(project_root))
+        .map(|d| d.join("node_modules").join(".bin").join(&file))
+        .find(|bin| bin.is_file())
+        .map(|bin| bin.to_string_lossy().to_string())
+}
+
+fn has_eslint_config(dir: &Path) -> bool {
+    ESLINT_CONFIGS.iter().any(|name| dir.join(name).is_file())
+        || std::fs::read_to_string(dir.join("package.json"))
+            .ok()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+            .is_some_and(|manifest| manifest.get("eslintConfig").is_some())
+}
+
+fn has_ruff_config(dir: &Path) -> bool {
+    dir.join("ruff.toml").is_file()
+        || dir.join(".ruff.toml").is_file()
+        || std::fs::read_to_string(dir.join("pyproject.toml"))
+            .ok()
+            .and_then(|text| text.parse::<toml::Table>().ok())
+            .is_some_and(|manifest| manifest.get("tool").and_then(|t| t.get("ruff")).is_some())
+}
+
+/// `<name>` from the project's virtualenv, in `dir` or a parent up to the project root
+pub(crate) fn venv_bin(dir: &Path, project_root: &Path, name: &str) -> Option<String> {
+    let relative: PathBuf = if cfg!(windows) {
+        [".venv", "Scripts", &format!("{}.exe", name)].iter().collect()
+    } else {
+        [".venv", "bin", name].iter().collect()
+    };
+    dir.ancestors()
+        .take_while(|d| d.starts_with(project_root))
+        .map(|d| d.join(&relative))
+        .find(|bin| bin.is_file())
+        .map(|bin| bin.to_string_lossy().to_string())
+}
+
+/// Tools whose results a change to `path` can affect
+fn tools_for(path: &Path) -> Vec<DiagnosticTool> {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+    DiagnosticTool::ALL
+        .into_iter()
+        .filter(|tool| tool.watched_extensions().contains(&extension.as_str()))
+        .filter(|tool| match (tool, extension.as_str()) {
+            (DiagnosticTool::Cargo, "toml") => name == cargo::MANIFEST_FILE,
+            (DiagnosticTool::Ruff, "toml") => name == "pyproject.toml" || name.ends_with("ruff.toml"),
+            (DiagnosticTool::Tsc, "json") => name.starts_with("tsconfig") || name == "package.json",
+            _ => true,
+        })
+        .collect()
+}
+
+// ================================
+// MANAGER
+// ================================
+
+#[derive(Default)]
+struct ProjectDiagnostics {
+    /// Latest results per provider key
+    results: HashMap<String, Vec<Diagnostic>>,
+    runs: HashMap<String, DiagnosticRunInfo>,
+}
+
+/// Saves waiting out the debounce for one project
+#[derive(Default)]
+struct PendingRun {
+    generation: u64,
+    tools: BTreeSet<DiagnosticTool>,
+}
+
+/// Registered as Tauri state
+#[derive(Default)]
+pub struct DiagnosticsManager {
+    projects: Mutex<HashMap<String, ProjectDiagnostics>>,
+    /// Projects rerun on save, with the sub-project directories to check
+    on_save: Mutex<HashMap<String, Vec<String>>>,
+    pending: Mutex<HashMap<String, PendingRun>>,
+    /// Held for the duration of a run so runs of one project never overlap
+    locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl DiagnosticsManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_on_save(&self, project_root: &str, sub_projects: Vec<String>, enabled: bool) {
+        if let Ok(mut on_save) = self.on_save.lock() {
+            if enabled {
+                on_save.insert(project_root.to_string(), sub_projects);
+            } else {
+                on_save.remove(project_root);
+            }
+        }
+    }
+
+    /// Run the providers of `tools` (all when `None`) one after another, emitting an update after each
+    pub async fn run(
+        &self,
+        app_handle: &tauri::AppHandle,
+        project_root: &str,
+        sub_projects: Vec<String>,
+        tools: Option<&[DiagnosticTool]>,
+    ) -> AppResult<DiagnosticsSnapshot> {
+        let providers = tokio::task::spawn_blocking({
+            let root = project_root.to_string();
+            move || detect_providers(Path::new(&root), &sub_projects)
+        })
+        .await
+        .map_err(|e| AppError::internal("DIAGNOSTICS_FAILED".to_string(), format!("Tool detection did not complete: {}", e)))?;
+        let providers: Vec<DiagnosticProvider> =
+            providers.into_iter().filter(|p| tools.is_none_or(|tools| tools.contains(&p.tool))).collect();
+        if providers.is_empty() && tools.is_none() {
+            return Err(AppError::project_with_path(
+                "NO_DIAGNOSTIC_TOOLS".to_string(),
+                "No cargo, tsc, eslint or ruff configuration found".to_string(),
+                project_root.to_string(),
+            ));
+        }
+
+        let lock = self
+            .locks
+            .lock()
+            .map_err(|_| AppError::internal("DIAGNOSTICS_FAILED".to_string(), "Diagnostics state is unavailable".to_string()))?
+            .entry(project_root.to_string())
+            .or_default()
+            .clone();
+        let _guard = lock.lock().await;
+
+        for provider in providers {
+            tracing::info!("🔎 Running {:?} diagnostics in {}", provider.tool, provider.cwd);
+            let (run, diagnostics) = run_provider(provider).await;
+            let update = self.apply(project_root, run, diagnostics);
+            if let Err(e) = app_handle.emit(DIAGNOSTICS_EVENT, &update) {
+                tracing::warn!("Failed to emit diagnostics update: {}", e);
+            }
+        }
+        Ok(self.snapshot(project_root, None))
+    }
+
+    /// Replace a provider's results and report what changed; a failed run keeps the previous results
+    fn apply(&self, project_root: &str, run: DiagnosticRunInfo, diagnostics: Option<Vec<Diagnostic>>) -> DiagnosticsUpdate {
+        let key = run.provider.key();
+        let (added, removed) = match self.projects.lock() {
+            Ok(mut projects) => {
+                let project = projects.entry(project_root.to_string()).or_default();
+                project.runs.insert(key.clone(), run.clone());
+                match diagnostics {
+                    Some(diagnostics) => {
+                        let previous = project.results.insert(key, diagnostics.clone()).unwrap_or_default();
+                        let previous_ids: HashSet<&str> = previous.iter().map(|d| d.id.as_str()).collect();
+                        let current_ids: HashSet<&str> = diagnostics.iter().map(|d| d.id.as_str()).collect();
+                        let added = diagnostics.iter().filter(|d| !previous_ids.contains(d.id.as_str())).cloned().collect();
+                        let removed = previous.iter().filter(|d| !current_ids.contains(d.id.as_str())).map(|d| d.id.clone()).collect();
+                        (added, removed)
+                    }
+                    None => (Vec::new(), Vec::new()),
+                }
+            }
+            Err(_) => (Vec::new(), Vec::new()),
+        };
+        let snapshot = self.snapshot(project_root, None);
+        DiagnosticsUpdate {
+            project_path: project_root.to_string(),
+            run,
+            added,
+            removed,
+            error_count: snapshot.error_count,
+            warning_count: snapshot.warning_count,
+        }
+    }
+
+    /// Current diagnostics of a project, optionally of one file only
+    pub fn snapshot(&self, project_root: &str, file: Option<&str>) -> DiagnosticsSnapshot {
+        let (diagnostics, mut runs) = match self.projects.lock() {
+            Ok(projects) => match projects.get(project_root) {
+                Some(project) => (
+                    project.results.values().flatten().cloned().collect::<Vec<_>>(),
+                    project.runs.values().cloned().collect::<Vec<_>>(),
+                ),
+                None => (Vec::new(), Vec::new()),
+            },
+            Err(_) => (Vec::new(), Vec::new()),
+        };
+        let mut diagnostics = parsers::dedupe(diagnostics);
+        if let Some(file) = file {
+            let file = Path::new(file);
+            diagnostics.retain(|d| Path::new(&d.file) == file);
+        }
+        diagnostics.sort_by(|a, b| {
+            (&a.file, a.range.start_line, a.range.start_column, a.severity).cmp(&(&b.file, b.range.start_line, b.range.start_column, b.severity))
+        });
+        runs.sort_by_key(|run| run.provider.key());
+
+        DiagnosticsSnapshot {
+            project_path: project_root.to_string(),
+            error_count: diagnostics.iter().filter(|d| d.severity == DiagnosticSeverity::Error).count(),
+            warning_count: diagnostics.iter().filter(|d| d.severity == DiagnosticSeverity::Warning).count(),
+            diagnostics,
+            runs,
+        }
+    }
+
+    /// Called by the file watcher; schedules a debounced rerun of the affected tools
+    pub fn file_changed(&self, app_handle: &tauri::AppHandle, path: &Path) {
+        if path.components().any(|c| IGNORED_DIRS.contains(&c.as_os_str().to_string_lossy().as_ref())) {
+            return;
+        }
+        let tools = tools_for(path);
+        if tools.is_empty() {
+            return;
+        }
+        // The innermost watched project owns the file
+        let Some((project_root, sub_projects)) = self.on_save.lock().ok().and_then(|on_save| {
+            on_save
+                .iter()
+                .filter(|(root, _)| path.starts_with(root))
+                .max_by_key(|(root, _)| root.len())
+                .map(|(root, sub_projects)| (root.clone(), sub_projects.clone()))
+        }) else {
+            return;
+        };
+
+        let generation = match self.pending.lock() {
+            Ok(mut pending) => {
+                let entry = pending.entry(project_root.clone()).or_default();
+                entry.generation += 1;
+                entry.tools.extend(tools);
+                entry.generation
+            }
+            Err(_) => return,
+        };
+
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SAVE_DEBOUNCE).await;
+            let manager = app_handle.state::<DiagnosticsManager>();
+            // A later save restarted the wait and will run instead
+            let tools: Vec<DiagnosticTool> = match manager.pending.lock() {
+                Ok(mut pending) => match pending.get(&project_root) {
+                    Some(entry) if entry.generation == generation => {
+                        pending.remove(&project_root).map(|entry| entry.tools.into_iter().collect()).unwrap_or_default()
+                    }
+                    _ => return,
+                },
+                Err(_) => return,
+            };
+            if let Err(e) = manager.run(&app_handle, &project_root, sub_projects, Some(&tools)).await {
+                e.log_warn();
+            }
+        });
+    }
+}
+
+/// Run one tool to completion; `None` diagnostics mean the run failed and produced nothing usable
+async fn run_provider(provider: DiagnosticProvider) -> (DiagnosticRunInfo, Option<Vec<Diagnostic>>) {
+    let started_at = current_timestamp();
+    let started = Instant::now();
+    let mut command = tokio::process::Command::new(&provider.program);
+    command.args(&provider.args).current_dir(&provider.cwd).kill_on_drop(true);
+
+    let outcome = match tokio::time::timeout(RUN_TIMEOUT, command.output()).await {
+        Err(_) => Err(format!("{} did not finish within {} seconds", provider.program, RUN_TIMEOUT.as_secs())),
+        Ok(Err(e)) => Err(format!("Failed to run {}: {}", provider.program, e)),
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let cwd = Path::new(&provider.cwd);
+            let diagnostics = parsers::dedupe(match provider.tool {
+                DiagnosticTool::Cargo => parsers::parse_cargo(&stdout, cwd),
+                DiagnosticTool::Tsc => parsers::parse_tsc(&stdout, cwd),
+                DiagnosticTool::Eslint => parsers::parse_eslint(&stdout),
+                DiagnosticTool::Ruff => parsers::parse_ruff(&stdout, cwd),
+            });
+            // These tools exit non-zero when they report problems; a failure with nothing parsed is the tool itself failing
+            if !output.status.success() && diagnostics.is_empty() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let detail = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("no output");
+                Err(format!("{} exited with {}: {}", provider.program, output.status, detail.trim()))
+            } else {
+                Ok(diagnostics)
+            }
+        }
+    };
+
+    let (error, diagnostics) = match outcome {
+        Ok(diagnostics) => (None, Some(diagnostics)),
+        Err(error) => {
+            tracing::warn!("Diagnostics run failed: {}", error);
+            (Some(error), None)
+        }
+    };
+    let run = DiagnosticRunInfo {
+        provider,
+        started_at,
+        duration_ms: started.elapsed().as_millis() as u64,
+        diagnostic_count: diagnostics.as_ref().map(Vec::len).unwrap_or(0),
+        error,
+    };
+    (run, diagnostics)
+}
@@ -0,0 +1,339 @@
+// Syntari AI IDE - Diagnostics Parsers
+// Reads the machine-readable output of cargo, tsc, eslint and ruff into diagnostics
+
+use std::collections::HashSet;
+use std::path::Path;
+use regex::Regex;
+use serde_json::Value;
+use crate::core::content_hash;
+use crate::project::diagnostics::types::{
+    Diagnostic, DiagnosticRange, DiagnosticSeverity, DiagnosticTool, RelatedLocation, SuggestedFix, TextEdit,
+};
+
+fn diagnostic(tool: DiagnosticTool, file: String, range: DiagnosticRange, severity: DiagnosticSeverity, code: Option<String>, message: String) -> Diagnostic {
+    let identity = format!(
+        "{:?}|{}|{}:{}-{}:{}|{}|{}",
+        tool, file, range.start_line, range.start_column, range.end_line, range.end_column, code.as_deref().unwrap_or(""), message
+    );
+    Diagnostic {
+        id: content_hash(identity.as_bytes()),
+        tool,
+        file,
+        range,
+        severity,
+        code,
+        message,
+        related: Vec::new(),
+        fixes: Vec::new(),
+    }
+}
+
+fn absolute(cwd: &Path, file: &str) -> String {
+    cwd.join(file).to_string_lossy().to_string()
+}
+
+fn number(value: &Value, key: &str) -> Option<u32> {
+    value.get(key).and_then(|v| v.as_u64()).map(|n| n as u32)
+}
+
+// ================================
+// CARGO
+// ================================
+
+/// `cargo check --message-format=json`: one JSON object per line, diagnostics under `compiler-message`
+pub fn parse_cargo(output: &str, workspace_root: &Path) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for line in output.lines().filter(|l| l.starts_with('{')) {
+        let Ok(record) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if record.get("reason").and_then(|r| r.as_str()) != Some("compiler-message") {
+            continue;
+        }
+        let Some(message) = record.get("message") else {
+            continue;
+        };
+        if let Some(parsed) = cargo_diagnostic(message, workspace_root) {
+            diagnostics.push(parsed);
+        }
+    }
+    diagnostics
+}
+
+fn cargo_diagnostic(message: &Value, workspace_root: &Path) -> Option<Diagnostic> {
+    let severity = match message.get("level").and_then(|l| l.as_str())? {
+        level if level.starts_with("error") => DiagnosticSeverity::Error,
+        "warning" => DiagnosticSeverity::Warning,
+        "note" => DiagnosticSeverity::Info,
+        "help" => DiagnosticSeverity::Hint,
+        _ => return None,
+    };
+    let spans = message.get("spans").and_then(|s| s.as_array()).cloned().unwrap_or_default();
+    // Summaries such as "aborting due to 2 previous errors" have no location
+    let primary = spans.iter().find(|s| s.get("is_primary").and_then(|p| p.as_bool()) == Some(true))?;
+    let (file, range) = cargo_location(primary, workspace_root)?;
+
+    let mut text = message.get("message").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+    let children = message.get("children").and_then(|c| c.as_array()).cloned().unwrap_or_default();
+    // Notes and helps without a location read as part of the message
+    for child in &children {
+        let child_spans = child.get("spans").and_then(|s| s.as_array()).map(|s| s.len()).unwrap_or(0);
+        if child_spans == 0 {
+            let level = child.get("level").and_then(|l| l.as_str()).unwrap_or("note");
+            let child_message = child.get("message").and_then(|m| m.as_str()).unwrap_or_default();
+            text.push_str(&format!("\n{}: {}", level, child_message));
+        }
+    }
+    let code = message.get("code").and_then(|c| c.get("code")).and_then(|c| c.as_str()).map(str::to_string);
+    let mut parsed = diagnostic(DiagnosticTool::Cargo, file, range, severity, code, text);
+
+    for span in spans.iter().filter(|s| s.get("is_primary").and_then(|p| p.as_bool()) != Some(true)) {
+        if let (Some((file, range)), Some(label)) = (cargo_location(span, workspace_root), span.get("label").and_then(|l| l.as_str())) {
+            parsed.related.push(RelatedLocation { file, range, message: label.to_string() });
+        }
+    }
+    for child in &children {
+        let child_message = child.get("message").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+        let child_spans = child.get("spans").and_then(|s| s.as_array()).cloned().unwrap_or_default();
+        let mut edits = Vec::new();
+        let mut machine_applicable = !child_spans.is_empty();
+        for span in &child_spans {
+            let Some((file, range)) = cargo_location(span, workspace_root) else {
+                continue;
+            };
+            match span.get("suggested_replacement").and_then(|r| r.as_str()) {
+                Some(replacement) => {
+                    machine_applicable &= span.get("suggestion_applicability").and_then(|a| a.as_str()) == Some("MachineApplicable");
+                    edits.push(TextEdit { file, range, replacement: replacement.to_string() });
+                }
+                None => parsed.related.push(RelatedLocation { file, range, message: child_message.clone() }),
+            }
+        }
+        if !edits.is_empty() {
+            parsed.fixes.push(SuggestedFix { message: child_message, edits, machine_applicable });
+        }
+    }
+    Some(parsed)
+}
+
+/// A span's file and range; spans inside macros from other crates point at the invocation instead
+fn cargo_location(span: &Value, workspace_root: &Path) -> Option<(String, DiagnosticRange)> {
+    let mut current = span;
+    loop {
+        let file = current.get("file_name").and_then(|f| f.as_str())?;
+        let path = workspace_root.join(file);
+        let outside = path.is_absolute() && !path.starts_with(workspace_root) || file.starts_with('<');
+        match current.get("expansion").and_then(|e| e.get("span")) {
+            Some(parent) if outside => current = parent,
+            _ => {
+                let range = DiagnosticRange {
+                    start_line: number(current, "line_start")?,
+                    start_column: number(current, "column_start")?,
+                    end_line: number(current, "line_end")?,
+                    end_column: number(current, "column_end")?,
+                };
+                return Some((path.to_string_lossy().to_string(), range));
+            }
+        }
+    }
+}
+
+// ================================
+// TSC
+// ================================
+
+/// `tsc --noEmit --pretty false`: `file(line,col): error TS1234: message`, continued on indented lines
+pub fn parse_tsc(output: &str, cwd: &Path) -> Vec<Diagnostic> {
+    let Ok(located) = Regex::new(r"^(.+?)\((\d+),(\d+)\): (error|warning|message) (TS\d+): (.*)$") else {
+        return Vec::new();
+    };
+    let Ok(global) = Regex::new(r"^(error|warning|message) (TS\d+): (.*)$") else {
+        return Vec::new();
+    };
+    let severity = |label: &str| match label {
+        "error" => DiagnosticSeverity::Error,
+        "warning" => DiagnosticSeverity::Warning,
+        _ => DiagnosticSeverity::Info,
+    };
+
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut pending: Option<(String, DiagnosticRange, DiagnosticSeverity, String, String)> = None;
+    let mut flush = |pending: &mut Option<(String, DiagnosticRange, DiagnosticSeverity, String, String)>| {
+        if let Some((file, range, severity, code, message)) = pending.take() {
+            diagnostics.push(diagnostic(DiagnosticTool::Tsc, file, range, severity, Some(code), message));
+        }
+    };
+    for line in output.lines() {
+        if let Some(captures) = located.captures(line) {
+            flush(&mut pending);
+            let line_number: u32 = captures[2].parse().unwrap_or(1);
+            let column: u32 = captures[3].parse().unwrap_or(1);
+            pending = Some((absolute(cwd, &captures[1]), DiagnosticRange::point(line_number, column), severity(&captures[4]), captures[5].to_string(), captures[6].to_string()));
+        } else if let Some(captures) = global.captures(line) {
+            // Configuration problems have no file; pin them to the tsconfig
+            flush(&mut pending);
+            pending = Some((absolute(cwd, "tsconfig.json"), DiagnosticRange::point(1, 1), severity(&captures[1]), captures[2].to_string(), captures[3].to_string()));
+        } else if line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+            if let Some((_, _, _, _, message)) = pending.as_mut() {
+                message.push('\n');
+                message.push_str(line.trim_end());
+            }
+        }
+    }
+    flush(&mut pending);
+    diagnostics
+}
+
+// ================================
+// ESLINT
+// ================================
+
+/// `eslint -f json`: results per file; fixes are UTF-16 offsets into the file's text
+pub fn parse_eslint(output: &str) -> Vec<Diagnostic> {
+    let Some(results) = json_payload(output).and_then(|v| v.as_array().cloned()) else {
+        return Vec::new();
+    };
+    let mut diagnostics = Vec::new();
+    for result in &results {
+        let Some(file) = result.get("filePath").and_then(|f| f.as_str()) else {
+            continue;
+        };
+        let mut source: Option<String> = None;
+        for message in result.get("messages").and_then(|m| m.as_array()).into_iter().flatten() {
+            let line = number(message, "line").unwrap_or(1);
+            let column = number(message, "column").unwrap_or(1);
+            let range = DiagnosticRange {
+                start_line: line,
+                start_column: column,
+                end_line: number(message, "endLine").unwrap_or(line),
+                end_column: number(message, "endColumn").unwrap_or(column),
+            };
+            let severity = if message.get("severity").and_then(|s| s.as_u64()) == Some(2) { DiagnosticSeverity::Error } else { DiagnosticSeverity::Warning };
+            let code = message.get("ruleId").and_then(|r| r.as_str()).map(str::to_string);
+            let text = message.get("message").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+            let mut parsed = diagnostic(DiagnosticTool::Eslint, file.to_string(), range, severity, code, text);
+
+            let mut offset_fix = |fix: &Value, description: String, machine_applicable: bool| -> Option<SuggestedFix> {
+                let bounds = fix.get("range")?.as_array()?;
+                let (start, end) = (bounds.first()?.as_u64()? as usize, bounds.get(1)?.as_u64()? as usize);
+                let text = source.get_or_insert_with(|| std::fs::read_to_string(file).unwrap_or_default());
+                let (start_line, start_column) = utf16_position(text, start);
+                let (end_line, end_column) = utf16_position(text, end);
+                Some(SuggestedFix {
+                    message: description,
+                    edits: vec![TextEdit {
+                        file: file.to_string(),
+                        range: DiagnosticRange { start_line, start_column, end_line, end_column },
+                        replacement: fix.get("text")?.as_str()?.to_string(),
+                    }],
+                    machine_applicable,
+                })
+            };
+            if let Some(fix) = message.get("fix").and_then(|fix| offset_fix(fix, "Apply the rule's fix".to_string(), true)) {
+                parsed.fixes.push(fix);
+            }
+            for suggestion in message.get("suggestions").and_then(|s| s.as_array()).into_iter().flatten() {
+                let description = suggestion.get("desc").and_then(|d| d.as_str()).unwrap_or("Apply suggestion").to_string();
+                if let Some(fix) = suggestion.get("fix").and_then(|fix| offset_fix(fix, description, false)) {
+                    parsed.fixes.push(fix);
+                }
+            }
+            diagnostics.push(parsed);
+        }
+    }
+    diagnostics
+}
+
+/// 1-based line and column of a UTF-16 code unit offset, as JavaScript tools count
+fn utf16_position(text: &str, offset: usize) -> (u32, u32) {
+    let (mut line, mut column, mut units) = (1u32, 1u32, 0usize);
+    for c in text.chars() {
+        if units >= offset {
+            break;
+        }
+        units += c.len_utf16();
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += c.len_utf16() as u32;
+        }
+    }
+    (line, column)
+}
+
+// ================================
+// RUFF
+// ================================
+
+/// `ruff check --output-format=json`: a flat array; entries without a code are syntax errors
+pub fn parse_ruff(output: &str, cwd: &Path) -> Vec<Diagnostic> {
+    let Some(entries) = json_payload(output).and_then(|v| v.as_array().cloned()) else {
+        return Vec::new();
+    };
+    let position = |value: Option<&Value>| -> Option<(u32, u32)> {
+        let value = value?;
+        Some((number(value, "row")?, number(value, "column")?))
+    };
+
+    let mut diagnostics = Vec::new();
+    for entry in &entries {
+        let Some(file) = entry.get("filename").and_then(|f| f.as_str()) else {
+            continue;
+        };
+        let file = absolute(cwd, file);
+        let (start_line, start_column) = position(entry.get("location")).unwrap_or((1, 1));
+        let (end_line, end_column) = position(entry.get("end_location")).unwrap_or((start_line, start_column));
+        let code = entry.get("code").and_then(|c| c.as_str()).map(str::to_string);
+        let severity = if code.is_some() { DiagnosticSeverity::Warning } else { DiagnosticSeverity::Error };
+        let message = entry.get("message").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+        let mut parsed = diagnostic(
+            DiagnosticTool::Ruff,
+            file.clone(),
+            DiagnosticRange { start_line, start_column, end_line, end_column },
+            severity,
+            code,
+            message,
+        );
+
+        if let Some(fix) = entry.get("fix").filter(|f| !f.is_null()) {
+            let edits: Vec<TextEdit> = fix
+                .get("edits")
+                .and_then(|e| e.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|edit| {
+                    let (start_line, start_column) = position(edit.get("location"))?;
+                    let (end_line, end_column) = position(edit.get("end_location"))?;
+                    Some(TextEdit {
+                        file: file.clone(),
+                        range: DiagnosticRange { start_line, start_column, end_line, end_column },
+                        replacement: edit.get("content").and_then(|c| c.as_str()).unwrap_or_default().to_string(),
+                    })
+                })
+                .collect();
+            if !edits.is_empty() {
+                let applicability = fix.get("applicability").and_then(|a| a.as_str()).unwrap_or_default();
+                parsed.fixes.push(SuggestedFix {
+                    message: fix.get("message").and_then(|m| m.as_str()).unwrap_or("Apply fix").to_string(),
+                    edits,
+                    machine_applicable: matches!(applicability, "safe" | "Automatic"),
+                });
+            }
+        }
+        diagnostics.push(parsed);
+    }
+    diagnostics
+}
+
+/// The JSON document in a tool's stdout, skipping banners some package managers print first
+fn json_payload(output: &str) -> Option<Value> {
+    let start = output.find(['[', '{'])?;
+    serde_json::from_str(output[start..].trim()).ok()
+}
+
+/// Drop repeats within one run, e.g. cargo reporting a warning for both the lib and its test build
+pub fn dedupe(diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    let mut seen = HashSet::new();
+    diagnostics.into_iter().filter(|d| seen.insert(d.id.clone())).collect()
+}
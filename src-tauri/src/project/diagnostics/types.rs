@@ -0,0 +1,152 @@
+// Syntari AI IDE - Diagnostics Types
+// Compiler and linter diagnostics, suggested fixes and per-tool run records
+
+use serde::{Deserialize, Serialize};
+
+// ================================
+// TOOLS
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticTool {
+    /// `cargo check --message-format=json`
+    Cargo,
+    /// `tsc --noEmit --pretty false`
+    Tsc,
+    /// `eslint -f json`
+    Eslint,
+    /// `ruff check --output-format=json`
+    Ruff,
+}
+
+impl DiagnosticTool {
+    pub const ALL: [DiagnosticTool; 4] = [Self::Cargo, Self::Tsc, Self::Eslint, Self::Ruff];
+
+    /// Saving a file with one of these extensions reruns the tool
+    pub fn watched_extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Cargo => &["rs", "toml"],
+            Self::Tsc => &["ts", "tsx", "mts", "cts", "json"],
+            Self::Eslint => &["js", "jsx", "mjs", "cjs", "ts", "tsx", "vue"],
+            Self::Ruff => &["py", "pyi", "toml"],
+        }
+    }
+}
+
+/// One way of running a tool in one directory of the project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticProvider {
+    pub tool: DiagnosticTool,
+    pub cwd: String,
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl DiagnosticProvider {
+    /// Results of a provider replace its previous results, keyed by this
+    pub fn key(&self) -> String {
+        format!("{:?}@{}", self.tool, self.cwd).to_lowercase()
+    }
+}
+
+// ================================
+// DIAGNOSTICS
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+/// 1-based lines and columns; the end column is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DiagnosticRange {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+impl DiagnosticRange {
+    pub fn point(line: u32, column: u32) -> Self {
+        Self { start_line: line, start_column: column, end_line: line, end_column: column }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub file: String,
+    pub range: DiagnosticRange,
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuggestedFix {
+    pub message: String,
+    pub edits: Vec<TextEdit>,
+    /// Whether the tool considers the fix safe to apply without review
+    pub machine_applicable: bool,
+}
+
+/// Secondary location, e.g. where a moved value was first used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelatedLocation {
+    pub file: String,
+    pub range: DiagnosticRange,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Content hash of tool, file, range, code and message; the same problem keeps its id across runs
+    pub id: String,
+    pub tool: DiagnosticTool,
+    /// Absolute path
+    pub file: String,
+    pub range: DiagnosticRange,
+    pub severity: DiagnosticSeverity,
+    pub code: Option<String>,
+    pub message: String,
+    pub related: Vec<RelatedLocation>,
+    pub fixes: Vec<SuggestedFix>,
+}
+
+// ================================
+// RUNS AND SNAPSHOTS
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticRunInfo {
+    pub provider: DiagnosticProvider,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub diagnostic_count: usize,
+    /// Set when the tool could not run or produced nothing readable; its previous diagnostics are kept
+    pub error: Option<String>,
+}
+
+/// Payload of the `diagnostics-updated` event: what one provider's run changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsUpdate {
+    pub project_path: String,
+    pub run: DiagnosticRunInfo,
+    pub added: Vec<Diagnostic>,
+    pub removed: Vec<String>,
+    pub error_count: usize,
+    pub warning_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsSnapshot {
+    pub project_path: String,
+    /// Sorted by file, then position
+    pub diagnostics: Vec<Diagnostic>,
+    pub runs: Vec<DiagnosticRunInfo>,
+    pub error_count: usize,
+    pub warning_count: usize,
+}
@@ -0,0 +1,69 @@
+// Syntari AI IDE - License Compliance Commands
+// License inventory and report export exposed to the frontend
+
+use tauri::State;
+use std::path::Path;
+use crate::core::{AppError, AppResult, AppState, TauriResult};
+use crate::project::commands::project_directories;
+use crate::project::licenses::{self, LicensePolicy, LicenseReport, LicenseReportFormat};
+
+/// License inventory of the project and its locked dependencies, checked against `policy` or the project's policy file
+#[tauri::command]
+pub async fn get_license_report(
+    project_path: Option<String>,
+    policy: Option<LicensePolicy>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<LicenseReport>, String> {
+    let result = build_license_report(&state, project_path, policy).await;
+
+    if let Err(e) = &result {
+        e.log_warn();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+/// Render the report as JSON or CSV, writing it to `output_path` when given
+#[tauri::command]
+pub async fn export_license_report(
+    format: LicenseReportFormat,
+    output_path: Option<String>,
+    project_path: Option<String>,
+    policy: Option<LicensePolicy>,
+    state: State<'_, AppState>,
+) -> std::result::Result<TauriResult<String>, String> {
+    let result: AppResult<String> = async {
+        let report = build_license_report(&state, project_path, policy).await?;
+        let content = licenses::export_report(&report, format)?;
+        if let Some(path) = &output_path {
+            std::fs::write(path, &content)?;
+            tracing::info!("📤 Exported license report ({}) to {}", format.extension(), path);
+        }
+        Ok(content)
+    }.await;
+
+    if let Err(e) = &result {
+        e.log_error();
+    }
+    Ok(TauriResult::from_result(result))
+}
+
+async fn build_license_report(state: &AppState, project_path: Option<String>, policy: Option<LicensePolicy>) -> AppResult<LicenseReport> {
+    let (root, sub_projects) = project_directories(state, project_path).await?;
+    let policy = match policy {
+        Some(policy) => policy,
+        None => licenses::load_policy(Path::new(&root))?,
+    };
+
+    // Walks node_modules, virtual environments and the cargo registry, so keep it off the async runtime
+    let report = tokio::task::spawn_blocking(move || licenses::build_report(Path::new(&root), &sub_projects, policy))
+        .await
+        .map_err(|e| AppError::internal("LICENSE_SCAN_FAILED".to_string(), format!("License scan did not complete: {}", e)))?;
+
+    tracing::info!(
+        "⚖️ License report: {} packages, {} violations, {} unknown",
+        report.entries.len(),
+        report.violations,
+        report.unknown
+    );
+    Ok(report)
+}
@@ -0,0 +1,486 @@
+// Syntari AI IDE - License Compliance
+// Inventory of project and dependency licenses from manifests, lockfiles and installed packages
+
+pub mod types;
+pub mod spdx;
+pub mod commands;
+
+pub use types::{LicenseEntry, LicensePolicy, LicenseReport, LicenseReportFormat, LicenseStatus};
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::core::{AppError, AppResult};
+use crate::project::audit::ecosystem;
+use crate::project::cargo;
+use crate::project::dependencies::{elements, tag_text};
+use crate::project::lockfile::{self, LockfileKind, ResolvedPackage};
+use crate::project::licenses::types::{LicenseSource, LicenseSourceKind};
+
+/// Project-level policy file, relative to the project root
+pub const POLICY_FILE: &str = ".syntari/license-policy.json";
+
+/// The project's policy file, or the default policy when there is none
+pub fn load_policy(project_root: &Path) -> AppResult<LicensePolicy> {
+    let path = project_root.join(POLICY_FILE);
+    if !path.is_file() {
+        return Ok(LicensePolicy::default());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&content).map_err(|e| {
+        AppError::project_with_path(
+            "INVALID_LICENSE_POLICY".to_string(),
+            format!("Invalid license policy: {}", e),
+            path.to_string_lossy().to_string(),
+        )
+    })
+}
+
+// ================================
+// REPORT
+// ================================
+
+/// License of the project itself plus every package its lockfiles, and those of its sub-projects, resolve
+pub fn build_report(project_root: &Path, sub_projects: &[String], policy: LicensePolicy) -> LicenseReport {
+    let mut project = LicenseEntry {
+        package: project_name(project_root),
+        version: None,
+        ecosystem: None,
+        license: String::new(),
+        sources: project_sources(project_root),
+        status: LicenseStatus::Unknown,
+        reason: None,
+    };
+    resolve(&mut project, &policy);
+
+    let graphs = lockfile::load_graphs(project_root, sub_projects);
+    let mut npm_metadata = HashMap::new();
+    for graph in graphs.iter().filter(|g| g.kind == LockfileKind::Npm) {
+        npm_metadata.extend(package_lock_licenses(Path::new(&graph.lockfile)));
+    }
+    let mut entries = Vec::new();
+    for graph in graphs {
+        let lockfile_dir = Path::new(&graph.lockfile).parent().map(Path::to_path_buf).unwrap_or_else(|| project_root.to_path_buf());
+        for package in graph.packages.iter().filter(|p| !graph.roots.contains(&p.id)) {
+            let mut sources = Vec::new();
+            if let Some((path, declared)) = npm_metadata.get(&(package.name.clone(), package.version.clone())) {
+                sources.push(source(LicenseSourceKind::Lockfile, path, declared));
+            }
+            sources.extend(installed_sources(project_root, &lockfile_dir, graph.kind, package));
+            entries.push(LicenseEntry {
+                package: package.name.clone(),
+                version: Some(package.version.clone()),
+                ecosystem: Some(ecosystem(graph.kind).to_string()),
+                license: String::new(),
+                sources,
+                status: LicenseStatus::Unknown,
+                reason: None,
+            });
+        }
+    }
+
+    // The same package can appear in several lockfiles, e.g. npm and pnpm during a migration or in two
+    // sub-projects; merge their sources before resolving so one verdict covers every copy
+    entries.sort_by(|a, b| {
+        a.package
+            .cmp(&b.package)
+            .then_with(|| a.version.cmp(&b.version))
+            .then_with(|| a.ecosystem.cmp(&b.ecosystem))
+    });
+    entries.dedup_by(|duplicate, kept| {
+        if duplicate.package != kept.package || duplicate.version != kept.version || duplicate.ecosystem != kept.ecosystem {
+            return false;
+        }
+        for source in duplicate.sources.drain(..) {
+            if !kept.sources.iter().any(|s| s.path == source.path) {
+                kept.sources.push(source);
+            }
+        }
+        true
+    });
+    for entry in &mut entries {
+        resolve(entry, &policy);
+    }
+    entries.sort_by(|a, b| {
+        status_rank(a.status)
+            .cmp(&status_rank(b.status))
+            .then_with(|| a.package.cmp(&b.package))
+            .then_with(|| a.version.cmp(&b.version))
+    });
+
+    LicenseReport {
+        project_path: project_root.to_string_lossy().to_string(),
+        violations: entries.iter().filter(|e| e.status == LicenseStatus::Violation).count(),
+        unknown: entries.iter().filter(|e| e.status == LicenseStatus::Unknown).count(),
+        project,
+        policy,
+        entries,
+        generated_at: crate::core::current_timestamp(),
+    }
+}
+
+fn status_rank(status: LicenseStatus) -> u8 {
+    match status {
+        LicenseStatus::Violation => 0,
+        LicenseStatus::Unknown => 1,
+        LicenseStatus::Allowed => 2,
+        LicenseStatus::Ignored => 3,
+    }
+}
+
+/// Pick the license from the sources and check it against the policy
+fn resolve(entry: &mut LicenseEntry, policy: &LicensePolicy) {
+    // Declared licenses win; license texts only fill in when nothing usable was declared
+    let declared = entry
+        .sources
+        .iter()
+        .filter(|s| s.kind != LicenseSourceKind::LicenseFile)
+        .map(|s| spdx::normalize(&s.declared))
+        .find(|license| license != spdx::NOASSERTION && license != "LicenseRef-SeeLicenseFile");
+    let mut detected: Vec<String> = entry
+        .sources
+        .iter()
+        .filter(|s| s.kind == LicenseSourceKind::LicenseFile)
+        .map(|s| s.declared.clone())
+        .collect();
+    detected.sort();
+    detected.dedup();
+    // Without a declaration every license text found applies
+    entry.license = declared.unwrap_or_else(|| if detected.is_empty() { spdx::NOASSERTION.to_string() } else { detected.join(" AND ") });
+
+    if policy.ignore_packages.iter().any(|p| p == &entry.package) {
+        entry.status = LicenseStatus::Ignored;
+        entry.reason = Some("Exempted by policy".to_string());
+    } else if let Some(offending) = spdx::violations(&entry.license, policy) {
+        entry.status = LicenseStatus::Violation;
+        entry.reason = Some(format!("Not permitted by policy: {}", offending.join(", ")));
+    } else if policy.flag_unknown && (entry.license == spdx::NOASSERTION || entry.license.contains("LicenseRef-")) {
+        entry.status = LicenseStatus::Unknown;
+        entry.reason = Some(if entry.license == spdx::NOASSERTION {
+            "No license declaration or license file found".to_string()
+        } else {
+            "License is not a recognized SPDX identifier".to_string()
+        });
+    } else {
+        entry.status = LicenseStatus::Allowed;
+        entry.reason = None;
+    }
+}
+
+fn source(kind: LicenseSourceKind, path: &Path, declared: &str) -> LicenseSource {
+    LicenseSource {
+        kind,
+        path: path.to_string_lossy().to_string(),
+        declared: declared.trim().to_string(),
+    }
+}
+
+// ================================
+// PROJECT MANIFESTS
+// ================================
+
+fn project_name(project_root: &Path) -> String {
+    let read_json = |file: &str| -> Option<serde_json::Value> { serde_json::from_str(&std::fs::read_to_string(project_root.join(file)).ok()?).ok() };
+    let read_toml = |file: &str| -> Option<toml::Table> { std::fs::read_to_string(project_root.join(file)).ok()?.parse().ok() };
+    read_json("package.json")
+        .and_then(|json| json.get("name")?.as_str().map(str::to_string))
+        .or_else(|| read_toml(cargo::MANIFEST_FILE).and_then(|t| t.get("package")?.get("name")?.as_str().map(str::to_string)))
+        .or_else(|| read_toml("pyproject.toml").and_then(|t| t.get("project")?.get("name")?.as_str().map(str::to_string)))
+        .or_else(|| read_json("composer.json").and_then(|json| json.get("name")?.as_str().map(str::to_string)))
+        .or_else(|| project_root.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default()
+}
+
+/// Declarations in the project's own manifests plus its license files
+fn project_sources(project_root: &Path) -> Vec<LicenseSource> {
+    let mut sources = Vec::new();
+    let read = |file: &str| std::fs::read_to_string(project_root.join(file)).ok();
+
+    if let Some(json) = read("package.json").and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) {
+        sources.extend(npm_license(&json).map(|d| source(LicenseSourceKind::Manifest, &project_root.join("package.json"), &d)));
+    }
+    let manifest = project_root.join(cargo::MANIFEST_FILE);
+    if manifest.is_file() {
+        sources.extend(cargo_license(project_root, true).map(|d| source(LicenseSourceKind::Manifest, &manifest, &d)));
+    }
+    if let Some(table) = read("pyproject.toml").and_then(|c| c.parse::<toml::Table>().ok()) {
+        sources.extend(pyproject_license(&table).map(|d| source(LicenseSourceKind::Manifest, &project_root.join("pyproject.toml"), &d)));
+    }
+    if let Some(json) = read("composer.json").and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) {
+        sources.extend(npm_license(&json).map(|d| source(LicenseSourceKind::Manifest, &project_root.join("composer.json"), &d)));
+    }
+    if let Some(pom) = read("pom.xml") {
+        let names: Vec<String> = elements(&pom, "license").iter().filter_map(|license| tag_text(license, "name")).collect();
+        if !names.is_empty() {
+            sources.push(source(LicenseSourceKind::Manifest, &project_root.join("pom.xml"), &spdx::normalize_any(&names)));
+        }
+    }
+    sources.extend(license_files(project_root));
+    sources
+}
+
+/// `license`, the legacy `licenses` array or `{ "type": ... }` objects, as used by npm and Composer
+fn npm_license(json: &serde_json::Value) -> Option<String> {
+    let text = |value: &serde_json::Value| -> Option<String> {
+        value.as_str().or_else(|| value.get("type")?.as_str()).map(str::to_string)
+    };
+    let declared: Vec<String> = match json.get("license").or_else(|| json.get("licenses"))? {
+        serde_json::Value::Array(items) => items.iter().filter_map(text).collect(),
+        value => text(value).into_iter().collect(),
+    };
+    (!declared.is_empty()).then(|| spdx::normalize_any(&declared))
+}
+
+/// `license` of a Cargo manifest; `license-file` texts are picked up with the other license files
+fn cargo_license(dir: &Path, inherit: bool) -> Option<String> {
+    let manifest: toml::Table = std::fs::read_to_string(dir.join(cargo::MANIFEST_FILE)).ok()?.parse().ok()?;
+    let package = manifest.get("package")?;
+    match package.get("license")? {
+        toml::Value::String(license) => Some(license.clone()),
+        // `license.workspace = true`; published crates never inherit
+        toml::Value::Table(_) if inherit => {
+            let workspace = cargo::load_workspace(dir).ok()?;
+            let root: toml::Table = std::fs::read_to_string(&workspace.manifest_path).ok()?.parse().ok()?;
+            root.get("workspace")?.get("package")?.get("license")?.as_str().map(str::to_string)
+        }
+        _ => None,
+    }
+}
+
+/// PEP 639 expression, PEP 621 table, Poetry's field, or trove classifiers
+fn pyproject_license(table: &toml::Table) -> Option<String> {
+    let project = table.get("project");
+    let declared = match project.and_then(|p| p.get("license")) {
+        Some(toml::Value::String(expression)) => Some(expression.clone()),
+        Some(toml::Value::Table(license)) => license.get("text").and_then(|t| t.as_str()).map(str::to_string),
+        _ => None,
+    };
+    declared
+        .or_else(|| table.get("tool")?.get("poetry")?.get("license")?.as_str().map(str::to_string))
+        .or_else(|| {
+            let classifiers = project?.get("classifiers")?.as_array()?;
+            let ids: Vec<String> = classifiers.iter().filter_map(|c| spdx::from_classifier(c.as_str()?)).collect();
+            (!ids.is_empty()).then(|| spdx::normalize_any(&ids))
+        })
+}
+
+// ================================
+// LICENSE FILES
+// ================================
+
+const LICENSE_FILE_PREFIXES: &[&str] = &["LICENSE", "LICENCE", "COPYING", "UNLICENSE", "COPYRIGHT"];
+
+/// LICENSE, COPYING and similar files directly in `dir`, with the license their text matches
+fn license_files(dir: &Path) -> Vec<LicenseSource> {
+    let mut sources = Vec::new();
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_uppercase()).unwrap_or_default();
+            path.is_file() && LICENSE_FILE_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    files.sort();
+    for path in files {
+        // License texts are short; anything large is not one
+        if std::fs::metadata(&path).map(|m| m.len() > 256 * 1024).unwrap_or(true) {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Some(license) = spdx::detect_license_text(&text) {
+            sources.push(source(LicenseSourceKind::LicenseFile, &path, &license));
+        }
+    }
+    sources
+}
+
+// ================================
+// INSTALLED AND VENDORED PACKAGES
+// ================================
+
+/// `license` fields npm records in package-lock.json, keyed by (name, version)
+fn package_lock_licenses(path: &Path) -> HashMap<(String, String), (PathBuf, String)> {
+    let mut licenses = HashMap::new();
+    let Some(lock) = std::fs::read_to_string(&path).ok().and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) else {
+        return licenses;
+    };
+    for (key, package) in lock.get("packages").and_then(|p| p.as_object()).into_iter().flatten() {
+        let Some(install_path) = key.rsplit_once("node_modules/").map(|(_, name)| name) else {
+            continue;
+        };
+        let name = package.get("name").and_then(|n| n.as_str()).unwrap_or(install_path);
+        let (Some(version), Some(license)) = (package.get("version").and_then(|v| v.as_str()), npm_license(package)) else {
+            continue;
+        };
+        licenses.insert((name.to_string(), version.to_string()), (path.to_path_buf(), license));
+    }
+    licenses
+}
+
+/// Manifests and license files of the package's installed or vendored copy
+fn installed_sources(project_root: &Path, lockfile_dir: &Path, kind: LockfileKind, package: &ResolvedPackage) -> Vec<LicenseSource> {
+    match kind {
+        LockfileKind::Npm | LockfileKind::Yarn | LockfileKind::Pnpm => npm_package_dir(lockfile_dir, package)
+            .map(|dir| {
+                let manifest = dir.join("package.json");
+                let declared = std::fs::read_to_string(&manifest)
+                    .ok()
+                    .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+                    .and_then(|json| npm_license(&json));
+                declared.map(|d| source(LicenseSourceKind::Vendored, &manifest, &d)).into_iter().chain(license_files(&dir)).collect()
+            })
+            .unwrap_or_default(),
+        LockfileKind::Cargo => crate::ai::context7::local_docs::find_crate_source(project_root, &package.name, Some(&package.version))
+            .map(|dir| {
+                let declared = cargo_license(&dir, false);
+                declared
+                    .map(|d| source(LicenseSourceKind::Vendored, &dir.join(cargo::MANIFEST_FILE), &d))
+                    .into_iter()
+                    .chain(license_files(&dir))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        LockfileKind::Poetry => python_dist_info(lockfile_dir, package).map(|dir| dist_info_sources(&dir)).unwrap_or_default(),
+        // Go modules declare no license; only the texts shipped with the module count
+        LockfileKind::Go => go_module_dir(lockfile_dir, package).map(|dir| license_files(&dir)).unwrap_or_default(),
+    }
+}
+
+/// node_modules copy with the locked version, including pnpm's content-addressed layout
+fn npm_package_dir(lockfile_dir: &Path, package: &ResolvedPackage) -> Option<PathBuf> {
+    let node_modules = lockfile_dir.join("node_modules");
+    let pnpm_dir = format!("{}@{}", package.name.replace('/', "+"), package.version);
+    [
+        node_modules.join(&package.name),
+        node_modules.join(".pnpm").join(pnpm_dir).join("node_modules").join(&package.name),
+    ]
+    .into_iter()
+    .find(|dir| {
+        std::fs::read_to_string(dir.join("package.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+            .is_some_and(|json| json.get("version").and_then(|v| v.as_str()) == Some(package.version.as_str()))
+    })
+}
+
+/// `<name>-<version>.dist-info` in the project's virtual environment
+fn python_dist_info(lockfile_dir: &Path, package: &ResolvedPackage) -> Option<PathBuf> {
+    let normalize = |name: &str| name.to_lowercase().replace(['-', '.'], "_");
+    let wanted = format!("{}-{}.dist-info", normalize(&package.name), package.version.to_lowercase());
+    for venv in [".venv", "venv", "env"] {
+        let venv = lockfile_dir.join(venv);
+        let mut site_packages = vec![venv.join("Lib").join("site-packages")];
+        for lib in std::fs::read_dir(venv.join("lib")).into_iter().flatten().flatten() {
+            site_packages.push(lib.path().join("site-packages"));
+        }
+        for dir in site_packages {
+            for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let (stem, version) = name.trim_end_matches(".dist-info").rsplit_once('-').unwrap_or(("", ""));
+                if format!("{}-{}.dist-info", normalize(stem), version.to_lowercase()) == wanted {
+                    return Some(entry.path());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Core metadata: `License-Expression`, then `License`, then license classifiers; plus bundled license files
+fn dist_info_sources(dist_info: &Path) -> Vec<LicenseSource> {
+    let metadata_path = dist_info.join("METADATA");
+    let mut sources = Vec::new();
+    if let Ok(metadata) = std::fs::read_to_string(&metadata_path) {
+        // Headers end at the first blank line; the description follows
+        let headers: Vec<(&str, &str)> = metadata
+            .lines()
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(": "))
+            .collect();
+        let header = |key: &str| headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v.trim().to_string());
+        let classifiers: Vec<String> = headers
+            .iter()
+            .filter(|(k, v)| k.eq_ignore_ascii_case("Classifier") && v.starts_with("License ::"))
+            .filter_map(|(_, v)| spdx::from_classifier(v))
+            .collect();
+        // Some packages paste the whole license text into `License`
+        let license = header("License").filter(|l| l.len() <= 100 && !l.eq_ignore_ascii_case("UNKNOWN"));
+        let declared = header("License-Expression")
+            .or(license)
+            .or_else(|| (!classifiers.is_empty()).then(|| spdx::normalize_any(&classifiers)));
+        sources.extend(declared.map(|d| source(LicenseSourceKind::Vendored, &metadata_path, &d)));
+    }
+    sources.extend(license_files(dist_info));
+    sources.extend(license_files(&dist_info.join("licenses")));
+    sources
+}
+
+/// Vendored copy, then the module cache; module paths escape capitals as `!x`
+fn go_module_dir(lockfile_dir: &Path, package: &ResolvedPackage) -> Option<PathBuf> {
+    let vendored = lockfile_dir.join("vendor").join(&package.name);
+    if vendored.is_dir() {
+        return Some(vendored);
+    }
+    let escaped: String = package
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_uppercase() { format!("!{}", c.to_ascii_lowercase()) } else { c.to_string() })
+        .collect();
+    let cache = std::env::var_os("GOMODCACHE")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("GOPATH").map(|p| PathBuf::from(p).join("pkg").join("mod")))
+        .or_else(|| dirs::home_dir().map(|h| h.join("go").join("pkg").join("mod")))?;
+    let dir = cache.join(format!("{}@{}", escaped, package.version));
+    dir.is_dir().then_some(dir)
+}
+
+// ================================
+// EXPORT
+// ================================
+
+pub fn export_report(report: &LicenseReport, format: LicenseReportFormat) -> AppResult<String> {
+    match format {
+        LicenseReportFormat::Json => serde_json::to_string_pretty(report)
+            .map_err(|e| AppError::internal("LICENSE_EXPORT_FAILED".to_string(), format!("Could not serialize license report: {}", e))),
+        LicenseReportFormat::Csv => Ok(to_csv(report)),
+    }
+}
+
+/// One row per package, the project itself first
+fn to_csv(report: &LicenseReport) -> String {
+    let mut csv = String::from("package,version,ecosystem,license,status,reason,sources\n");
+    for entry in std::iter::once(&report.project).chain(&report.entries) {
+        let status = match entry.status {
+            LicenseStatus::Allowed => "allowed",
+            LicenseStatus::Violation => "violation",
+            LicenseStatus::Unknown => "unknown",
+            LicenseStatus::Ignored => "ignored",
+        };
+        let sources: Vec<&str> = entry.sources.iter().map(|s| s.path.as_str()).collect();
+        let row = [
+            entry.package.as_str(),
+            entry.version.as_deref().unwrap_or(""),
+            entry.ecosystem.as_deref().unwrap_or(""),
+            entry.license.as_str(),
+            status,
+            entry.reason.as_deref().unwrap_or(""),
+            &sources.join(";"),
+        ]
+        .map(csv_field);
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
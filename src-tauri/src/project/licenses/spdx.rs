@@ -0,0 +1,306 @@
+// Syntari AI IDE - SPDX Normalization
+// Turns declared licenses and license texts into SPDX expressions and checks them against a policy
+
+use crate::project::licenses::types::LicensePolicy;
+
+/// Placeholder SPDX uses when nothing could be determined
+pub const NOASSERTION: &str = "NOASSERTION";
+
+const KNOWN_IDS: &[&str] = &[
+    "0BSD", "AFL-3.0", "AGPL-3.0-only", "AGPL-3.0-or-later", "Apache-1.1", "Apache-2.0", "Artistic-2.0",
+    "BlueOak-1.0.0", "BSD-1-Clause", "BSD-2-Clause", "BSD-3-Clause", "BSD-3-Clause-Clear", "BSL-1.0",
+    "CC-BY-3.0", "CC-BY-4.0", "CC-BY-SA-4.0", "CC0-1.0", "CDDL-1.0", "CDDL-1.1", "EPL-1.0", "EPL-2.0",
+    "EUPL-1.2", "GPL-2.0-only", "GPL-2.0-or-later", "GPL-3.0-only", "GPL-3.0-or-later", "ISC",
+    "LGPL-2.0-only", "LGPL-2.0-or-later", "LGPL-2.1-only", "LGPL-2.1-or-later", "LGPL-3.0-only",
+    "LGPL-3.0-or-later", "MIT", "MIT-0", "MPL-1.1", "MPL-2.0", "MS-PL", "NCSA", "OFL-1.1", "OpenSSL",
+    "PSF-2.0", "Python-2.0", "Ruby", "SSPL-1.0", "Unicode-3.0", "Unicode-DFS-2016", "Unlicense", "W3C",
+    "WTFPL", "X11", "Zlib", "zlib-acknowledgement",
+];
+
+/// Common spellings that are not SPDX identifiers, including deprecated ids
+const ALIASES: &[(&str, &str)] = &[
+    ("mit license", "MIT"),
+    ("the mit license", "MIT"),
+    ("expat", "MIT"),
+    ("apache 2.0", "Apache-2.0"),
+    ("apache-2", "Apache-2.0"),
+    ("apache 2", "Apache-2.0"),
+    ("apache2", "Apache-2.0"),
+    ("apache license 2.0", "Apache-2.0"),
+    ("apache license, version 2.0", "Apache-2.0"),
+    ("apache software license", "Apache-2.0"),
+    ("the apache software license, version 2.0", "Apache-2.0"),
+    ("bsd-3", "BSD-3-Clause"),
+    ("new bsd", "BSD-3-Clause"),
+    ("new bsd license", "BSD-3-Clause"),
+    ("modified bsd", "BSD-3-Clause"),
+    ("bsd-2", "BSD-2-Clause"),
+    ("simplified bsd", "BSD-2-Clause"),
+    ("freebsd", "BSD-2-Clause"),
+    ("isc license", "ISC"),
+    ("gpl-2.0", "GPL-2.0-only"),
+    ("gpl-2.0+", "GPL-2.0-or-later"),
+    ("gplv2", "GPL-2.0-only"),
+    ("gplv2+", "GPL-2.0-or-later"),
+    ("gpl-3.0", "GPL-3.0-only"),
+    ("gpl-3.0+", "GPL-3.0-or-later"),
+    ("gplv3", "GPL-3.0-only"),
+    ("gplv3+", "GPL-3.0-or-later"),
+    ("lgpl-2.1", "LGPL-2.1-only"),
+    ("lgpl-2.1+", "LGPL-2.1-or-later"),
+    ("lgpl-3.0", "LGPL-3.0-only"),
+    ("lgpl-3.0+", "LGPL-3.0-or-later"),
+    ("lgplv3", "LGPL-3.0-only"),
+    ("agpl-3.0", "AGPL-3.0-only"),
+    ("agpl-3.0+", "AGPL-3.0-or-later"),
+    ("agplv3", "AGPL-3.0-only"),
+    ("mozilla public license 2.0", "MPL-2.0"),
+    ("mpl 2.0", "MPL-2.0"),
+    ("boost", "BSL-1.0"),
+    ("boost software license", "BSL-1.0"),
+    ("public domain", "Unlicense"),
+    ("the unlicense", "Unlicense"),
+    ("psf", "PSF-2.0"),
+    ("python software foundation license", "PSF-2.0"),
+    ("zlib license", "Zlib"),
+    ("cc0", "CC0-1.0"),
+    // npm's marker for "no license granted"
+    ("unlicensed", "LicenseRef-Proprietary"),
+    ("proprietary", "LicenseRef-Proprietary"),
+];
+
+/// PyPI trove classifiers after `License :: OSI Approved :: `
+const CLASSIFIERS: &[(&str, &str)] = &[
+    ("MIT License", "MIT"),
+    ("Apache Software License", "Apache-2.0"),
+    ("BSD License", "BSD-3-Clause"),
+    ("ISC License (ISCL)", "ISC"),
+    ("GNU General Public License v2 (GPLv2)", "GPL-2.0-only"),
+    ("GNU General Public License v2 or later (GPLv2+)", "GPL-2.0-or-later"),
+    ("GNU General Public License v3 (GPLv3)", "GPL-3.0-only"),
+    ("GNU General Public License v3 or later (GPLv3+)", "GPL-3.0-or-later"),
+    ("GNU Lesser General Public License v2 or later (LGPLv2+)", "LGPL-2.0-or-later"),
+    ("GNU Lesser General Public License v3 (LGPLv3)", "LGPL-3.0-only"),
+    ("GNU Lesser General Public License v3 or later (LGPLv3+)", "LGPL-3.0-or-later"),
+    ("GNU Affero General Public License v3", "AGPL-3.0-only"),
+    ("GNU Affero General Public License v3 or later (AGPLv3+)", "AGPL-3.0-or-later"),
+    ("Mozilla Public License 2.0 (MPL 2.0)", "MPL-2.0"),
+    ("Python Software Foundation License", "PSF-2.0"),
+    ("The Unlicense (Unlicense)", "Unlicense"),
+    ("Boost Software License 1.0 (BSL-1.0)", "BSL-1.0"),
+];
+
+// ================================
+// NORMALIZATION
+// ================================
+
+/// Normalize a declared license to an SPDX expression; identifiers that cannot be mapped become `LicenseRef-*`
+pub fn normalize(declared: &str) -> String {
+    let declared = declared.trim();
+    if declared.is_empty() {
+        return NOASSERTION.to_string();
+    }
+    if let Some(id) = lookup(declared) {
+        return id;
+    }
+    if declared.to_lowercase().starts_with("see license in") {
+        return "LicenseRef-SeeLicenseFile".to_string();
+    }
+
+    // Cargo's deprecated `MIT/Apache-2.0` means a choice between the two
+    let expression = declared.replace('/', " OR ");
+    let mut normalized: Vec<String> = Vec::new();
+    for token in tokenize(&expression) {
+        let upper = token.to_uppercase();
+        let piece = match upper.as_str() {
+            "AND" | "OR" | "WITH" => upper,
+            "(" | ")" => token,
+            // Exception ids such as `LLVM-exception` are kept as written
+            _ if normalized.last().is_some_and(|last| last == "WITH") => token,
+            _ => lookup(&token).unwrap_or_else(|| license_ref(&token)),
+        };
+        normalized.push(piece);
+    }
+    normalized.join(" ").replace("( ", "(").replace(" )", ")")
+}
+
+/// Join several declared licenses of one package, e.g. composer's array or Maven's `<licenses>`
+pub fn normalize_any(declared: &[String]) -> String {
+    let mut parts: Vec<String> = declared.iter().map(|d| normalize(d)).filter(|d| d != NOASSERTION).collect();
+    parts.dedup();
+    match parts.len() {
+        0 => NOASSERTION.to_string(),
+        1 => parts.remove(0),
+        _ => parts.iter().map(|p| if p.contains(' ') { format!("({})", p) } else { p.clone() }).collect::<Vec<_>>().join(" OR "),
+    }
+}
+
+pub fn from_classifier(classifier: &str) -> Option<String> {
+    let name = classifier.rsplit("::").next()?.trim();
+    CLASSIFIERS.iter().find(|(label, _)| *label == name).map(|(_, id)| id.to_string())
+}
+
+fn lookup(name: &str) -> Option<String> {
+    let lower = name.trim().to_lowercase();
+    KNOWN_IDS
+        .iter()
+        .find(|id| id.to_lowercase() == lower)
+        .map(|id| id.to_string())
+        .or_else(|| ALIASES.iter().find(|(alias, _)| *alias == lower).map(|(_, id)| id.to_string()))
+}
+
+fn license_ref(name: &str) -> String {
+    let id: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '-' }).collect();
+    if id.starts_with("LicenseRef-") { id } else { format!("LicenseRef-{}", id.trim_matches('-')) }
+}
+
+/// Identifiers, operators and parentheses; multi-word names between operators stay together
+fn tokenize(expression: &str) -> Vec<String> {
+    let spaced = expression.replace('(', " ( ").replace(')', " ) ");
+    let mut tokens: Vec<String> = Vec::new();
+    let mut name: Vec<&str> = Vec::new();
+    for word in spaced.split_whitespace() {
+        if matches!(word.to_uppercase().as_str(), "AND" | "OR" | "WITH" | "(" | ")") {
+            if !name.is_empty() {
+                tokens.push(name.join(" "));
+                name.clear();
+            }
+            tokens.push(word.to_string());
+        } else {
+            name.push(word);
+        }
+    }
+    if !name.is_empty() {
+        tokens.push(name.join(" "));
+    }
+    tokens
+}
+
+// ================================
+// LICENSE TEXT DETECTION
+// ================================
+
+/// Identify a license from the text of a LICENSE or COPYING file
+pub fn detect_license_text(text: &str) -> Option<String> {
+    let text: String = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let has = |phrase: &str| text.contains(phrase);
+    let or_later = has("or (at your option) any later version");
+
+    let id = if has("gnu affero general public license") {
+        if or_later { "AGPL-3.0-or-later" } else { "AGPL-3.0-only" }
+    } else if has("gnu lesser general public license") {
+        match (has("version 3"), or_later) {
+            (true, true) => "LGPL-3.0-or-later",
+            (true, false) => "LGPL-3.0-only",
+            (false, true) => "LGPL-2.1-or-later",
+            (false, false) => "LGPL-2.1-only",
+        }
+    } else if has("gnu general public license") {
+        match (has("version 3"), or_later) {
+            (true, true) => "GPL-3.0-or-later",
+            (true, false) => "GPL-3.0-only",
+            (false, true) => "GPL-2.0-or-later",
+            (false, false) => "GPL-2.0-only",
+        }
+    } else if has("apache license") && has("version 2.0") {
+        "Apache-2.0"
+    } else if has("mozilla public license version 2.0") || has("mozilla public license, version 2.0") {
+        "MPL-2.0"
+    } else if has("permission is hereby granted, free of charge") {
+        "MIT"
+    } else if has("permission to use, copy, modify, and/or distribute this software") || has("permission to use, copy, modify, and distribute this software for any purpose with or without fee") {
+        "ISC"
+    } else if has("redistribution and use in source and binary forms") {
+        if has("neither the name") || has("names of its contributors") { "BSD-3-Clause" } else { "BSD-2-Clause" }
+    } else if has("this is free and unencumbered software released into the public domain") {
+        "Unlicense"
+    } else if has("boost software license") {
+        "BSL-1.0"
+    } else if has("creative commons legal code") && has("cc0") {
+        "CC0-1.0"
+    } else if has("eclipse public license - v 2.0") {
+        "EPL-2.0"
+    } else if has("server side public license") {
+        "SSPL-1.0"
+    } else if has("this software is provided 'as-is', without any express or implied") {
+        "Zlib"
+    } else {
+        return None;
+    };
+    Some(id.to_string())
+}
+
+// ================================
+// POLICY EVALUATION
+// ================================
+
+/// Licenses in an expression that make it unacceptable, or None when some choice satisfies the policy
+pub fn violations(expression: &str, policy: &LicensePolicy) -> Option<Vec<String>> {
+    let tokens = tokenize(expression);
+    let mut position = 0;
+    let (acceptable, offending) = evaluate_or(&tokens, &mut position, policy);
+    if acceptable {
+        None
+    } else {
+        let mut offending = offending;
+        offending.sort();
+        offending.dedup();
+        Some(offending)
+    }
+}
+
+/// Whether an identifier is acceptable on its own
+pub fn is_allowed(id: &str, policy: &LicensePolicy) -> bool {
+    let matches = |pattern: &String| match pattern.strip_suffix('*') {
+        Some(prefix) => id.starts_with(prefix),
+        None => pattern.eq_ignore_ascii_case(id),
+    };
+    !policy.deny.iter().any(matches) && (policy.allow.is_empty() || policy.allow.iter().any(matches))
+}
+
+// Recursive descent over `OR` (lowest), `AND`, then `WITH` and parentheses
+
+fn evaluate_or(tokens: &[String], position: &mut usize, policy: &LicensePolicy) -> (bool, Vec<String>) {
+    let (mut acceptable, mut offending) = evaluate_and(tokens, position, policy);
+    while tokens.get(*position).is_some_and(|t| t == "OR") {
+        *position += 1;
+        let (right, right_offending) = evaluate_and(tokens, position, policy);
+        acceptable |= right;
+        offending.extend(right_offending);
+    }
+    (acceptable, offending)
+}
+
+fn evaluate_and(tokens: &[String], position: &mut usize, policy: &LicensePolicy) -> (bool, Vec<String>) {
+    let (mut acceptable, mut offending) = evaluate_term(tokens, position, policy);
+    while tokens.get(*position).is_some_and(|t| t == "AND") {
+        *position += 1;
+        let (right, right_offending) = evaluate_term(tokens, position, policy);
+        acceptable &= right;
+        offending.extend(right_offending);
+    }
+    (acceptable, offending)
+}
+
+fn evaluate_term(tokens: &[String], position: &mut usize, policy: &LicensePolicy) -> (bool, Vec<String>) {
+    let Some(token) = tokens.get(*position) else {
+        return (false, Vec::new());
+    };
+    *position += 1;
+    let result = if token == "(" {
+        let inner = evaluate_or(tokens, position, policy);
+        if tokens.get(*position).is_some_and(|t| t == ")") {
+            *position += 1;
+        }
+        inner
+    } else if is_allowed(token, policy) {
+        (true, Vec::new())
+    } else {
+        (false, vec![token.clone()])
+    };
+    // Exceptions only add permissions, so the base license decides
+    if tokens.get(*position).is_some_and(|t| t == "WITH") {
+        *position += 2;
+    }
+    result
+}
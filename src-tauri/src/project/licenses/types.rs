@@ -0,0 +1,109 @@
+// Syntari AI IDE - License Compliance Types
+// Policy, per-package license entries and the compliance report
+
+use serde::{Deserialize, Serialize};
+
+// ================================
+// POLICY
+// ================================
+
+/// Which licenses a project accepts; patterns ending in `*` match by prefix, e.g. `AGPL-*`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LicensePolicy {
+    pub deny: Vec<String>,
+    /// When non-empty, only these licenses are accepted
+    pub allow: Vec<String>,
+    /// Report packages whose license could not be determined
+    pub flag_unknown: bool,
+    /// Package names exempt from the policy, e.g. after a legal review
+    pub ignore_packages: Vec<String>,
+}
+
+impl Default for LicensePolicy {
+    fn default() -> Self {
+        Self {
+            deny: vec!["AGPL-*".to_string(), "SSPL-*".to_string()],
+            allow: Vec::new(),
+            flag_unknown: true,
+            ignore_packages: Vec::new(),
+        }
+    }
+}
+
+// ================================
+// REPORT TYPES
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseSourceKind {
+    /// The package's own manifest, e.g. `license` in package.json or Cargo.toml
+    Manifest,
+    /// Metadata recorded in a lockfile
+    Lockfile,
+    /// Manifest or metadata of an installed or vendored copy
+    Vendored,
+    /// Text of a LICENSE or COPYING file
+    LicenseFile,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseSource {
+    pub kind: LicenseSourceKind,
+    pub path: String,
+    /// License as written in the source
+    pub declared: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseStatus {
+    Allowed,
+    Violation,
+    Unknown,
+    Ignored,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseEntry {
+    pub package: String,
+    pub version: Option<String>,
+    /// Ecosystem of the package, e.g. `crates.io` or `npm`; None for the project itself
+    pub ecosystem: Option<String>,
+    /// SPDX expression, `NOASSERTION` when nothing was found
+    pub license: String,
+    pub sources: Vec<LicenseSource>,
+    pub status: LicenseStatus,
+    /// Why the entry is a violation or unknown
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseReport {
+    pub project_path: String,
+    pub policy: LicensePolicy,
+    /// The project's own license; not counted in `violations` or `unknown`
+    pub project: LicenseEntry,
+    /// Violations first, then by package name
+    pub entries: Vec<LicenseEntry>,
+    pub violations: usize,
+    pub unknown: usize,
+    pub generated_at: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseReportFormat {
+    Json,
+    Csv,
+}
+
+impl LicenseReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LicenseReportFormat::Json => "json",
+            LicenseReportFormat::Csv => "csv",
+        }
+    }
+}
@@ -0,0 +1,786 @@
+// Syntari AI IDE - Lockfile Parsing
+// Resolves exact dependency versions and the graph between them from ecosystem lockfiles
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+use serde_yaml::Value as Yaml;
+use crate::core::{AppError, AppResult};
+use crate::project::cargo;
+use crate::project::dependencies::{parse_go_mod, parse_package_json, parse_pyproject};
+
+// ================================
+// GRAPH TYPES
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockfileKind {
+    Cargo,
+    Npm,
+    Yarn,
+    Pnpm,
+    Poetry,
+    Go,
+}
+
+impl LockfileKind {
+    pub const ALL: [LockfileKind; 6] = [Self::Cargo, Self::Npm, Self::Yarn, Self::Pnpm, Self::Poetry, Self::Go];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.lock",
+            Self::Npm => "package-lock.json",
+            Self::Yarn => "yarn.lock",
+            Self::Pnpm => "pnpm-lock.yaml",
+            Self::Poetry => "poetry.lock",
+            Self::Go => "go.sum",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedPackage {
+    /// `name@version`
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    /// Registry, tarball, git or path the package was resolved from; None for local packages
+    pub source: Option<String>,
+    pub checksum: Option<String>,
+    /// Ids of the packages this one depends on
+    pub dependencies: Vec<String>,
+    /// Ids of the packages depending on this one
+    pub dependents: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub kind: LockfileKind,
+    pub lockfile: String,
+    /// Ids of the project's own packages; every path explained by `why` starts at one of them
+    pub roots: Vec<String>,
+    pub packages: Vec<ResolvedPackage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyPath {
+    pub kind: LockfileKind,
+    /// Package ids from a project package down to the queried package
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatePackage {
+    pub kind: LockfileKind,
+    pub name: String,
+    pub versions: Vec<String>,
+    /// Ids depending on each version
+    pub dependents: BTreeMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    pub fn package(&self, id: &str) -> Option<&ResolvedPackage> {
+        self.packages.iter().find(|package| package.id == id)
+    }
+
+    /// Why `name` (or a `name@version` id) is present: the shortest path from a project package through each of its dependents
+    pub fn why(&self, name: &str) -> Vec<DependencyPath> {
+        let index: HashMap<&str, &ResolvedPackage> = self.packages.iter().map(|p| (p.id.as_str(), p)).collect();
+
+        // Shortest-path tree from the project packages
+        let mut parents: HashMap<&str, Option<&str>> = HashMap::new();
+        let mut queue: VecDeque<&str> = VecDeque::new();
+        for root in &self.roots {
+            if parents.insert(root.as_str(), None).is_none() {
+                queue.push_back(root.as_str());
+            }
+        }
+        while let Some(id) = queue.pop_front() {
+            for dependency in index.get(id).map(|p| p.dependencies.as_slice()).unwrap_or_default() {
+                if !parents.contains_key(dependency.as_str()) {
+                    parents.insert(dependency.as_str(), Some(id));
+                    queue.push_back(dependency.as_str());
+                }
+            }
+        }
+        let chain = |id: &str| {
+            let mut path = vec![id.to_string()];
+            let mut current = parents.get(id).copied().flatten();
+            while let Some(parent) = current {
+                path.push(parent.to_string());
+                current = parents.get(parent).copied().flatten();
+            }
+            path.reverse();
+            path
+        };
+
+        let mut paths: Vec<Vec<String>> = Vec::new();
+        for target in self.packages.iter().filter(|p| p.name == name || p.id == name) {
+            if self.roots.contains(&target.id) {
+                paths.push(vec![target.id.clone()]);
+                continue;
+            }
+            for dependent in target.dependents.iter().filter(|d| parents.contains_key(d.as_str())) {
+                let mut path = chain(dependent);
+                path.push(target.id.clone());
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        paths.into_iter().map(|path| DependencyPath { kind: self.kind, path }).collect()
+    }
+
+    /// Packages resolved at more than one version
+    pub fn duplicates(&self) -> Vec<DuplicatePackage> {
+        let mut by_name: BTreeMap<&str, Vec<&ResolvedPackage>> = BTreeMap::new();
+        for package in &self.packages {
+            by_name.entry(package.name.as_str()).or_default().push(package);
+        }
+        by_name
+            .into_iter()
+            .filter(|(_, packages)| packages.len() > 1)
+            .map(|(name, packages)| DuplicatePackage {
+                kind: self.kind,
+                name: name.to_string(),
+                versions: packages.iter().map(|p| p.version.clone()).collect(),
+                dependents: packages.iter().map(|p| (p.version.clone(), p.dependents.clone())).collect(),
+            })
+            .collect()
+    }
+}
+
+/// Collects packages and edges, then derives dependents
+#[derive(Default)]
+struct GraphBuilder {
+    packages: BTreeMap<String, ResolvedPackage>,
+    roots: Vec<String>,
+}
+
+impl GraphBuilder {
+    fn add(&mut self, name: &str, version: &str, source: Option<String>, checksum: Option<String>) -> String {
+        let id = format!("{}@{}", name, version);
+        let package = self.packages.entry(id.clone()).or_insert_with(|| ResolvedPackage {
+            id: id.clone(),
+            name: name.to_string(),
+            version: version.to_string(),
+            source: None,
+            checksum: None,
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        });
+        package.source = package.source.take().or(source);
+        package.checksum = package.checksum.take().or(checksum);
+        id
+    }
+
+    fn root(&mut self, name: &str, version: &str) -> String {
+        let id = self.add(name, version, None, None);
+        if !self.roots.contains(&id) {
+            self.roots.push(id.clone());
+        }
+        id
+    }
+
+    fn edge(&mut self, from: &str, to: &str) {
+        if from == to {
+            return;
+        }
+        if let Some(package) = self.packages.get_mut(from) {
+            if !package.dependencies.iter().any(|d| d == to) {
+                package.dependencies.push(to.to_string());
+            }
+        }
+    }
+
+    fn build(mut self, kind: LockfileKind, lockfile: &Path) -> DependencyGraph {
+        let edges: Vec<(String, String)> = self
+            .packages
+            .values()
+            .flat_map(|p| p.dependencies.iter().map(move |d| (p.id.clone(), d.clone())))
+            .collect();
+        for (from, to) in edges {
+            if let Some(package) = self.packages.get_mut(&to) {
+                package.dependents.push(from);
+            }
+        }
+        // Edges to packages the lockfile never defines would dangle
+        let known: Vec<String> = self.packages.keys().cloned().collect();
+        for package in self.packages.values_mut() {
+            package.dependencies.retain(|d| known.binary_search(d).is_ok());
+            package.dependencies.sort();
+            package.dependents.sort();
+        }
+        DependencyGraph {
+            kind,
+            lockfile: lockfile.to_string_lossy().to_string(),
+            roots: self.roots,
+            packages: self.packages.into_values().collect(),
+        }
+    }
+}
+
+// ================================
+// LOADING
+// ================================
+
+/// Parse every lockfile of the project and its sub-projects; unreadable lockfiles are logged and skipped
+pub fn load_graphs(project_path: &Path, sub_projects: &[String]) -> Vec<DependencyGraph> {
+    let directories = std::iter::once(project_path).chain(sub_projects.iter().map(Path::new));
+    // Workspace members resolve to the same shared lockfile
+    let mut seen = std::collections::HashSet::new();
+    let mut graphs = Vec::new();
+    for dir in directories {
+        for kind in LockfileKind::ALL {
+            let Some(path) = lockfile_path(dir, kind) else {
+                continue;
+            };
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            match load_graph(&path, kind) {
+                Ok(graph) => graphs.push(graph),
+                Err(e) => e.log_warn(),
+            }
+        }
+    }
+    graphs
+}
+
+/// Cargo members share the workspace's lockfile, which may sit in an ancestor directory
+fn lockfile_path(project_path: &Path, kind: LockfileKind) -> Option<std::path::PathBuf> {
+    let path = project_path.join(kind.file_name());
+    if path.is_file() {
+        return Some(path);
+    }
+    if kind == LockfileKind::Cargo && project_path.join(cargo::MANIFEST_FILE).is_file() {
+        let workspace = cargo::load_workspace(project_path).ok()?;
+        let path = Path::new(&workspace.root).join(kind.file_name());
+        return path.is_file().then_some(path);
+    }
+    None
+}
+
+pub fn load_graph(path: &Path, kind: LockfileKind) -> AppResult<DependencyGraph> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AppError::filesystem_with_path(
+            "LOCKFILE_READ_FAILED".to_string(),
+            format!("Cannot read lockfile: {}", e),
+            path.to_string_lossy().to_string(),
+        )
+    })?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let invalid = |message: String| AppError::project_with_path("LOCKFILE_PARSE_FAILED".to_string(), message, path.to_string_lossy().to_string());
+
+    let builder = match kind {
+        LockfileKind::Cargo => parse_cargo_lock(&content).map_err(invalid)?,
+        LockfileKind::Npm => parse_package_lock(&content, dir).map_err(invalid)?,
+        LockfileKind::Yarn => parse_yarn_lock(&content, dir).map_err(invalid)?,
+        LockfileKind::Pnpm => parse_pnpm_lock(&content, dir).map_err(invalid)?,
+        LockfileKind::Poetry => parse_poetry_lock(&content, dir).map_err(invalid)?,
+        LockfileKind::Go => parse_go_sum(&content, dir),
+    };
+    Ok(builder.build(kind, path))
+}
+
+/// Name and version from the project's package.json, for lockfiles that do not record the root
+fn local_package(dir: &Path) -> (String, String) {
+    let manifest = std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Json>(&content).ok());
+    let field = |key: &str| manifest.as_ref().and_then(|m| m.get(key)).and_then(|v| v.as_str()).map(str::to_string);
+    let fallback = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "root".to_string());
+    (field("name").unwrap_or(fallback), field("version").unwrap_or_else(|| "0.0.0".to_string()))
+}
+
+/// `@scope/name@range` → (`@scope/name`, `range`)
+fn split_spec(spec: &str) -> Option<(&str, &str)> {
+    let at = spec.get(1..)?.find('@')? + 1;
+    Some((&spec[..at], &spec[at + 1..]))
+}
+
+// ================================
+// CARGO
+// ================================
+
+fn parse_cargo_lock(content: &str) -> Result<GraphBuilder, String> {
+    let lock: toml::Table = content.parse().map_err(|e: toml::de::Error| format!("Invalid Cargo.lock: {}", e.message()))?;
+    let packages = lock.get("package").and_then(|p| p.as_array()).cloned().unwrap_or_default();
+    let mut builder = GraphBuilder::default();
+
+    let mut versions: HashMap<String, Vec<String>> = HashMap::new();
+    for package in &packages {
+        let (Some(name), Some(version)) = (package.get("name").and_then(|v| v.as_str()), package.get("version").and_then(|v| v.as_str())) else {
+            continue;
+        };
+        let source = package.get("source").and_then(|v| v.as_str()).map(str::to_string);
+        let checksum = package.get("checksum").and_then(|v| v.as_str()).map(str::to_string);
+        let id = if source.is_none() { builder.root(name, version) } else { builder.add(name, version, source, checksum) };
+        versions.entry(name.to_string()).or_default().push(id);
+    }
+
+    // Entries are `name`, or `name version` and optionally `(source)` when the name is ambiguous
+    for package in &packages {
+        let (Some(name), Some(version)) = (package.get("name").and_then(|v| v.as_str()), package.get("version").and_then(|v| v.as_str())) else {
+            continue;
+        };
+        let from = format!("{}@{}", name, version);
+        for entry in package.get("dependencies").and_then(|d| d.as_array()).into_iter().flatten().filter_map(|d| d.as_str()) {
+            let mut parts = entry.split_whitespace();
+            let Some(dep_name) = parts.next() else {
+                continue;
+            };
+            let to = match parts.next() {
+                Some(dep_version) => Some(format!("{}@{}", dep_name, dep_version)),
+                None => versions.get(dep_name).and_then(|ids| ids.first().cloned()),
+            };
+            if let Some(to) = to {
+                builder.edge(&from, &to);
+            }
+        }
+    }
+    Ok(builder)
+}
+
+// ================================
+// NPM
+// ================================
+
+fn parse_package_lock(content: &str, dir: &Path) -> Result<GraphBuilder, String> {
+    let lock: Json = serde_json::from_str(content).map_err(|e| format!("Invalid package-lock.json: {}", e))?;
+    let entries = match lock.get("packages").and_then(|p| p.as_object()) {
+        Some(packages) => packages.clone(),
+        None => flatten_lock_v1(&lock, dir),
+    };
+
+    let name_of = |path: &str, entry: &Json| -> String {
+        entry
+            .get("name")
+            .and_then(|n| n.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| path.rsplit("node_modules/").next().unwrap_or(path).to_string())
+    };
+    let mut builder = GraphBuilder::default();
+    let mut ids: HashMap<String, String> = HashMap::new();
+
+    for (path, entry) in &entries {
+        if entry.get("link").and_then(|l| l.as_bool()) == Some(true) {
+            continue;
+        }
+        let name = if path.is_empty() { local_package(dir).0 } else { name_of(path, entry) };
+        let version = entry.get("version").and_then(|v| v.as_str()).unwrap_or("0.0.0");
+        // The root and workspace members live outside node_modules
+        let id = if path.is_empty() || !path.contains("node_modules/") {
+            builder.root(entry.get("name").and_then(|n| n.as_str()).unwrap_or(&name), version)
+        } else {
+            let source = entry.get("resolved").and_then(|v| v.as_str()).map(str::to_string);
+            let checksum = entry.get("integrity").and_then(|v| v.as_str()).map(str::to_string);
+            builder.add(&name, version, source, checksum)
+        };
+        ids.insert(path.clone(), id);
+    }
+    // Links point at the workspace directory holding the real entry
+    for (path, entry) in &entries {
+        if let Some(target) = entry.get("resolved").and_then(|r| r.as_str()).filter(|_| entry.get("link").and_then(|l| l.as_bool()) == Some(true)) {
+            if let Some(id) = ids.get(target).cloned() {
+                ids.insert(path.clone(), id);
+            }
+        }
+    }
+
+    for (path, entry) in &entries {
+        let Some(from) = ids.get(path).cloned() else {
+            continue;
+        };
+        for section in ["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"] {
+            for name in entry.get(section).and_then(|d| d.as_object()).into_iter().flat_map(|d| d.keys()) {
+                if let Some(to) = resolve_node_module(path, name).iter().find_map(|candidate| ids.get(candidate)) {
+                    builder.edge(&from, to);
+                }
+            }
+        }
+    }
+    Ok(builder)
+}
+
+/// Directories Node searches for `name` from a package at `path`, nearest first
+fn resolve_node_module(path: &str, name: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut base = path.to_string();
+    loop {
+        let prefix = if base.is_empty() { String::new() } else { format!("{}/", base) };
+        candidates.push(format!("{}node_modules/{}", prefix, name));
+        match base.rfind("/node_modules/") {
+            Some(index) => base.truncate(index),
+            None if base.is_empty() => break,
+            None => base.clear(),
+        }
+    }
+    candidates
+}
+
+/// Lockfile v1 nests `dependencies`; rewrite it into the v2 path-keyed shape
+fn flatten_lock_v1(lock: &Json, dir: &Path) -> serde_json::Map<String, Json> {
+    fn walk(prefix: &str, dependencies: &serde_json::Map<String, Json>, out: &mut serde_json::Map<String, Json>) {
+        for (name, entry) in dependencies {
+            let path = format!("{}node_modules/{}", prefix, name);
+            let mut flat = serde_json::json!({
+                "version": entry.get("version"),
+                "resolved": entry.get("resolved"),
+                "integrity": entry.get("integrity"),
+            });
+            if let Some(requires) = entry.get("requires") {
+                flat["dependencies"] = requires.clone();
+            }
+            out.insert(path.clone(), flat);
+            if let Some(nested) = entry.get("dependencies").and_then(|d| d.as_object()) {
+                walk(&format!("{}/", path), nested, out);
+            }
+        }
+    }
+
+    let mut out = serde_json::Map::new();
+    let top = lock.get("dependencies").and_then(|d| d.as_object()).cloned().unwrap_or_default();
+    let root_dependencies: serde_json::Map<String, Json> = std::fs::read_to_string(dir.join("package.json"))
+        .map(|content| parse_package_json(&content).into_iter().map(|dep| (dep.name, Json::Null)).collect())
+        .unwrap_or_else(|_| top.keys().map(|name| (name.clone(), Json::Null)).collect());
+    out.insert(String::new(), serde_json::json!({
+        "name": lock.get("name"),
+        "version": lock.get("version"),
+        "dependencies": root_dependencies,
+    }));
+    walk("", &top, &mut out);
+    out
+}
+
+// ================================
+// YARN
+// ================================
+
+struct YarnEntry {
+    specs: Vec<String>,
+    version: String,
+    source: Option<String>,
+    checksum: Option<String>,
+    dependencies: Vec<(String, String)>,
+}
+
+fn parse_yarn_lock(content: &str, dir: &Path) -> Result<GraphBuilder, String> {
+    // Berry lockfiles are YAML with a `__metadata` entry; classic ones use their own format
+    let berry = content.lines().any(|line| line.starts_with("__metadata:"));
+    let entries = if berry { yarn_berry_entries(content)? } else { yarn_classic_entries(content) };
+
+    let mut builder = GraphBuilder::default();
+    let mut by_spec: HashMap<String, String> = HashMap::new();
+    for entry in &entries {
+        let Some((name, _)) = entry.specs.first().and_then(|spec| split_spec(spec)) else {
+            continue;
+        };
+        let id = if entry.source.as_deref().is_some_and(|s| s.starts_with("workspace:")) {
+            builder.root(name, &entry.version)
+        } else {
+            builder.add(name, &entry.version, entry.source.clone(), entry.checksum.clone())
+        };
+        for spec in &entry.specs {
+            by_spec.insert(spec.clone(), id.clone());
+        }
+    }
+    let resolve = |name: &str, range: &str| {
+        by_spec
+            .get(&format!("{}@{}", name, range))
+            .or_else(|| by_spec.get(&format!("{}@npm:{}", name, range)))
+            .cloned()
+    };
+
+    for entry in &entries {
+        let Some(from) = entry.specs.first().and_then(|spec| by_spec.get(spec)).cloned() else {
+            continue;
+        };
+        for (name, range) in &entry.dependencies {
+            if let Some(to) = resolve(name, range) {
+                builder.edge(&from, &to);
+            }
+        }
+    }
+
+    // Classic lockfiles do not record the project itself
+    if builder.roots.is_empty() {
+        let (name, version) = local_package(dir);
+        let root = builder.root(&name, &version);
+        let manifest = std::fs::read_to_string(dir.join("package.json")).unwrap_or_default();
+        for dep in parse_package_json(&manifest) {
+            if let Some(to) = dep.version_req.as_deref().and_then(|range| resolve(&dep.name, range)) {
+                builder.edge(&root, &to);
+            }
+        }
+    }
+    Ok(builder)
+}
+
+fn yarn_classic_entries(content: &str) -> Vec<YarnEntry> {
+    let unquote = |s: &str| s.trim().trim_matches('"').to_string();
+    let mut entries: Vec<YarnEntry> = Vec::new();
+    let mut in_dependencies = false;
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let line = line.trim();
+        if indent == 0 {
+            let specs = line.trim_end_matches(':').split(", ").map(unquote).collect();
+            entries.push(YarnEntry { specs, version: String::new(), source: None, checksum: None, dependencies: Vec::new() });
+            in_dependencies = false;
+            continue;
+        }
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+        if indent == 2 {
+            in_dependencies = line == "dependencies:" || line == "optionalDependencies:";
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "version" => entry.version = unquote(value),
+                "resolved" => entry.source = Some(unquote(value)),
+                "integrity" => entry.checksum = Some(unquote(value)),
+                _ => {}
+            }
+        } else if in_dependencies {
+            // Scoped names are quoted, so split after the closing quote when there is one
+            let split = match line.strip_prefix('"') {
+                Some(quoted) => quoted.find('"').map(|i| i + 2),
+                None => line.find(' '),
+            };
+            if let Some(index) = split {
+                entry.dependencies.push((unquote(&line[..index]), unquote(&line[index..])));
+            }
+        }
+    }
+    entries
+}
+
+fn yarn_berry_entries(content: &str) -> Result<Vec<YarnEntry>, String> {
+    let lock: Yaml = serde_yaml::from_str(content).map_err(|e| format!("Invalid yarn.lock: {}", e))?;
+    let mut entries = Vec::new();
+    for (key, value) in lock.as_mapping().into_iter().flatten() {
+        let Some(key) = key.as_str().filter(|k| *k != "__metadata") else {
+            continue;
+        };
+        let field = |name: &str| value.get(name).and_then(yaml_string);
+        // `resolution` is `name@protocol:reference`; the source is the part after the name
+        let source = field("resolution").and_then(|r| split_spec(&r).map(|(_, s)| s.to_string()));
+        let dependencies = ["dependencies", "optionalDependencies"]
+            .iter()
+            .filter_map(|section| value.get(*section).and_then(|d| d.as_mapping()))
+            .flatten()
+            .filter_map(|(name, range)| Some((name.as_str()?.to_string(), yaml_string(range)?)))
+            .collect();
+        entries.push(YarnEntry {
+            specs: key.split(", ").map(|s| s.trim().to_string()).collect(),
+            version: field("version").unwrap_or_default(),
+            source,
+            checksum: field("checksum"),
+            dependencies,
+        });
+    }
+    Ok(entries)
+}
+
+fn yaml_string(value: &Yaml) -> Option<String> {
+    match value {
+        Yaml::String(s) => Some(s.clone()),
+        Yaml::Number(n) => Some(n.to_string()),
+        Yaml::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// ================================
+// PNPM
+// ================================
+
+fn parse_pnpm_lock(content: &str, dir: &Path) -> Result<GraphBuilder, String> {
+    let lock: Yaml = serde_yaml::from_str(content).map_err(|e| format!("Invalid pnpm-lock.yaml: {}", e))?;
+    let lockfile_version = lock
+        .get("lockfileVersion")
+        .and_then(yaml_string)
+        .and_then(|v| v.split('.').next().and_then(|major| major.parse::<u32>().ok()))
+        .unwrap_or(9);
+    // Before v6 keys look like `/name/1.0.0_peer@1.0.0`
+    let legacy = lockfile_version < 6;
+    let version_of = |reference: &str| -> String {
+        let cut = if legacy { reference.find('_') } else { reference.find('(') };
+        reference[..cut.unwrap_or(reference.len())].to_string()
+    };
+    let parse_key = |key: &str| -> Option<(String, String)> {
+        let key = key.trim_start_matches('/');
+        if legacy {
+            let segments = if key.starts_with('@') { 2 } else { 1 };
+            let split = key.match_indices('/').nth(segments - 1)?.0;
+            return Some((key[..split].to_string(), version_of(&key[split + 1..])));
+        }
+        let (name, version) = split_spec(key)?;
+        Some((name.to_string(), version_of(version)))
+    };
+
+    let mut builder = GraphBuilder::default();
+    let packages = lock.get("packages").and_then(|p| p.as_mapping()).cloned().unwrap_or_default();
+    for (key, value) in &packages {
+        let Some((name, version)) = key.as_str().and_then(parse_key) else {
+            continue;
+        };
+        let resolution = value.get("resolution");
+        let source = resolution.and_then(|r| r.get("tarball").or_else(|| r.get("repo"))).and_then(yaml_string);
+        let checksum = resolution.and_then(|r| r.get("integrity")).and_then(yaml_string);
+        builder.add(&name, &version, source, checksum);
+    }
+
+    // A dependency reference is a version (with peer suffix), a full key, or a `link:` to a workspace package
+    let resolve = |name: &str, reference: &str| -> Option<String> {
+        if reference.starts_with("link:") {
+            return None;
+        }
+        // Aliases reference another package by key, e.g. `string-width@4.2.3`
+        let base = &reference[..reference.find('(').unwrap_or(reference.len())];
+        if reference.starts_with('/') || (!legacy && split_spec(base).is_some()) {
+            return parse_key(reference).map(|(n, v)| format!("{}@{}", n, v));
+        }
+        Some(format!("{}@{}", name, version_of(reference)))
+    };
+    let edges = |builder: &mut GraphBuilder, from: &str, value: &Yaml| {
+        for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+            for (name, reference) in value.get(section).and_then(|d| d.as_mapping()).into_iter().flatten() {
+                // Importers in v6+ record `{ specifier, version }`
+                let reference = reference.get("version").and_then(yaml_string).or_else(|| yaml_string(reference));
+                if let (Some(name), Some(reference)) = (name.as_str(), reference) {
+                    if let Some(to) = resolve(name, &reference) {
+                        builder.edge(from, &to);
+                    }
+                }
+            }
+        }
+    };
+
+    // v9 moves per-install dependency lists from `packages` into `snapshots`
+    let snapshots = lock.get("snapshots").and_then(|s| s.as_mapping()).cloned().unwrap_or(packages);
+    for (key, value) in &snapshots {
+        if let Some((name, version)) = key.as_str().and_then(parse_key) {
+            edges(&mut builder, &format!("{}@{}", name, version), value);
+        }
+    }
+
+    let importers: Vec<(String, Yaml)> = match lock.get("importers").and_then(|i| i.as_mapping()) {
+        Some(importers) => importers.iter().filter_map(|(path, value)| Some((path.as_str()?.to_string(), value.clone()))).collect(),
+        None => vec![(".".to_string(), lock.clone())],
+    };
+    for (path, value) in importers {
+        let (name, version) = local_package(&dir.join(&path));
+        let root = builder.root(&name, &version);
+        edges(&mut builder, &root, &value);
+    }
+    Ok(builder)
+}
+
+// ================================
+// POETRY
+// ================================
+
+/// PEP 503 normalized name, so `Flask_Login` matches `flask-login`
+fn normalize_python_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+fn parse_poetry_lock(content: &str, dir: &Path) -> Result<GraphBuilder, String> {
+    let lock: toml::Table = content.parse().map_err(|e: toml::de::Error| format!("Invalid poetry.lock: {}", e.message()))?;
+    let packages = lock.get("package").and_then(|p| p.as_array()).cloned().unwrap_or_default();
+    // Lockfiles before 2.0 keep hashes under `[metadata.files]`
+    let legacy_files = lock.get("metadata").and_then(|m| m.get("files")).and_then(|f| f.as_table());
+    let first_hash = |files: Option<&toml::Value>| {
+        files?.as_array()?.first()?.get("hash")?.as_str().map(str::to_string)
+    };
+
+    let mut builder = GraphBuilder::default();
+    let mut by_name: HashMap<String, String> = HashMap::new();
+    for package in &packages {
+        let (Some(name), Some(version)) = (package.get("name").and_then(|v| v.as_str()), package.get("version").and_then(|v| v.as_str())) else {
+            continue;
+        };
+        let source = package.get("source").and_then(|s| s.get("url")).and_then(|u| u.as_str()).map(str::to_string);
+        let checksum = first_hash(package.get("files")).or_else(|| first_hash(legacy_files.and_then(|f| f.get(name))));
+        let id = builder.add(name, version, source, checksum);
+        by_name.insert(normalize_python_name(name), id);
+    }
+    for package in &packages {
+        let Some(from) = package.get("name").and_then(|v| v.as_str()).and_then(|n| by_name.get(&normalize_python_name(n))).cloned() else {
+            continue;
+        };
+        for name in package.get("dependencies").and_then(|d| d.as_table()).into_iter().flat_map(|d| d.keys()) {
+            if let Some(to) = by_name.get(&normalize_python_name(name)) {
+                builder.edge(&from, to);
+            }
+        }
+    }
+
+    let manifest = std::fs::read_to_string(dir.join("pyproject.toml")).unwrap_or_default();
+    let project = manifest.parse::<toml::Table>().ok();
+    let project_field = |key: &str| {
+        let project = project.as_ref()?;
+        project
+            .get("project")
+            .and_then(|p| p.get(key))
+            .or_else(|| project.get("tool").and_then(|t| t.get("poetry")).and_then(|p| p.get(key)))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    let root_name = project_field("name").unwrap_or_else(|| local_package(dir).0);
+    let root = builder.root(&root_name, &project_field("version").unwrap_or_else(|| "0.0.0".to_string()));
+    for dep in parse_pyproject(&manifest) {
+        if let Some(to) = by_name.get(&normalize_python_name(&dep.name)) {
+            builder.edge(&root, to);
+        }
+    }
+    Ok(builder)
+}
+
+// ================================
+// GO
+// ================================
+
+/// go.sum holds checksums only; edges come from the main module's go.mod requirements
+fn parse_go_sum(content: &str, dir: &Path) -> GraphBuilder {
+    let mut builder = GraphBuilder::default();
+    let mut module_hashes: HashMap<(String, String), String> = HashMap::new();
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(module), Some(version), Some(hash)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        // `/go.mod` lines only pin a module's manifest; the module itself was not downloaded
+        if !version.ends_with("/go.mod") {
+            module_hashes.insert((module.to_string(), version.to_string()), hash.to_string());
+        }
+    }
+    for ((module, version), hash) in &module_hashes {
+        builder.add(module, version, None, Some(hash.clone()));
+    }
+
+    let go_mod = std::fs::read_to_string(dir.join("go.mod")).unwrap_or_default();
+    let module = go_mod
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))
+        .map(|m| m.trim().trim_matches('"').to_string())
+        .unwrap_or_else(|| local_package(dir).0);
+    // Go reports the main module's version as `(devel)`
+    let root = builder.root(&module, "(devel)");
+    for dep in parse_go_mod(&go_mod) {
+        if let Some(version) = dep.version_req {
+            let id = builder.add(&dep.name, &version, None, None);
+            builder.edge(&root, &id);
+        }
+    }
+    builder
+}
@@ -4,8 +4,23 @@
 pub mod types;
 pub mod service;
 pub mod analyzer;
+pub mod audit;
+pub mod cargo;
+pub mod dependencies;
+pub mod diagnostics;
+pub mod lockfile;
+#[cfg(feature = "compliance")]
+pub mod licenses;
+pub mod monorepo;
+pub mod tasks;
+pub mod testing;
+pub mod workspace;
 pub mod commands;
 
 // Re-export commonly used types
 pub use types::*;
-pub use service::ProjectService; 
\ No newline at end of file
+pub use service::ProjectService;
+pub use diagnostics::DiagnosticsManager;
+pub use tasks::TaskRunner;
+pub use testing::TestExplorer;
+pub use workspace::WorkspaceStore; 
\ No newline at end of file
@@ -0,0 +1,378 @@
+// Syntari AI IDE - Monorepo Detection
+// Finds sub-projects and workspace declarations below a project root
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use ignore::WalkBuilder;
+use crate::project::analyzer::SKIPPED_DIRECTORIES;
+use crate::project::cargo;
+use crate::project::commands::{detect_framework, detect_project_type};
+use crate::project::dependencies::detect_dependencies;
+use crate::project::types::{ProjectType, SubProject, WorkspaceKind};
+
+/// How far below the root manifests are searched for; declared workspace members may be deeper
+const MAX_DEPTH: usize = 6;
+
+const MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "go.work",
+    "pyproject.toml",
+    "setup.py",
+    "requirements.txt",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "composer.json",
+    "Gemfile",
+];
+
+/// Members declared by a workspace manifest in one directory
+struct WorkspaceDeclaration {
+    kind: WorkspaceKind,
+    members: Vec<PathBuf>,
+}
+
+/// Detect every sub-project under `root` and nest them by directory
+pub async fn detect_project_tree(root: &Path) -> SubProject {
+    let mut manifest_dirs = find_manifest_dirs(root);
+
+    // Declared members count even when they sit deeper than the search or in ignored directories
+    let mut declarations: BTreeMap<PathBuf, Vec<WorkspaceDeclaration>> = BTreeMap::new();
+    for dir in manifest_dirs.keys().cloned().collect::<Vec<_>>() {
+        let found = workspace_declarations(&dir);
+        for member in found.iter().flat_map(|declaration| &declaration.members) {
+            if !manifest_dirs.contains_key(member) {
+                manifest_dirs.insert(member.clone(), manifests_in(member));
+            }
+        }
+        if !found.is_empty() {
+            declarations.insert(dir, found);
+        }
+    }
+    manifest_dirs.entry(root.to_path_buf()).or_insert_with(|| manifests_in(root));
+
+    let mut nodes: BTreeMap<PathBuf, SubProject> = BTreeMap::new();
+    for (dir, manifests) in manifest_dirs {
+        let workspace_root = declarations
+            .iter()
+            .find(|(workspace_dir, found)| **workspace_dir != dir && found.iter().any(|d| d.members.contains(&dir)))
+            .map(|(workspace_dir, _)| relative(root, workspace_dir));
+        let mut node = describe(root, &dir, manifests).await;
+        node.workspace = declarations.get(&dir).and_then(|found| found.first()).map(|declaration| declaration.kind);
+        node.workspace_root = workspace_root;
+        nodes.insert(dir, node);
+    }
+
+    // Attach deepest directories first so each node is complete before it moves into its parent
+    let mut dirs: Vec<PathBuf> = nodes.keys().filter(|dir| dir.as_path() != root).cloned().collect();
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+    for dir in dirs {
+        let Some(node) = nodes.remove(&dir) else {
+            continue;
+        };
+        let parent = dir
+            .ancestors()
+            .skip(1)
+            .find(|ancestor| nodes.contains_key(*ancestor))
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| root.to_path_buf());
+        if let Some(parent) = nodes.get_mut(&parent) {
+            parent.children.push(node);
+        }
+    }
+
+    let mut tree = nodes.remove(root).expect("root node is always present");
+    sort_children(&mut tree);
+    if tree.project_type == ProjectType::Unknown.to_string() && !tree.children.is_empty() {
+        tree.project_type = "monorepo".to_string();
+    }
+    tree
+}
+
+fn sort_children(node: &mut SubProject) {
+    node.children.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    for child in &mut node.children {
+        sort_children(child);
+    }
+}
+
+fn find_manifest_dirs(root: &Path) -> BTreeMap<PathBuf, Vec<String>> {
+    let mut dirs: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let walker = WalkBuilder::new(root)
+        .require_git(false)
+        .max_depth(Some(MAX_DEPTH))
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_some_and(|t| t.is_dir()) && SKIPPED_DIRECTORIES.contains(&name.as_ref()))
+        })
+        .build();
+
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_manifest(&name) {
+            if let Some(dir) = entry.path().parent() {
+                dirs.entry(dir.to_path_buf()).or_default().push(name);
+            }
+        }
+    }
+    for manifests in dirs.values_mut() {
+        manifests.sort();
+    }
+    dirs
+}
+
+fn is_manifest(name: &str) -> bool {
+    MANIFESTS.contains(&name) || name.ends_with(".csproj")
+}
+
+fn manifests_in(dir: &Path) -> Vec<String> {
+    let mut manifests: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| is_manifest(name))
+                .collect()
+        })
+        .unwrap_or_default();
+    manifests.sort();
+    manifests
+}
+
+async fn describe(root: &Path, dir: &Path, manifests: Vec<String>) -> SubProject {
+    let mut project_type = detect_project_type(dir).await;
+    if project_type == ProjectType::Unknown {
+        project_type = match manifests.iter().map(String::as_str).find(|m| *m == "go.work" || *m == "setup.py") {
+            Some("go.work") => ProjectType::Go,
+            Some(_) => ProjectType::Python,
+            None => ProjectType::Unknown,
+        };
+    }
+    let framework = detect_framework(dir).await;
+    let dependencies = detect_dependencies(dir, &project_type).await;
+
+    SubProject {
+        name: manifest_name(dir).unwrap_or_else(|| {
+            dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| dir.to_string_lossy().to_string())
+        }),
+        root_path: dir.to_string_lossy().to_string(),
+        relative_path: relative(root, dir),
+        project_type: project_type.to_string().to_string(),
+        framework: framework.map(|f| f.to_string().to_string()),
+        dependencies,
+        manifests,
+        workspace: None,
+        workspace_root: None,
+        children: Vec::new(),
+    }
+}
+
+fn relative(root: &Path, dir: &Path) -> String {
+    match dir.strip_prefix(root) {
+        Ok(path) if path.as_os_str().is_empty() => ".".to_string(),
+        Ok(path) => path.to_string_lossy().to_string(),
+        Err(_) => dir.to_string_lossy().to_string(),
+    }
+}
+
+/// The name a manifest gives the project, if any
+fn manifest_name(dir: &Path) -> Option<String> {
+    if let Some(package) = read_json(&dir.join("package.json")) {
+        if let Some(name) = package.get("name").and_then(|n| n.as_str()) {
+            return Some(name.to_string());
+        }
+    }
+    if let Some(manifest) = read_toml(&dir.join("Cargo.toml")) {
+        if let Some(name) = manifest.get("package").and_then(|p| p.get("name")).and_then(|n| n.as_str()) {
+            return Some(name.to_string());
+        }
+    }
+    if let Some(manifest) = read_toml(&dir.join("pyproject.toml")) {
+        let name = manifest
+            .get("project")
+            .and_then(|p| p.get("name"))
+            .or_else(|| manifest.get("tool").and_then(|t| t.get("poetry")).and_then(|p| p.get("name")))
+            .and_then(|n| n.as_str());
+        if let Some(name) = name {
+            return Some(name.to_string());
+        }
+    }
+    let go_mod = std::fs::read_to_string(dir.join("go.mod")).ok()?;
+    go_mod
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))
+        .map(|module| module.trim().trim_matches('"').to_string())
+}
+
+// ================================
+// WORKSPACE DECLARATIONS
+// ================================
+
+/// Workspaces declared in `dir`, most specific tool first; one directory may host several ecosystems
+fn workspace_declarations(dir: &Path) -> Vec<WorkspaceDeclaration> {
+    let mut declarations = Vec::new();
+
+    // pnpm keeps its list outside package.json, so it wins over the `workspaces` field
+    if let Ok(content) = std::fs::read_to_string(dir.join("pnpm-workspace.yaml")) {
+        declarations.push(WorkspaceDeclaration {
+            kind: WorkspaceKind::Pnpm,
+            members: expand_patterns(dir, &pnpm_packages(&content)),
+        });
+    } else if let Some(package) = read_json(&dir.join("package.json")) {
+        let patterns = match package.get("workspaces") {
+            Some(serde_json::Value::Array(items)) => string_items(items),
+            Some(serde_json::Value::Object(object)) => object
+                .get("packages")
+                .and_then(|p| p.as_array())
+                .map(|items| string_items(items))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        if !patterns.is_empty() {
+            let kind = if dir.join("yarn.lock").exists() { WorkspaceKind::Yarn } else { WorkspaceKind::Npm };
+            declarations.push(WorkspaceDeclaration { kind, members: expand_patterns(dir, &patterns) });
+        }
+    }
+
+    if read_toml(&dir.join(cargo::MANIFEST_FILE)).is_some_and(|manifest| manifest.contains_key("workspace")) {
+        let members = match cargo::load_workspace(dir) {
+            Ok(workspace) => workspace.packages.into_iter().map(|p| PathBuf::from(p.root)).filter(|p| p != dir).collect(),
+            Err(e) => {
+                e.log_warn();
+                Vec::new()
+            }
+        };
+        declarations.push(WorkspaceDeclaration { kind: WorkspaceKind::Cargo, members });
+    }
+
+    if let Ok(content) = std::fs::read_to_string(dir.join("go.work")) {
+        let members = go_work_uses(&content)
+            .into_iter()
+            .map(|path| normalize(&dir.join(path)))
+            .filter(|path| path != dir && path.is_dir())
+            .collect();
+        declarations.push(WorkspaceDeclaration { kind: WorkspaceKind::Go, members });
+    }
+
+    let uv_members = read_toml(&dir.join("pyproject.toml")).and_then(|manifest| {
+        let workspace = manifest.get("tool")?.get("uv")?.get("workspace")?;
+        let members: Vec<String> = workspace
+            .get("members")?
+            .as_array()?
+            .iter()
+            .filter_map(|m| m.as_str().map(str::to_string))
+            .collect();
+        let excluded: Vec<String> = workspace
+            .get("exclude")
+            .and_then(|e| e.as_array())
+            .map(|items| items.iter().filter_map(|m| m.as_str().map(|s| format!("!{}", s))).collect())
+            .unwrap_or_default();
+        Some(members.into_iter().chain(excluded).collect::<Vec<_>>())
+    });
+    if let Some(patterns) = uv_members {
+        declarations.push(WorkspaceDeclaration { kind: WorkspaceKind::Uv, members: expand_patterns(dir, &patterns) });
+    }
+    declarations
+}
+
+/// Expand member globs relative to `dir`; patterns starting with `!` exclude
+fn expand_patterns(dir: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let (exclusions, inclusions): (Vec<&String>, Vec<&String>) = patterns.iter().partition(|p| p.starts_with('!'));
+    let excluded: Vec<glob::Pattern> = exclusions
+        .iter()
+        .filter_map(|p| glob::Pattern::new(&dir.join(p.trim_start_matches('!')).to_string_lossy()).ok())
+        .collect();
+
+    let mut members = Vec::new();
+    for pattern in inclusions {
+        let full = dir.join(pattern.trim_end_matches('/'));
+        let Ok(paths) = glob::glob(&full.to_string_lossy()) else {
+            tracing::warn!("Invalid workspace pattern '{}' in {}", pattern, dir.display());
+            continue;
+        };
+        for path in paths.flatten().map(|p| normalize(&p)) {
+            if path.is_dir() && path != dir && !excluded.iter().any(|e| e.matches_path(&path)) && !members.contains(&path) {
+                members.push(path);
+            }
+        }
+    }
+    members.sort();
+    members
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// `packages:` entries of a pnpm-workspace.yaml
+fn pnpm_packages(content: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(' ') && !line.starts_with('-') {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if in_packages {
+            if let Some(item) = trimmed.strip_prefix('-') {
+                packages.push(item.trim().trim_matches(|c| c == '\'' || c == '"').to_string());
+            }
+        }
+    }
+    packages
+}
+
+/// Directories named by `use` directives in a go.work file
+fn go_work_uses(content: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                uses.push(line.trim_matches('"').to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+            } else if !rest.is_empty() {
+                uses.push(rest.trim_matches('"').to_string());
+            }
+        }
+    }
+    uses
+}
+
+fn string_items(items: &[serde_json::Value]) -> Vec<String> {
+    items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(path).ok()?.parse().ok()
+}
@@ -0,0 +1,370 @@
+// Syntari AI IDE - Task Discovery
+// Finds runnable tasks in package.json scripts, Cargo targets, Makefiles, justfiles and pyproject.toml
+
+use std::collections::HashSet;
+use std::path::Path;
+use regex::Regex;
+use crate::project::cargo::{self, TargetKind};
+use crate::project::tasks::types::{TaskDefinition, TaskGroup, TaskSource, WorkspaceTasksFile};
+
+/// User-defined tasks, relative to the project root
+pub const TASKS_FILE: &str = ".syntari/tasks.json";
+
+/// Tasks of the project root and of each sub-project directory; ids of sub-project tasks carry `@<relative dir>`
+pub fn discover_tasks(project_root: &Path, sub_projects: &[String]) -> Vec<TaskDefinition> {
+    let mut directories = vec![project_root.to_path_buf()];
+    for dir in sub_projects {
+        let dir = Path::new(dir);
+        if dir != project_root && !directories.iter().any(|d| d == dir) {
+            directories.push(dir.to_path_buf());
+        }
+    }
+
+    let mut tasks = Vec::new();
+    let mut cargo_roots = HashSet::new();
+    for dir in &directories {
+        let scope = dir.strip_prefix(project_root).ok().map(|p| p.to_string_lossy().replace('\\', "/")).unwrap_or_default();
+        let mut found = Vec::new();
+        found.extend(npm_tasks(dir));
+        found.extend(cargo_tasks(dir, &mut cargo_roots));
+        found.extend(make_tasks(dir));
+        found.extend(just_tasks(dir));
+        found.extend(python_tasks(dir));
+        if !scope.is_empty() {
+            for task in &mut found {
+                task.id = format!("{}@{}", task.id, scope);
+                task.label = format!("{} ({})", task.label, scope);
+            }
+        }
+        tasks.extend(found);
+    }
+    tasks.extend(workspace_tasks(project_root));
+    tasks
+}
+
+fn task(id: String, label: String, source: TaskSource, group: TaskGroup, command: String, cwd: &Path) -> TaskDefinition {
+    TaskDefinition {
+        id,
+        label,
+        source,
+        group,
+        command,
+        cwd: cwd.to_string_lossy().to_string(),
+        depends_on: Vec::new(),
+        description: None,
+    }
+}
+
+/// Group by the conventional meaning of a script or target name
+fn group_for(name: &str) -> TaskGroup {
+    let name = name.to_lowercase();
+    if name.contains("test") || name.contains("spec") {
+        TaskGroup::Test
+    } else if name.contains("build") || name.contains("compile") || name.contains("bundle") {
+        TaskGroup::Build
+    } else if ["start", "dev", "serve", "run", "watch", "preview"].iter().any(|word| name.contains(word)) {
+        TaskGroup::Run
+    } else {
+        TaskGroup::Other
+    }
+}
+
+/// Quote an argument for the platform shell when it contains anything beyond plain word characters
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:=,@+".contains(c));
+    if plain {
+        arg.to_string()
+    } else if cfg!(target_os = "windows") {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+fn command_line(program: &str, args: &[String]) -> String {
+    std::iter::once(program.to_string()).chain(args.iter().map(|a| shell_quote(a))).collect::<Vec<_>>().join(" ")
+}
+
+// ================================
+// NPM SCRIPTS
+// ================================
+
+fn npm_tasks(dir: &Path) -> Vec<TaskDefinition> {
+    let Some(json) = std::fs::read_to_string(dir.join("package.json")).ok().and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) else {
+        return Vec::new();
+    };
+    let manager = if dir.join("pnpm-lock.yaml").is_file() {
+        "pnpm"
+    } else if dir.join("yarn.lock").is_file() {
+        "yarn"
+    } else if dir.join("bun.lockb").is_file() || dir.join("bun.lock").is_file() {
+        "bun"
+    } else {
+        "npm"
+    };
+
+    let mut tasks = Vec::new();
+    for (name, script) in json.get("scripts").and_then(|s| s.as_object()).into_iter().flatten() {
+        // Lifecycle hooks run automatically around their main script
+        let hook = ["pre", "post"].iter().any(|prefix| name.strip_prefix(prefix).is_some_and(|rest| json["scripts"].get(rest).is_some()));
+        if hook {
+            continue;
+        }
+        let mut definition = task(
+            format!("npm:{}", name),
+            name.clone(),
+            TaskSource::Npm,
+            group_for(name),
+            format!("{} run {}", manager, shell_quote(name)),
+            dir,
+        );
+        definition.description = script.as_str().map(str::to_string);
+        tasks.push(definition);
+    }
+    tasks
+}
+
+// ================================
+// CARGO TARGETS
+// ================================
+
+/// Workspace-wide build and test, then build/run/test per binary and the natural action of the other targets
+fn cargo_tasks(dir: &Path, seen_roots: &mut HashSet<String>) -> Vec<TaskDefinition> {
+    if !dir.join(cargo::MANIFEST_FILE).is_file() {
+        return Vec::new();
+    }
+    let workspace = match cargo::load_workspace(dir) {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            e.log_warn();
+            return Vec::new();
+        }
+    };
+    // Members of a workspace discovered earlier share its tasks
+    if !seen_roots.insert(workspace.root.clone()) {
+        return Vec::new();
+    }
+    let root = Path::new(&workspace.root);
+    let cargo_task = |id: String, label: String, group: TaskGroup, args: &[String]| {
+        task(format!("cargo:{}", id), label, TaskSource::Cargo, group, command_line("cargo", args), root)
+    };
+    let with_verb = |command: &[String], verb: &str| -> Vec<String> {
+        std::iter::once(verb.to_string()).chain(command.iter().skip(1).cloned()).collect()
+    };
+
+    let mut tasks = vec![
+        cargo_task("build".into(), "cargo build".into(), TaskGroup::Build, &["build".into(), "--workspace".into()]),
+        cargo_task("test".into(), "cargo test".into(), TaskGroup::Test, &["test".into(), "--workspace".into()]),
+    ];
+    for package in &workspace.packages {
+        for target in &package.targets {
+            let name = &target.name;
+            match target.kind {
+                TargetKind::Bin => {
+                    tasks.push(cargo_task(format!("build:{}", name), format!("build {}", name), TaskGroup::Build, &with_verb(&target.command, "build")));
+                    tasks.push(cargo_task(format!("run:{}", name), format!("run {}", name), TaskGroup::Run, &target.command));
+                    tasks.push(cargo_task(format!("test:{}", name), format!("test {}", name), TaskGroup::Test, &with_verb(&target.command, "test")));
+                }
+                TargetKind::Lib => {
+                    tasks.push(cargo_task(format!("build:{}:lib", package.name), format!("build {} (lib)", package.name), TaskGroup::Build, &target.command));
+                    tasks.push(cargo_task(format!("test:{}:lib", package.name), format!("test {} (lib)", package.name), TaskGroup::Test, &with_verb(&target.command, "test")));
+                }
+                TargetKind::Test => {
+                    tasks.push(cargo_task(format!("test:{}:{}", package.name, name), format!("test {}", name), TaskGroup::Test, &target.command));
+                }
+                TargetKind::Example => {
+                    tasks.push(cargo_task(format!("example:{}", name), format!("example {}", name), TaskGroup::Run, &target.command));
+                }
+                TargetKind::Bench => {
+                    tasks.push(cargo_task(format!("bench:{}", name), format!("bench {}", name), TaskGroup::Test, &target.command));
+                }
+                TargetKind::BuildScript => {}
+            }
+        }
+    }
+    tasks
+}
+
+// ================================
+// MAKEFILE TARGETS
+// ================================
+
+/// Explicit targets; special (`.PHONY`), pattern and file targets are left out
+fn make_tasks(dir: &Path) -> Vec<TaskDefinition> {
+    let Some((file, content)) = ["GNUmakefile", "Makefile", "makefile"]
+        .iter()
+        .find_map(|name| std::fs::read_to_string(dir.join(name)).ok().map(|content| (*name, content)))
+    else {
+        return Vec::new();
+    };
+    let Ok(rule) = Regex::new(r"^([A-Za-z0-9_-]+(?:[ \t]+[A-Za-z0-9_-]+)*)[ \t]*::?(?:[^=]|$)") else {
+        return Vec::new();
+    };
+
+    let mut tasks: Vec<TaskDefinition> = Vec::new();
+    let mut comment: Option<String> = None;
+    for line in content.lines() {
+        if let Some(text) = line.strip_prefix('#') {
+            comment = Some(text.trim_start_matches('#').trim().to_string());
+            continue;
+        }
+        let Some(captures) = rule.captures(line) else {
+            comment = None;
+            continue;
+        };
+        // `target: deps ## description` is a common self-documenting convention
+        let inline = line.split_once("##").map(|(_, text)| text.trim().to_string());
+        for name in captures[1].split_whitespace() {
+            if tasks.iter().any(|t| t.label == name) {
+                continue;
+            }
+            let mut definition = task(
+                format!("make:{}", name),
+                name.to_string(),
+                TaskSource::Make,
+                group_for(name),
+                format!("make -f {} {}", file, name),
+                dir,
+            );
+            definition.description = inline.clone().or_else(|| comment.clone()).filter(|d| !d.is_empty());
+            tasks.push(definition);
+        }
+        comment = None;
+    }
+    tasks
+}
+
+// ================================
+// JUSTFILE RECIPES
+// ================================
+
+fn just_tasks(dir: &Path) -> Vec<TaskDefinition> {
+    let Some(content) = ["justfile", "Justfile", ".justfile"].iter().find_map(|name| std::fs::read_to_string(dir.join(name)).ok()) else {
+        return Vec::new();
+    };
+    let Ok(recipe) = Regex::new(r"^@?([A-Za-z_][A-Za-z0-9_-]*)([^:]*):(?:[^=]|$)") else {
+        return Vec::new();
+    };
+
+    let mut tasks = Vec::new();
+    let mut comment: Option<String> = None;
+    let mut private = false;
+    for line in content.lines() {
+        if let Some(text) = line.strip_prefix('#') {
+            comment = Some(text.trim().to_string());
+            continue;
+        }
+        if line.starts_with('[') {
+            private |= line.contains("private");
+            continue;
+        }
+        let keyword = line.split_whitespace().next().unwrap_or("");
+        let captures = recipe.captures(line).filter(|_| !matches!(keyword, "set" | "alias" | "export" | "import" | "mod"));
+        if let Some(captures) = captures {
+            let name = &captures[1];
+            if !private && !name.starts_with('_') {
+                let mut definition = task(format!("just:{}", name), name.to_string(), TaskSource::Just, group_for(name), format!("just {}", name), dir);
+                definition.description = comment.clone().filter(|d| !d.is_empty());
+                tasks.push(definition);
+            }
+        }
+        if !line.starts_with([' ', '\t']) {
+            comment = None;
+            private = false;
+        }
+    }
+    tasks
+}
+
+// ================================
+// PYPROJECT SCRIPTS
+// ================================
+
+/// Console scripts and task-runner entries, run through the environment manager that locks the project
+fn python_tasks(dir: &Path) -> Vec<TaskDefinition> {
+    let Some(table) = std::fs::read_to_string(dir.join("pyproject.toml")).ok().and_then(|c| c.parse::<toml::Table>().ok()) else {
+        return Vec::new();
+    };
+    let runner = if dir.join("uv.lock").is_file() {
+        "uv run "
+    } else if dir.join("poetry.lock").is_file() {
+        "poetry run "
+    } else if dir.join("pdm.lock").is_file() {
+        "pdm run "
+    } else {
+        ""
+    };
+    let keys = |value: Option<&toml::Value>| -> Vec<(String, Option<String>)> {
+        value
+            .and_then(|v| v.as_table())
+            .map(|table| {
+                table
+                    .iter()
+                    .map(|(name, entry)| {
+                        let description = entry
+                            .as_str()
+                            .map(str::to_string)
+                            .or_else(|| entry.get("help").or_else(|| entry.get("cmd")).and_then(|v| v.as_str()).map(str::to_string));
+                        (name.clone(), description)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let tool = table.get("tool");
+
+    let mut tasks: Vec<TaskDefinition> = Vec::new();
+    let mut add = |name: String, command: String, description: Option<String>| {
+        if tasks.iter().any(|t| t.label == name) {
+            return;
+        }
+        let mut definition = task(format!("python:{}", name), name.clone(), TaskSource::Python, group_for(&name), command, dir);
+        definition.description = description;
+        tasks.push(definition);
+    };
+    for (name, entry) in keys(table.get("project").and_then(|p| p.get("scripts"))).into_iter().chain(keys(tool.and_then(|t| t.get("poetry")).and_then(|p| p.get("scripts")))) {
+        add(name.clone(), format!("{}{}", runner, shell_quote(&name)), entry);
+    }
+    for (name, entry) in keys(tool.and_then(|t| t.get("pdm")).and_then(|p| p.get("scripts"))) {
+        // `_`-prefixed keys such as `_.env_file` configure pdm itself
+        if !name.starts_with('_') {
+            add(name.clone(), format!("pdm run {}", shell_quote(&name)), entry);
+        }
+    }
+    for (name, entry) in keys(tool.and_then(|t| t.get("poe")).and_then(|p| p.get("tasks"))) {
+        add(name.clone(), format!("{}poe {}", runner, shell_quote(&name)), entry);
+    }
+    tasks
+}
+
+// ================================
+// WORKSPACE TASKS
+// ================================
+
+/// Tasks declared in `.syntari/tasks.json`; these may depend on any discovered task by id or label
+fn workspace_tasks(project_root: &Path) -> Vec<TaskDefinition> {
+    let path = project_root.join(TASKS_FILE);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let file: WorkspaceTasksFile = match serde_json::from_str(&content) {
+        Ok(file) => file,
+        Err(e) => {
+            tracing::warn!("Ignoring invalid tasks file {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+    file.tasks
+        .into_iter()
+        .map(|workspace_task| TaskDefinition {
+            id: format!("workspace:{}", workspace_task.label),
+            group: workspace_task.group.unwrap_or_else(|| group_for(&workspace_task.label)),
+            command: workspace_task.command.unwrap_or_default(),
+            cwd: project_root.join(workspace_task.cwd.unwrap_or_default()).to_string_lossy().to_string(),
+            depends_on: workspace_task.depends_on,
+            description: workspace_task.description,
+            label: workspace_task.label,
+            source: TaskSource::Workspace,
+        })
+        .collect()
+}
@@ -0,0 +1,10 @@
+// Syntari AI IDE - Project Tasks
+// Task discovery across build tools and a runner backed by terminal sessions
+
+pub mod types;
+pub mod discovery;
+pub mod runner;
+
+pub use types::{TaskDefinition, TaskGroup, TaskRun, TaskSource, TaskStatus};
+pub use discovery::discover_tasks;
+pub use runner::TaskRunner;
@@ -0,0 +1,304 @@
+// Syntari AI IDE - Task Runner
+// Runs tasks and their dependencies in dedicated PTY sessions and keeps a history of runs
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{Emitter, Manager};
+use crate::core::{AppError, AppResult, current_timestamp, generate_id};
+use crate::project::tasks::types::{TaskDefinition, TaskRun, TaskStatus};
+use crate::terminal::commands::TerminalManager;
+
+/// Older runs fall out of the history
+const MAX_HISTORY: usize = 100;
+/// Emitted with a `TaskRun` on every status change
+pub const TASK_STATUS_EVENT: &str = "task-status";
+
+/// The last task started from the panel, kept for "rerun last task"
+#[derive(Debug, Clone)]
+pub struct LastTask {
+    pub project_path: String,
+    pub task_id: String,
+}
+
+/// Registered as Tauri state
+pub struct TaskRunner {
+    history: Mutex<VecDeque<TaskRun>>,
+    killers: Mutex<HashMap<String, Box<dyn portable_pty::ChildKiller + Send + Sync>>>,
+    last: Mutex<Option<LastTask>>,
+}
+
+impl Default for TaskRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskRunner {
+    pub fn new() -> Self {
+        Self {
+            history: Mutex::new(VecDeque::new()),
+            killers: Mutex::new(HashMap::new()),
+            last: Mutex::new(None),
+        }
+    }
+
+    pub fn last_task(&self) -> Option<LastTask> {
+        self.last.lock().ok().and_then(|last| last.clone())
+    }
+
+    /// Newest first
+    pub fn history(&self, limit: Option<usize>) -> Vec<TaskRun> {
+        match self.history.lock() {
+            Ok(history) => history.iter().rev().take(limit.unwrap_or(MAX_HISTORY)).cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Start `task_id` after its dependencies; returns the pending or running record of the requested task
+    pub fn start(
+        &self,
+        app_handle: &tauri::AppHandle,
+        project_path: &str,
+        tasks: &[TaskDefinition],
+        task_id: &str,
+        size: (u16, u16),
+    ) -> AppResult<TaskRun> {
+        let order = execution_order(tasks, task_id)?;
+        // `task_id` may have been a label; the requested task is always last
+        let task_id = order.last().map(|task| task.id.clone()).unwrap_or_default();
+        if let Ok(mut last) = self.last.lock() {
+            *last = Some(LastTask { project_path: project_path.to_string(), task_id: task_id.clone() });
+        }
+
+        let main_run_id = generate_id();
+        let runs: Vec<TaskRun> = order
+            .iter()
+            .map(|task| {
+                let is_main = task.id == task_id;
+                TaskRun {
+                    run_id: if is_main { main_run_id.clone() } else { generate_id() },
+                    task_id: task.id.clone(),
+                    label: task.label.clone(),
+                    command: task.command.clone(),
+                    cwd: task.cwd.clone(),
+                    project_path: project_path.to_string(),
+                    terminal_session_id: None,
+                    status: TaskStatus::Pending,
+                    exit_code: None,
+                    started_at: None,
+                    finished_at: None,
+                    duration_ms: None,
+                    dependency_of: (!is_main).then(|| main_run_id.clone()),
+                    error: None,
+                }
+            })
+            .collect();
+        for run in &runs {
+            self.record(app_handle, run);
+        }
+        let requested = runs.last().cloned().ok_or_else(|| AppError::project("TASK_NOT_FOUND", "Task not found"))?;
+
+        // Dependencies run one after another; a failure skips everything after it
+        let app_handle = app_handle.clone();
+        std::thread::spawn(move || {
+            let runner = app_handle.state::<TaskRunner>();
+            let mut failed: Option<String> = None;
+            for mut run in runs {
+                if runner.status_of(&run.run_id) == Some(TaskStatus::Cancelled) {
+                    failed = Some(run.label.clone());
+                    continue;
+                }
+                if let Some(label) = &failed {
+                    run.status = TaskStatus::Skipped;
+                    run.error = Some(format!("Dependency '{}' did not succeed", label));
+                    runner.record(&app_handle, &run);
+                    continue;
+                }
+                runner.execute(&app_handle, &mut run, size);
+                if run.status != TaskStatus::Succeeded {
+                    failed = Some(run.label.clone());
+                }
+            }
+        });
+        Ok(requested)
+    }
+
+    /// Run one task to completion in its own PTY session
+    fn execute(&self, app_handle: &tauri::AppHandle, run: &mut TaskRun, (cols, rows): (u16, u16)) {
+        let started = Instant::now();
+        run.started_at = Some(current_timestamp());
+
+        // Tasks that only chain their dependencies have nothing to run themselves
+        if run.command.trim().is_empty() {
+            run.status = TaskStatus::Succeeded;
+            run.finished_at = run.started_at;
+            run.duration_ms = Some(0);
+            if !self.record_if(app_handle, run, TaskStatus::Pending) {
+                run.status = TaskStatus::Cancelled;
+            }
+            return;
+        }
+
+        let terminals = app_handle.state::<TerminalManager>();
+        let env = vec![("SYNTARI_TASK".to_string(), run.task_id.clone())];
+        let mut child = match terminals.create_command_session(&run.cwd, &run.command, &env, cols, rows) {
+            Ok((session_id, child)) => {
+                run.terminal_session_id = Some(session_id);
+                child
+            }
+            Err(e) => {
+                run.status = TaskStatus::Failed;
+                run.error = Some(e);
+                run.finished_at = Some(current_timestamp());
+                run.duration_ms = Some(started.elapsed().as_millis() as u64);
+                self.record(app_handle, run);
+                return;
+            }
+        };
+        if let Ok(mut killers) = self.killers.lock() {
+            killers.insert(run.run_id.clone(), child.clone_killer());
+        }
+        // The killer is registered first, so a cancel either sees Running and kills it or is seen here
+        run.status = TaskStatus::Running;
+        if self.record_if(app_handle, run, TaskStatus::Pending) {
+            tracing::info!("▶️ Task {} started: {}", run.task_id, run.command);
+        } else {
+            let killer = self.killers.lock().ok().and_then(|mut killers| killers.remove(&run.run_id));
+            if let Some(mut killer) = killer {
+                if let Err(e) = killer.kill() {
+                    tracing::warn!("Failed to stop cancelled task {}: {}", run.task_id, e);
+                }
+            }
+        }
+
+        let status = child.wait();
+        let cancelled = self.killers.lock().map(|mut killers| killers.remove(&run.run_id).is_none()).unwrap_or(false);
+        run.finished_at = Some(current_timestamp());
+        run.duration_ms = Some(started.elapsed().as_millis() as u64);
+        match status {
+            _ if cancelled => run.status = TaskStatus::Cancelled,
+            Ok(status) => {
+                run.exit_code = Some(status.exit_code() as i32);
+                run.status = if status.success() { TaskStatus::Succeeded } else { TaskStatus::Failed };
+            }
+            Err(e) => {
+                run.status = TaskStatus::Failed;
+                run.error = Some(format!("Failed to wait for task: {}", e));
+            }
+        }
+        tracing::info!("⏹️ Task {} finished: {:?} in {}ms", run.task_id, run.status, run.duration_ms.unwrap_or(0));
+        self.record(app_handle, run);
+    }
+
+    /// Stop a run: kill it if running, or drop it if still waiting; cancelling a requested task also stops its dependencies
+    pub fn cancel(&self, app_handle: &tauri::AppHandle, run_id: &str) -> AppResult<()> {
+        let chain: Vec<TaskRun> = match self.history.lock() {
+            Ok(history) => history
+                .iter()
+                .filter(|r| r.run_id == run_id || r.dependency_of.as_deref() == Some(run_id))
+                .filter(|r| !r.status.is_finished())
+                .cloned()
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        if chain.is_empty() {
+            return Err(AppError::project("TASK_NOT_RUNNING", "Task is not running"));
+        }
+
+        for mut run in chain {
+            if run.status == TaskStatus::Pending {
+                run.status = TaskStatus::Cancelled;
+                if self.record_if(app_handle, &run, TaskStatus::Pending) {
+                    continue;
+                }
+                // It started meanwhile; its killer is registered by now
+            }
+            // The runner thread sees the missing killer and records the run as cancelled
+            let killer = self.killers.lock().ok().and_then(|mut killers| killers.remove(&run.run_id));
+            if let Some(mut killer) = killer {
+                killer
+                    .kill()
+                    .map_err(|e| AppError::internal("TASK_CANCEL_FAILED".to_string(), format!("Failed to stop task: {}", e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn status_of(&self, run_id: &str) -> Option<TaskStatus> {
+        self.history.lock().ok()?.iter().find(|r| r.run_id == run_id).map(|r| r.status)
+    }
+
+    /// Update a run only if its recorded status is still `expected`; returns whether it was updated
+    fn record_if(&self, app_handle: &tauri::AppHandle, run: &TaskRun, expected: TaskStatus) -> bool {
+        let updated = match self.history.lock() {
+            Ok(mut history) => match history.iter_mut().find(|r| r.run_id == run.run_id) {
+                Some(existing) if existing.status == expected => {
+                    *existing = run.clone();
+                    true
+                }
+                _ => false,
+            },
+            Err(_) => false,
+        };
+        if updated {
+            if let Err(e) = app_handle.emit(TASK_STATUS_EVENT, run) {
+                tracing::warn!("Failed to emit task status: {}", e);
+            }
+        }
+        updated
+    }
+
+    /// Insert or update a run in the history and notify the frontend
+    fn record(&self, app_handle: &tauri::AppHandle, run: &TaskRun) {
+        if let Ok(mut history) = self.history.lock() {
+            match history.iter_mut().find(|r| r.run_id == run.run_id) {
+                Some(existing) => *existing = run.clone(),
+                None => {
+                    history.push_back(run.clone());
+                    while history.len() > MAX_HISTORY {
+                        history.pop_front();
+                    }
+                }
+            }
+        }
+        if let Err(e) = app_handle.emit(TASK_STATUS_EVENT, run) {
+            tracing::warn!("Failed to emit task status: {}", e);
+        }
+    }
+}
+
+/// Dependencies depth first, each task once, ending with `task_id`; dependencies may name tasks by id or label
+pub fn execution_order(tasks: &[TaskDefinition], task_id: &str) -> AppResult<Vec<TaskDefinition>> {
+    let find = |reference: &str| tasks.iter().find(|t| t.id == reference).or_else(|| tasks.iter().find(|t| t.label == reference));
+
+    fn visit<'a>(
+        task: &'a TaskDefinition,
+        find: &dyn Fn(&str) -> Option<&'a TaskDefinition>,
+        visiting: &mut Vec<String>,
+        order: &mut Vec<TaskDefinition>,
+    ) -> AppResult<()> {
+        if order.iter().any(|t| t.id == task.id) {
+            return Ok(());
+        }
+        if visiting.contains(&task.id) {
+            visiting.push(task.id.clone());
+            return Err(AppError::validation("TASK_DEPENDENCY_CYCLE", &format!("Task dependencies form a cycle: {}", visiting.join(" -> "))));
+        }
+        visiting.push(task.id.clone());
+        for dependency in &task.depends_on {
+            let resolved = find(dependency).ok_or_else(|| {
+                AppError::validation("TASK_DEPENDENCY_NOT_FOUND", &format!("Task '{}' depends on unknown task '{}'", task.label, dependency))
+            })?;
+            visit(resolved, find, visiting, order)?;
+        }
+        visiting.pop();
+        order.push(task.clone());
+        Ok(())
+    }
+
+    let task = find(task_id).ok_or_else(|| AppError::project("TASK_NOT_FOUND", &format!("No task named '{}'", task_id)))?;
+    let mut order = Vec::new();
+    visit(task, &find, &mut Vec::new(), &mut order)?;
+    Ok(order)
+}
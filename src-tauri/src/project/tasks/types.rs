@@ -0,0 +1,115 @@
+// Syntari AI IDE - Task Types
+// Runnable project tasks and the record of each run
+
+use serde::{Deserialize, Serialize};
+
+// ================================
+// TASK DEFINITIONS
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSource {
+    /// `scripts` of package.json
+    Npm,
+    /// Build, test and run actions of Cargo targets
+    Cargo,
+    Make,
+    Just,
+    /// Scripts declared in pyproject.toml
+    Python,
+    /// `.syntari/tasks.json`
+    Workspace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskGroup {
+    Build,
+    Test,
+    Run,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskDefinition {
+    /// Stable across discoveries, e.g. `npm:build` or `cargo:test:app`
+    pub id: String,
+    pub label: String,
+    pub source: TaskSource,
+    pub group: TaskGroup,
+    /// Shell command line
+    pub command: String,
+    pub cwd: String,
+    /// Ids of tasks that must succeed first, in order
+    pub depends_on: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// A user-defined task from `.syntari/tasks.json`
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkspaceTask {
+    pub label: String,
+    /// Optional for tasks that only chain others
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Relative to the project root
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Ids or labels of other tasks
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub group: Option<TaskGroup>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkspaceTasksFile {
+    #[serde(default)]
+    pub tasks: Vec<WorkspaceTask>,
+}
+
+// ================================
+// TASK RUNS
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    /// Waiting for its dependencies
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    /// Not started because a dependency did not succeed
+    Skipped,
+}
+
+impl TaskStatus {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, TaskStatus::Pending | TaskStatus::Running)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRun {
+    pub run_id: String,
+    pub task_id: String,
+    pub label: String,
+    pub command: String,
+    pub cwd: String,
+    pub project_path: String,
+    /// PTY session streaming the task's output; readable through the terminal commands
+    pub terminal_session_id: Option<String>,
+    pub status: TaskStatus,
+    pub exit_code: Option<i32>,
+    pub started_at: Option<u64>,
+    pub finished_at: Option<u64>,
+    pub duration_ms: Option<u64>,
+    /// Run id of the task this run was started for, when it ran as a dependency
+    pub dependency_of: Option<String>,
+    pub error: Option<String>,
+}
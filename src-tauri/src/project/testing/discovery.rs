@@ -0,0 +1,416 @@
+// Syntari AI IDE - Test Discovery
+// Lists tests through each framework's own runner and arranges them into the explorer tree
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+use serde_json::Value;
+use crate::project::cargo;
+use crate::project::diagnostics::{self, DiagnosticSeverity};
+use crate::project::testing::types::{
+    TestCase, TestFramework, TestNode, TestNodeKind, TestOutcome, TestResult, TestSuite, TestUnit,
+};
+
+/// Listing Rust tests builds every test target first, which takes minutes on a cold cache
+const LIST_TIMEOUT: Duration = Duration::from_secs(600);
+
+const VITEST_CONFIGS: &[&str] = &["vitest.config.ts", "vitest.config.mts", "vitest.config.js", "vitest.config.mjs", "vitest.config.cts", "vitest.config.cjs"];
+const JEST_CONFIGS: &[&str] = &["jest.config.js", "jest.config.ts", "jest.config.mjs", "jest.config.cjs", "jest.config.json"];
+
+/// Test suites of the project root and each sub-project, listed with their tests
+pub async fn discover_suites(project_root: &Path, sub_projects: &[String]) -> Vec<TestSuite> {
+    let mut directories = vec![project_root.to_path_buf()];
+    for dir in sub_projects {
+        let dir = Path::new(dir);
+        if !directories.iter().any(|d| d == dir) {
+            directories.push(dir.to_path_buf());
+        }
+    }
+
+    let mut suites = Vec::new();
+    let mut cargo_roots = HashSet::new();
+    for dir in &directories {
+        if dir.join(cargo::MANIFEST_FILE).is_file() {
+            // Members of a workspace are listed together from its root
+            match cargo::load_workspace(dir) {
+                Ok(workspace) if cargo_roots.insert(workspace.root.clone()) => {
+                    suites.push(cargo_suite(project_root, &workspace).await);
+                }
+                Ok(_) => {}
+                Err(e) => e.log_warn(),
+            }
+        }
+        let package_json = read_json(&dir.join("package.json"));
+        let depends_on = |name: &str| {
+            ["dependencies", "devDependencies"]
+                .iter()
+                .any(|section| package_json.as_ref().and_then(|p| p.get(section)).and_then(|d| d.get(name)).is_some())
+        };
+        if VITEST_CONFIGS.iter().any(|name| dir.join(name).is_file()) || depends_on("vitest") {
+            suites.push(vitest_suite(project_root, dir).await);
+        } else if JEST_CONFIGS.iter().any(|name| dir.join(name).is_file())
+            || package_json.as_ref().is_some_and(|p| p.get("jest").is_some())
+            || depends_on("jest")
+        {
+            suites.push(jest_suite(project_root, dir).await);
+        }
+        if has_pytest_config(dir) {
+            suites.push(pytest_suite(project_root, dir).await);
+        }
+    }
+    suites
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    std::fs::read_to_string(path).ok().and_then(|text| serde_json::from_str(&text).ok())
+}
+
+fn has_pytest_config(dir: &Path) -> bool {
+    let section_in = |file: &str, section: &str| std::fs::read_to_string(dir.join(file)).is_ok_and(|text| text.contains(section));
+    dir.join("pytest.ini").is_file()
+        || dir.join("conftest.py").is_file()
+        || section_in("pyproject.toml", "[tool.pytest.ini_options]")
+        || section_in("setup.cfg", "[tool:pytest]")
+        || section_in("tox.ini", "[pytest]")
+}
+
+pub(crate) fn suite_id(framework: TestFramework, project_root: &Path, dir: &Path) -> String {
+    let relative = dir.strip_prefix(project_root).map(|p| p.to_string_lossy().replace('\\', "/")).unwrap_or_default();
+    format!("{}@{}", framework.name(), if relative.is_empty() { "." } else { relative.as_str() })
+}
+
+fn empty_suite(framework: TestFramework, project_root: &Path, dir: &Path) -> TestSuite {
+    TestSuite {
+        id: suite_id(framework, project_root, dir),
+        framework,
+        cwd: dir.to_string_lossy().to_string(),
+        units: Vec::new(),
+        tests: Vec::new(),
+        error: None,
+    }
+}
+
+/// Build a test case whose id places it under its unit and groups
+pub(crate) fn test_case(suite_id: &str, unit: &str, path: Vec<String>, name: String, selector: String) -> TestCase {
+    let mut id = format!("{}::{}", suite_id, unit);
+    for segment in path.iter().chain(std::iter::once(&name)) {
+        id.push_str("::");
+        id.push_str(segment);
+    }
+    TestCase { id, unit: unit.to_string(), path, name, selector }
+}
+
+/// Run a listing command to completion; `Err` carries the reason it could not
+pub(crate) async fn command_output(program: &str, args: &[String], cwd: &str) -> Result<std::process::Output, String> {
+    let mut command = tokio::process::Command::new(program);
+    command.args(args).current_dir(cwd).kill_on_drop(true);
+    match tokio::time::timeout(LIST_TIMEOUT, command.output()).await {
+        Err(_) => Err(format!("{} did not finish within {} seconds", program, LIST_TIMEOUT.as_secs())),
+        Ok(Err(e)) => Err(format!("Failed to run {}: {}", program, e)),
+        Ok(Ok(output)) => Ok(output),
+    }
+}
+
+/// Last non-empty stderr line, which is where these tools put the reason they failed
+pub(crate) fn failure_reason(program: &str, output: &std::process::Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("no output").trim().to_string();
+    format!("{} exited with {}: {}", program, output.status, detail)
+}
+
+// ================================
+// CARGO
+// ================================
+
+/// A libtest harness produced by `cargo test --no-run`
+#[derive(Debug, Clone)]
+pub(crate) struct CargoTestBinary {
+    pub unit: TestUnit,
+    pub executable: String,
+}
+
+/// Build every test target of the workspace and return the harnesses; a build failure reports its first error
+pub(crate) async fn build_cargo_tests(workspace: &cargo::CargoWorkspace) -> Result<Vec<CargoTestBinary>, String> {
+    let args: Vec<String> = ["test", "--workspace", "--no-run", "--message-format=json"].iter().map(|a| a.to_string()).collect();
+    let output = command_output("cargo", &args, &workspace.root).await?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let first_error = diagnostics::parsers::parse_cargo(&stdout, Path::new(&workspace.root))
+            .into_iter()
+            .find(|d| d.severity == DiagnosticSeverity::Error)
+            .map(|d| format!("Build failed: {} ({}:{})", d.message.lines().next().unwrap_or_default(), d.file, d.range.start_line));
+        return Err(first_error.unwrap_or_else(|| failure_reason("cargo", &output)));
+    }
+
+    let mut binaries = Vec::new();
+    for line in stdout.lines().filter(|l| l.starts_with('{')) {
+        let Ok(artifact) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if artifact.get("reason").and_then(|r| r.as_str()) != Some("compiler-artifact") {
+            continue;
+        }
+        let Some(executable) = artifact.get("executable").and_then(|e| e.as_str()) else {
+            continue;
+        };
+        let Some(target) = artifact.get("target") else {
+            continue;
+        };
+        let name = target.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+        let src_path = target.get("src_path").and_then(|p| p.as_str()).unwrap_or_default();
+        let kind = match target.get("kind").and_then(|k| k.get(0)).and_then(|k| k.as_str()).unwrap_or("lib") {
+            kind @ ("bin" | "test" | "example" | "bench") => kind,
+            _ => "lib",
+        };
+        let (package, package_root) = match workspace.package_for_path(Path::new(src_path)) {
+            Some(package) => (package.name.clone(), package.root.clone()),
+            None => (name.to_string(), workspace.root.clone()),
+        };
+        let label = match kind {
+            "lib" => format!("{} (lib)", package),
+            _ => format!("{} ({} {})", package, kind, name),
+        };
+        binaries.push(CargoTestBinary {
+            unit: TestUnit {
+                key: format!("{}/{}/{}", package, kind, name),
+                label,
+                file: src_path.to_string(),
+                // Cargo runs test harnesses from the package directory
+                cwd: package_root,
+            },
+            executable: executable.to_string(),
+        });
+    }
+    Ok(binaries)
+}
+
+async fn cargo_suite(project_root: &Path, workspace: &cargo::CargoWorkspace) -> TestSuite {
+    let mut suite = empty_suite(TestFramework::Cargo, project_root, Path::new(&workspace.root));
+    let binaries = match build_cargo_tests(workspace).await {
+        Ok(binaries) => binaries,
+        Err(error) => {
+            suite.error = Some(error);
+            return suite;
+        }
+    };
+
+    for binary in binaries {
+        let args: Vec<String> = ["--list", "--format", "terse"].iter().map(|a| a.to_string()).collect();
+        match command_output(&binary.executable, &args, &binary.unit.cwd).await {
+            Ok(output) if output.status.success() => {
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    let Some(name) = line.strip_suffix(": test") else {
+                        continue;
+                    };
+                    let mut path: Vec<String> = name.split("::").map(str::to_string).collect();
+                    let leaf = path.pop().unwrap_or_default();
+                    suite.tests.push(test_case(&suite.id, &binary.unit.key, path, leaf, name.to_string()));
+                }
+            }
+            Ok(output) => suite.error = Some(failure_reason(&binary.unit.label, &output)),
+            Err(error) => suite.error = Some(error),
+        }
+        suite.units.push(binary.unit);
+    }
+    suite
+}
+
+// ================================
+// VITEST AND JEST
+// ================================
+
+fn js_unit(cwd: &Path, file: &str) -> TestUnit {
+    let absolute = cwd.join(file);
+    let key = absolute.strip_prefix(cwd).map(|p| p.to_string_lossy().replace('\\', "/")).unwrap_or_else(|_| file.to_string());
+    TestUnit {
+        label: key.clone(),
+        key,
+        file: absolute.to_string_lossy().to_string(),
+        cwd: cwd.to_string_lossy().to_string(),
+    }
+}
+
+/// `vitest list --json`: one entry per test with its file and `describe > test` name
+async fn vitest_suite(project_root: &Path, dir: &Path) -> TestSuite {
+    let mut suite = empty_suite(TestFramework::Vitest, project_root, dir);
+    let Some(vitest) = diagnostics::node_bin(dir, project_root, "vitest") else {
+        suite.error = Some("vitest is not installed; install the project's dependencies to list its tests".to_string());
+        return suite;
+    };
+    let args: Vec<String> = ["list", "--json"].iter().map(|a| a.to_string()).collect();
+    let output = match command_output(&vitest, &args, &suite.cwd).await {
+        Ok(output) => output,
+        Err(error) => {
+            suite.error = Some(error);
+            return suite;
+        }
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(entries) = stdout.find('[').and_then(|start| serde_json::from_str::<Vec<Value>>(&stdout[start..]).ok()) else {
+        suite.error = Some(failure_reason("vitest", &output));
+        return suite;
+    };
+
+    let mut units: BTreeMap<String, TestUnit> = BTreeMap::new();
+    for entry in entries {
+        let (Some(file), Some(name)) = (entry.get("file").and_then(|f| f.as_str()), entry.get("name").and_then(|n| n.as_str())) else {
+            continue;
+        };
+        let unit = js_unit(dir, file);
+        let mut path: Vec<String> = name.split(" > ").map(str::to_string).collect();
+        let leaf = path.pop().unwrap_or_default();
+        // `-t` matches the names joined with spaces
+        let selector = name.replace(" > ", " ");
+        suite.tests.push(test_case(&suite.id, &unit.key, path, leaf, selector));
+        units.entry(unit.key.clone()).or_insert(unit);
+    }
+    suite.units = units.into_values().collect();
+    suite
+}
+
+/// `jest --listTests --json` lists files only; their tests join the tree once they have run
+async fn jest_suite(project_root: &Path, dir: &Path) -> TestSuite {
+    let mut suite = empty_suite(TestFramework::Jest, project_root, dir);
+    let Some(jest) = diagnostics::node_bin(dir, project_root, "jest") else {
+        suite.error = Some("jest is not installed; install the project's dependencies to list its tests".to_string());
+        return suite;
+    };
+    let args: Vec<String> = ["--listTests", "--json"].iter().map(|a| a.to_string()).collect();
+    match command_output(&jest, &args, &suite.cwd).await {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            match stdout.find('[').and_then(|start| serde_json::from_str::<Vec<String>>(&stdout[start..]).ok()) {
+                Some(files) => suite.units = files.iter().map(|file| js_unit(dir, file)).collect(),
+                None => suite.error = Some(failure_reason("jest", &output)),
+            }
+        }
+        Err(error) => suite.error = Some(error),
+    }
+    suite.units.sort_by(|a, b| a.key.cmp(&b.key));
+    suite
+}
+
+// ================================
+// PYTEST
+// ================================
+
+/// The virtualenv's interpreter when there is one
+pub(crate) fn python_for(project_root: &Path, dir: &Path) -> String {
+    diagnostics::venv_bin(dir, project_root, "python").unwrap_or_else(|| if cfg!(windows) { "python" } else { "python3" }.to_string())
+}
+
+/// Split a pytest node id such as `tests/test_a.py::TestX::test_y[1]` into file, classes and name
+pub(crate) fn pytest_case(suite_id: &str, node_id: &str) -> TestCase {
+    let mut parts: Vec<String> = node_id.split("::").map(str::to_string).collect();
+    let name = parts.pop().unwrap_or_default();
+    let file = if parts.is_empty() { String::new() } else { parts.remove(0) };
+    test_case(suite_id, &file, parts, name, node_id.to_string())
+}
+
+/// `pytest --collect-only -q`: one node id per line, then a summary
+async fn pytest_suite(project_root: &Path, dir: &Path) -> TestSuite {
+    let mut suite = empty_suite(TestFramework::Pytest, project_root, dir);
+    let python = python_for(project_root, dir);
+    let args: Vec<String> = ["-m", "pytest", "--collect-only", "-q"].iter().map(|a| a.to_string()).collect();
+    let output = match command_output(&python, &args, &suite.cwd).await {
+        Ok(output) => output,
+        Err(error) => {
+            suite.error = Some(error);
+            return suite;
+        }
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines().take_while(|l| !l.trim().is_empty()) {
+        if line.contains("::") {
+            suite.tests.push(pytest_case(&suite.id, line.trim()));
+        }
+    }
+    let mut files: Vec<String> = suite.tests.iter().map(|t| t.unit.clone()).collect();
+    files.dedup();
+    suite.units = files.iter().map(|file| js_unit(dir, file)).collect();
+    // Exit code 5 only means nothing was collected
+    if !output.status.success() && output.status.code() != Some(5) {
+        let error = stdout.lines().rev().find(|l| !l.trim().is_empty()).map(str::to_string);
+        suite.error = Some(error.unwrap_or_else(|| failure_reason("pytest", &output)));
+    }
+    suite
+}
+
+// ================================
+// TREE
+// ================================
+
+/// Suites → files → modules, classes or describe blocks → tests, with the latest outcome of each
+pub fn build_tree(suites: &[TestSuite], results: &HashMap<String, TestResult>) -> Vec<TestNode> {
+    suites
+        .iter()
+        .map(|suite| {
+            let files = suite
+                .units
+                .iter()
+                .map(|unit| {
+                    let mut node = group_node(format!("{}::{}", suite.id, unit.key), unit.label.clone(), TestNodeKind::File, suite.framework);
+                    node.file = Some(unit.file.clone());
+                    for test in suite.tests.iter().filter(|t| t.unit == unit.key) {
+                        insert_test(&mut node, test, &unit.file, suite.framework, results);
+                    }
+                    node
+                })
+                .collect();
+            let mut node = group_node(suite.id.clone(), suite.id.clone(), TestNodeKind::Suite, suite.framework);
+            node.children = files;
+            node.error = suite.error.clone();
+            summarize(&mut node);
+            node
+        })
+        .collect()
+}
+
+fn group_node(id: String, label: String, kind: TestNodeKind, framework: TestFramework) -> TestNode {
+    TestNode { id, label, kind, framework, file: None, children: Vec::new(), test_count: 0, outcome: None, error: None }
+}
+
+fn insert_test(file_node: &mut TestNode, test: &TestCase, file: &str, framework: TestFramework, results: &HashMap<String, TestResult>) {
+    let mut parent = file_node;
+    for segment in &test.path {
+        let id = format!("{}::{}", parent.id, segment);
+        let index = match parent.children.iter().position(|c| c.id == id && c.kind == TestNodeKind::Module) {
+            Some(index) => index,
+            None => {
+                parent.children.push(group_node(id, segment.clone(), TestNodeKind::Module, framework));
+                parent.children.len() - 1
+            }
+        };
+        parent = &mut parent.children[index];
+    }
+    parent.children.push(TestNode {
+        id: test.id.clone(),
+        label: test.name.clone(),
+        kind: TestNodeKind::Test,
+        framework,
+        file: Some(file.to_string()),
+        children: Vec::new(),
+        test_count: 1,
+        outcome: results.get(&test.id).map(|r| r.outcome),
+        error: None,
+    });
+}
+
+/// Fill in counts and roll outcomes up: any failure fails the group, otherwise it passed once everything ran
+fn summarize(node: &mut TestNode) {
+    if node.kind == TestNodeKind::Test {
+        return;
+    }
+    for child in &mut node.children {
+        summarize(child);
+    }
+    node.test_count = node.children.iter().map(|c| c.test_count).sum();
+    let outcomes: Vec<Option<TestOutcome>> = node.children.iter().map(|c| c.outcome).collect();
+    node.outcome = if outcomes.contains(&Some(TestOutcome::Failed)) {
+        Some(TestOutcome::Failed)
+    } else if !outcomes.is_empty() && outcomes.iter().all(|o| o.is_some()) {
+        outcomes.into_iter().flatten().min()
+    } else {
+        None
+    };
+}
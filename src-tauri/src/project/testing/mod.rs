@@ -0,0 +1,11 @@
+// Syntari AI IDE - Test Explorer
+// Test discovery for cargo, vitest, jest and pytest, and a runner that streams structured results
+
+pub mod types;
+pub mod discovery;
+pub mod reports;
+pub mod runner;
+
+pub use types::{TestFramework, TestNode, TestOutcome, TestResult, TestRun, TestRunStatus, TestSuite};
+pub use discovery::{build_tree, discover_suites};
+pub use runner::TestExplorer;
@@ -0,0 +1,37 @@
+// Syntari test explorer reporter for Jest: prints one marked JSON line per finished test.
+// `onTestCaseResult` streams as each test finishes (Jest 26.6+); `onTestResult` catches skipped tests and older Jest.
+const MARKER = "##syntari-test ";
+
+class SyntariReporter {
+  constructor() {
+    this.seen = new Set();
+  }
+
+  emit(file, result) {
+    const key = `${file}\u0000${result.fullName || [...result.ancestorTitles, result.title].join(" ")}`;
+    if (this.seen.has(key)) {
+      return;
+    }
+    this.seen.add(key);
+    process.stdout.write(MARKER + JSON.stringify({
+      file,
+      ancestors: result.ancestorTitles || [],
+      name: result.title,
+      status: result.status,
+      duration: result.duration ?? null,
+      failures: result.failureMessages || [],
+    }) + "\n");
+  }
+
+  onTestCaseResult(test, result) {
+    this.emit(test.path, result);
+  }
+
+  onTestResult(test, testResult) {
+    for (const result of testResult.testResults) {
+      this.emit(test.path, result);
+    }
+  }
+}
+
+module.exports = SyntariReporter;
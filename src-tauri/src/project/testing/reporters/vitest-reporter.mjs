@@ -0,0 +1,60 @@
+// Syntari test explorer reporter for Vitest: prints one marked JSON line per finished test.
+// `onTaskUpdate` streams results as tests finish; `onFinished` catches tests that never ran, such as skipped ones.
+const MARKER = "##syntari-test ";
+
+const STATUS = { pass: "passed", fail: "failed", skip: "skipped", todo: "skipped" };
+
+export default class SyntariReporter {
+  constructor() {
+    this.seen = new Set();
+  }
+
+  onInit(ctx) {
+    this.ctx = ctx;
+  }
+
+  emit(task) {
+    if (task.type !== "test" && task.type !== "custom") {
+      return;
+    }
+    const state = task.result?.state ?? (task.mode === "skip" || task.mode === "todo" ? "skip" : undefined);
+    if (!STATUS[state] || this.seen.has(task.id)) {
+      return;
+    }
+    this.seen.add(task.id);
+
+    const ancestors = [];
+    for (let suite = task.suite; suite && suite !== task.file && !suite.filepath; suite = suite.suite) {
+      if (suite.name) {
+        ancestors.unshift(suite.name);
+      }
+    }
+    process.stdout.write(MARKER + JSON.stringify({
+      file: task.file?.filepath ?? "",
+      ancestors,
+      name: task.name,
+      status: STATUS[state],
+      duration: task.result?.duration ?? null,
+      failures: (task.result?.errors ?? []).map((error) => error.stack || error.message || String(error)),
+    }) + "\n");
+  }
+
+  onTaskUpdate(packs) {
+    for (const [id] of packs) {
+      const task = this.ctx?.state?.idMap?.get(id);
+      if (task) {
+        this.emit(task);
+      }
+    }
+  }
+
+  onFinished(files = []) {
+    const visit = (task) => {
+      this.emit(task);
+      for (const child of task.tasks ?? []) {
+        visit(child);
+      }
+    };
+    files.forEach(visit);
+  }
+}
@@ -0,0 +1,196 @@
+// Syntari AI IDE - Test Report Parsers
+// Reads libtest JSON events, JUnit XML and our Jest/Vitest reporter lines into test outcomes
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::Value;
+use crate::project::testing::types::TestOutcome;
+
+/// One finished test as a report describes it, before it is matched to a discovered test
+#[derive(Debug, Clone)]
+pub struct ReportedTest {
+    /// libtest: the full test path; JUnit: the `classname`; Jest and Vitest: the test file
+    pub group: String,
+    /// JUnit, Jest and Vitest: the test's own name within its group
+    pub name: String,
+    /// Describe blocks above a Jest or Vitest test
+    pub ancestors: Vec<String>,
+    pub outcome: TestOutcome,
+    pub duration_ms: Option<u64>,
+    pub output: Option<String>,
+}
+
+// ================================
+// LIBTEST
+// ================================
+
+/// One line of `--format json`: only finished tests are returned, suite and `started` events are skipped
+pub fn parse_libtest_line(line: &str) -> Option<ReportedTest> {
+    let event: Value = serde_json::from_str(line.trim()).ok()?;
+    if event.get("type")?.as_str()? != "test" {
+        return None;
+    }
+    let outcome = match event.get("event")?.as_str()? {
+        "ok" => TestOutcome::Passed,
+        "ignored" => TestOutcome::Skipped,
+        "failed" | "timeout" => TestOutcome::Failed,
+        _ => return None,
+    };
+    let output = event
+        .get("stdout")
+        .or_else(|| event.get("message"))
+        .and_then(|o| o.as_str())
+        .filter(|o| !o.trim().is_empty())
+        .map(str::to_string);
+    Some(ReportedTest {
+        group: event.get("name")?.as_str()?.to_string(),
+        name: String::new(),
+        ancestors: Vec::new(),
+        outcome,
+        duration_ms: event.get("exec_time").and_then(|t| t.as_f64()).map(|seconds| (seconds * 1000.0).round() as u64),
+        output,
+    })
+}
+
+// ================================
+// JUNIT XML
+// ================================
+
+lazy_static! {
+    static ref TESTCASE_PATTERN: Regex = Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)").unwrap();
+    static ref FAILURE_PATTERN: Regex = Regex::new(r"(?s)<(failure|error)\b([^>]*?)(?:/>|>(.*?)</(?:failure|error)>)").unwrap();
+    static ref SYSTEM_OUT_PATTERN: Regex = Regex::new(r"(?s)<system-(?:out|err)>(.*?)</system-(?:out|err)>").unwrap();
+    static ref ATTRIBUTE_PATTERN: Regex = Regex::new(r#"([A-Za-z_:][-A-Za-z0-9_:.]*)\s*=\s*"([^"]*)""#).unwrap();
+}
+
+/// `<testcase>` elements of a JUnit report, as written by pytest
+pub fn parse_junit(xml: &str) -> Vec<ReportedTest> {
+    TESTCASE_PATTERN
+        .captures_iter(xml)
+        .map(|captures| {
+            let attributes = captures.get(1).map(|m| m.as_str()).unwrap_or_default();
+            let body = captures.get(2).map(|m| m.as_str()).unwrap_or_default();
+            let (outcome, output) = if let Some(failed) = FAILURE_PATTERN.captures(body) {
+                let mut output = attribute(failed.get(2).map(|m| m.as_str()).unwrap_or_default(), "message").unwrap_or_default();
+                if let Some(details) = failed.get(3).map(|m| xml_text(m.as_str())).filter(|d| !d.trim().is_empty()) {
+                    if !output.is_empty() {
+                        output.push_str("\n\n");
+                    }
+                    output.push_str(details.trim_end());
+                }
+                for captured in SYSTEM_OUT_PATTERN.captures_iter(body) {
+                    let text = xml_text(&captured[1]);
+                    if !text.trim().is_empty() {
+                        output.push_str("\n\n");
+                        output.push_str(text.trim_end());
+                    }
+                }
+                (TestOutcome::Failed, Some(output))
+            } else if body.contains("<skipped") {
+                (TestOutcome::Skipped, None)
+            } else {
+                (TestOutcome::Passed, None)
+            };
+            ReportedTest {
+                group: attribute(attributes, "classname").unwrap_or_default(),
+                name: attribute(attributes, "name").unwrap_or_default(),
+                ancestors: Vec::new(),
+                outcome,
+                duration_ms: attribute(attributes, "time").and_then(|t| t.parse::<f64>().ok()).map(|seconds| (seconds * 1000.0).round() as u64),
+                output,
+            }
+        })
+        .collect()
+}
+
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    ATTRIBUTE_PATTERN
+        .captures_iter(attributes)
+        .find(|captures| &captures[1] == name)
+        .map(|captures| unescape(&captures[2]))
+}
+
+/// Element text with CDATA sections unwrapped and entities decoded
+fn xml_text(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<![CDATA[") {
+        result.push_str(&unescape(&rest[..start]));
+        let inner = &rest[start + "<![CDATA[".len()..];
+        let end = inner.find("]]>").unwrap_or(inner.len());
+        result.push_str(&inner[..end]);
+        rest = inner.get(end + "]]>".len()..).unwrap_or_default();
+    }
+    result.push_str(&unescape(rest));
+    result
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        let entity = &rest[start..];
+        let Some(end) = entity.find(';').filter(|end| *end <= 10) else {
+            result.push('&');
+            rest = &entity[1..];
+            continue;
+        };
+        let decoded = match &entity[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            code if code.starts_with("#x") => u32::from_str_radix(&code[2..], 16).ok().and_then(char::from_u32),
+            code if code.starts_with('#') => code[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &entity[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &entity[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// ================================
+// JEST AND VITEST
+// ================================
+
+/// Prefix of the lines our Jest and Vitest reporters print for each finished test
+pub const JS_REPORTER_MARKER: &str = "##syntari-test ";
+
+/// One line from `reporters/jest-reporter.cjs` or `reporters/vitest-reporter.mjs`; other output is skipped
+pub fn parse_js_reporter_line(line: &str) -> Option<ReportedTest> {
+    let event: Value = serde_json::from_str(line.trim().strip_prefix(JS_REPORTER_MARKER)?).ok()?;
+    let outcome = match event.get("status")?.as_str()? {
+        "passed" => TestOutcome::Passed,
+        "failed" => TestOutcome::Failed,
+        _ => TestOutcome::Skipped,
+    };
+    let output = event
+        .get("failures")
+        .and_then(|m| m.as_array())
+        .map(|messages| messages.iter().filter_map(|m| m.as_str()).collect::<Vec<_>>().join("\n\n"))
+        .filter(|m| !m.is_empty());
+    Some(ReportedTest {
+        group: event.get("file")?.as_str()?.to_string(),
+        name: event.get("name")?.as_str()?.to_string(),
+        ancestors: event
+            .get("ancestors")
+            .and_then(|a| a.as_array())
+            .map(|titles| titles.iter().filter_map(|t| t.as_str()).map(str::to_string).collect())
+            .unwrap_or_default(),
+        outcome,
+        duration_ms: event.get("duration").and_then(|d| d.as_f64()).map(|ms| ms.round() as u64),
+        output,
+    })
+}
@@ -0,0 +1,573 @@
+// Syntari AI IDE - Test Runner
+// Runs selected tests through their framework, streams results and remembers the latest outcome of each test
+
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::sync::watch;
+use crate::core::{AppError, AppResult, current_timestamp, generate_id};
+use crate::project::cargo;
+use crate::project::diagnostics;
+use crate::project::testing::discovery::{self, build_tree, pytest_case, test_case};
+use crate::project::testing::reports::{self, ReportedTest};
+use crate::project::testing::types::{
+    TestCase, TestFramework, TestNode, TestOutcome, TestResult, TestResultEvent, TestRun, TestRunStatus, TestSuite,
+};
+
+/// Emitted with a `TestResultEvent` as each test finishes
+pub const TEST_RESULT_EVENT: &str = "test-result";
+/// Emitted with a `TestRun` when a run starts and finishes
+pub const TEST_RUN_EVENT: &str = "test-run";
+/// Older runs fall out of the history
+const MAX_RUNS: usize = 50;
+
+/// What to run of one suite: whole files or executables, and single tests of others
+#[derive(Debug, Clone)]
+struct SuitePlan {
+    suite: TestSuite,
+    whole_units: Vec<String>,
+    tests: Vec<TestCase>,
+}
+
+/// Registered as Tauri state
+pub struct TestExplorer {
+    /// Discovered suites per project root
+    suites: Mutex<HashMap<String, Vec<TestSuite>>>,
+    /// Latest result of every test that has run, per project root
+    results: Mutex<HashMap<String, HashMap<String, TestResult>>>,
+    runs: Mutex<VecDeque<TestRun>>,
+    cancels: Mutex<HashMap<String, watch::Sender<bool>>>,
+}
+
+impl Default for TestExplorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestExplorer {
+    pub fn new() -> Self {
+        Self {
+            suites: Mutex::new(HashMap::new()),
+            results: Mutex::new(HashMap::new()),
+            runs: Mutex::new(VecDeque::new()),
+            cancels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn has_suites(&self, project_root: &str) -> bool {
+        self.suites.lock().is_ok_and(|suites| suites.contains_key(project_root))
+    }
+
+    pub fn set_suites(&self, project_root: &str, suites: Vec<TestSuite>) {
+        if let Ok(mut all) = self.suites.lock() {
+            all.insert(project_root.to_string(), suites);
+        }
+    }
+
+    /// The explorer tree of a discovered project, with the latest outcomes
+    pub fn tree(&self, project_root: &str) -> Vec<TestNode> {
+        let suites = self.suites.lock().ok().and_then(|s| s.get(project_root).cloned()).unwrap_or_default();
+        let results = self.results.lock().ok().and_then(|r| r.get(project_root).cloned()).unwrap_or_default();
+        build_tree(&suites, &results)
+    }
+
+    /// Newest first
+    pub fn runs(&self, limit: Option<usize>) -> Vec<TestRun> {
+        match self.runs.lock() {
+            Ok(runs) => runs.iter().rev().take(limit.unwrap_or(MAX_RUNS)).cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Start running the tests under `node_ids` (everything when empty), or only those that failed last time
+    pub fn start(&self, app_handle: &tauri::AppHandle, project_root: &str, node_ids: Vec<String>, only_failed: bool) -> AppResult<TestRun> {
+        let suites = self.suites.lock().ok().and_then(|s| s.get(project_root).cloned()).unwrap_or_default();
+        let results = self.results.lock().ok().and_then(|r| r.get(project_root).cloned()).unwrap_or_default();
+        let plans = select(&suites, &results, &node_ids, only_failed);
+        if plans.is_empty() {
+            let message = if only_failed { "No failed tests to rerun" } else { "No tests match the selection" };
+            return Err(AppError::validation("NO_TESTS_SELECTED", message));
+        }
+
+        let run = TestRun {
+            run_id: generate_id(),
+            project_path: project_root.to_string(),
+            node_ids,
+            only_failed,
+            status: TestRunStatus::Running,
+            passed: 0,
+            failed: 0,
+            skipped: 0,
+            started_at: current_timestamp(),
+            finished_at: None,
+            duration_ms: None,
+            results: Vec::new(),
+            error: None,
+        };
+        let (cancel_sender, cancel) = watch::channel(false);
+        if let Ok(mut cancels) = self.cancels.lock() {
+            cancels.insert(run.run_id.clone(), cancel_sender);
+        }
+        self.record_run(app_handle, &run);
+
+        let app_handle = app_handle.clone();
+        let run_id = run.run_id.clone();
+        let project_root = project_root.to_string();
+        tauri::async_runtime::spawn(async move {
+            let explorer = app_handle.state::<TestExplorer>();
+            let started = Instant::now();
+            let mut errors = Vec::new();
+            let mut cancelled = false;
+            for plan in plans {
+                let reporter = |result: TestResult| explorer.report(&app_handle, &project_root, &run_id, result);
+                let outcome = match plan.suite.framework {
+                    TestFramework::Cargo => run_cargo(&plan, cancel.clone(), reporter).await,
+                    TestFramework::Vitest | TestFramework::Jest => run_js(&project_root, &run_id, &plan, cancel.clone(), reporter).await,
+                    TestFramework::Pytest => run_pytest(&project_root, &run_id, &plan, cancel.clone(), reporter).await,
+                };
+                match outcome {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        cancelled = true;
+                        break;
+                    }
+                    Err(error) => {
+                        tracing::warn!("Test run failed in {}: {}", plan.suite.id, error);
+                        errors.push(error);
+                    }
+                }
+            }
+            explorer.finish(&app_handle, &run_id, started, cancelled, errors);
+        });
+        Ok(run)
+    }
+
+    pub fn cancel(&self, run_id: &str) -> AppResult<()> {
+        let sender = self.cancels.lock().ok().and_then(|cancels| cancels.get(run_id).cloned());
+        match sender {
+            Some(sender) => {
+                let _ = sender.send(true);
+                Ok(())
+            }
+            None => Err(AppError::project("TEST_RUN_NOT_RUNNING", "Test run is not running")),
+        }
+    }
+
+    /// Remember a finished test, add tests only seen when running (Jest) to the tree, and notify the frontend
+    fn report(&self, app_handle: &tauri::AppHandle, project_root: &str, run_id: &str, result: TestResult) {
+        if let Ok(mut suites) = self.suites.lock() {
+            if let Some(suite) = suites.get_mut(project_root).and_then(|s| s.iter_mut().find(|s| s.id == result.suite_id)) {
+                if !suite.tests.iter().any(|t| t.id == result.test_id) {
+                    let case = case_for(suite, &result);
+                    suite.tests.push(case);
+                }
+            }
+        }
+        if let Ok(mut results) = self.results.lock() {
+            results.entry(project_root.to_string()).or_default().insert(result.test_id.clone(), result.clone());
+        }
+        if let Ok(mut runs) = self.runs.lock() {
+            if let Some(run) = runs.iter_mut().find(|r| r.run_id == run_id) {
+                match result.outcome {
+                    TestOutcome::Passed => run.passed += 1,
+                    TestOutcome::Failed => run.failed += 1,
+                    TestOutcome::Skipped => run.skipped += 1,
+                }
+                run.results.push(result.clone());
+            }
+        }
+        let event = TestResultEvent { run_id: run_id.to_string(), project_path: project_root.to_string(), result };
+        if let Err(e) = app_handle.emit(TEST_RESULT_EVENT, &event) {
+            tracing::warn!("Failed to emit test result: {}", e);
+        }
+    }
+
+    fn finish(&self, app_handle: &tauri::AppHandle, run_id: &str, started: Instant, cancelled: bool, errors: Vec<String>) {
+        if let Ok(mut cancels) = self.cancels.lock() {
+            cancels.remove(run_id);
+        }
+        let finished = self.runs.lock().ok().and_then(|mut runs| {
+            let run = runs.iter_mut().find(|r| r.run_id == run_id)?;
+            run.finished_at = Some(current_timestamp());
+            run.duration_ms = Some(started.elapsed().as_millis() as u64);
+            run.error = (!errors.is_empty()).then(|| errors.join("\n"));
+            run.status = if cancelled {
+                TestRunStatus::Cancelled
+            } else if run.results.is_empty() && run.error.is_some() {
+                TestRunStatus::Error
+            } else if run.failed > 0 || run.error.is_some() {
+                TestRunStatus::Failed
+            } else {
+                TestRunStatus::Passed
+            };
+            Some(run.clone())
+        });
+        if let Some(run) = finished {
+            tracing::info!("🧪 Test run {:?}: {} passed, {} failed, {} skipped", run.status, run.passed, run.failed, run.skipped);
+            self.record_run(app_handle, &run);
+        }
+    }
+
+    /// Insert or update a run in the history and notify the frontend
+    fn record_run(&self, app_handle: &tauri::AppHandle, run: &TestRun) {
+        if let Ok(mut runs) = self.runs.lock() {
+            match runs.iter_mut().find(|r| r.run_id == run.run_id) {
+                Some(existing) => *existing = run.clone(),
+                None => {
+                    runs.push_back(run.clone());
+                    while runs.len() > MAX_RUNS {
+                        runs.pop_front();
+                    }
+                }
+            }
+        }
+        if let Err(e) = app_handle.emit(TEST_RUN_EVENT, run) {
+            tracing::warn!("Failed to emit test run: {}", e);
+        }
+    }
+}
+
+// ================================
+// SELECTION
+// ================================
+
+fn select(suites: &[TestSuite], results: &HashMap<String, TestResult>, node_ids: &[String], only_failed: bool) -> Vec<SuitePlan> {
+    let within = |id: &str| node_ids.is_empty() || node_ids.iter().any(|node| id == node || id.starts_with(&format!("{}::", node)));
+    suites
+        .iter()
+        .filter_map(|suite| {
+            let (whole_units, tests) = if only_failed {
+                let failed: Vec<TestCase> = results
+                    .values()
+                    .filter(|r| r.suite_id == suite.id && r.outcome == TestOutcome::Failed && within(&r.test_id))
+                    .map(|r| case_for(suite, r))
+                    .collect();
+                (Vec::new(), failed)
+            } else {
+                let whole: Vec<String> = suite
+                    .units
+                    .iter()
+                    .filter(|unit| within(&format!("{}::{}", suite.id, unit.key)))
+                    .map(|unit| unit.key.clone())
+                    .collect();
+                let tests: Vec<TestCase> =
+                    suite.tests.iter().filter(|t| !whole.contains(&t.unit) && within(&t.id)).cloned().collect();
+                (whole, tests)
+            };
+            (!whole_units.is_empty() || !tests.is_empty()).then(|| SuitePlan { suite: suite.clone(), whole_units, tests })
+        })
+        .collect()
+}
+
+/// The discovered test a result belongs to, or a new one rebuilt from the result
+fn case_for(suite: &TestSuite, result: &TestResult) -> TestCase {
+    if let Some(test) = suite.tests.iter().find(|t| t.id == result.test_id) {
+        return test.clone();
+    }
+    let prefix = format!("{}::{}::", suite.id, result.unit);
+    let mut path: Vec<String> = result.test_id.strip_prefix(&prefix).unwrap_or(&result.name).split("::").map(str::to_string).collect();
+    let name = path.pop().unwrap_or_default();
+    TestCase { id: result.test_id.clone(), unit: result.unit.clone(), path, name, selector: result.selector.clone() }
+}
+
+fn result_for(suite: &TestSuite, case: TestCase, reported: ReportedTest) -> TestResult {
+    TestResult {
+        test_id: case.id,
+        suite_id: suite.id.clone(),
+        unit: case.unit,
+        selector: case.selector,
+        name: case.name,
+        outcome: reported.outcome,
+        duration_ms: reported.duration_ms,
+        output: reported.output,
+    }
+}
+
+// ================================
+// PROCESSES
+// ================================
+
+/// Resolves once the run is cancelled; never, if its sender is gone
+async fn cancelled(cancel: &mut watch::Receiver<bool>) {
+    if cancel.wait_for(|cancelled| *cancelled).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Run a command, handing each stdout line to `on_line`; `Ok(None)` means it was cancelled and killed
+async fn run_streaming(
+    mut command: tokio::process::Command,
+    mut cancel: watch::Receiver<bool>,
+    mut on_line: impl FnMut(&str),
+) -> Result<Option<(std::process::ExitStatus, String)>, String> {
+    command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+    let mut child = command.spawn().map_err(|e| format!("Failed to start tests: {}", e))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let stderr_reader = tauri::async_runtime::spawn(async move {
+        let mut text = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut text).await;
+        }
+        text
+    });
+
+    if let Some(stdout) = stdout {
+        let mut lines = BufReader::new(stdout).lines();
+        loop {
+            tokio::select! {
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => on_line(&line),
+                    _ => break,
+                },
+                _ = cancelled(&mut cancel) => {
+                    let _ = child.kill().await;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+    let status = tokio::select! {
+        status = child.wait() => status.map_err(|e| format!("Failed to wait for tests: {}", e))?,
+        _ = cancelled(&mut cancel) => {
+            let _ = child.kill().await;
+            return Ok(None);
+        }
+    };
+    Ok(Some((status, stderr_reader.await.unwrap_or_default())))
+}
+
+fn last_line(text: &str) -> &str {
+    text.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("no output").trim()
+}
+
+/// A temporary report file for one batch of a run
+fn report_path(run_id: &str, index: usize, extension: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("syntari-tests-{}-{}.{}", run_id, index, extension))
+}
+
+// ================================
+// CARGO
+// ================================
+
+/// Rebuild the test targets, then run each harness with libtest's JSON output so results stream per test
+async fn run_cargo(plan: &SuitePlan, cancel: watch::Receiver<bool>, mut reporter: impl FnMut(TestResult)) -> Result<bool, String> {
+    let workspace = cargo::load_workspace(Path::new(&plan.suite.cwd)).map_err(|e| e.message().to_string())?;
+    let binaries = discovery::build_cargo_tests(&workspace).await?;
+
+    let mut units: Vec<(String, Vec<String>)> = plan.whole_units.iter().map(|unit| (unit.clone(), Vec::new())).collect();
+    for test in &plan.tests {
+        match units.iter_mut().find(|(unit, _)| *unit == test.unit) {
+            Some((_, filters)) => filters.push(test.selector.clone()),
+            None => units.push((test.unit.clone(), vec![test.selector.clone()])),
+        }
+    }
+
+    for (unit, filters) in units {
+        let Some(binary) = binaries.iter().find(|b| b.unit.key == unit) else {
+            return Err(format!("Test target {} no longer exists", unit));
+        };
+        let mut command = tokio::process::Command::new(&binary.executable);
+        if !filters.is_empty() {
+            command.args(&filters).arg("--exact");
+        }
+        // libtest's JSON output is unstable; the harness accepts it when bootstrapping is allowed
+        command
+            .args(["-Z", "unstable-options", "--format", "json", "--report-time"])
+            .env("RUSTC_BOOTSTRAP", "1")
+            .env("CARGO_MANIFEST_DIR", &binary.unit.cwd)
+            .current_dir(&binary.unit.cwd);
+
+        let mut reported = 0usize;
+        let outcome = run_streaming(command, cancel.clone(), |line| {
+            if let Some(test) = reports::parse_libtest_line(line) {
+                let mut path: Vec<String> = test.group.split("::").map(str::to_string).collect();
+                let name = path.pop().unwrap_or_default();
+                let case = test_case(&plan.suite.id, &unit, path, name, test.group.clone());
+                reported += 1;
+                reporter(result_for(&plan.suite, case, test));
+            }
+        })
+        .await?;
+        let Some((status, stderr)) = outcome else {
+            return Ok(false);
+        };
+        // Exit code 101 with results is an ordinary test failure
+        if reported == 0 && !status.success() {
+            return Err(format!("{} exited with {}: {}", binary.unit.label, status, last_line(&stderr)));
+        }
+    }
+    Ok(true)
+}
+
+// ================================
+// VITEST AND JEST
+// ================================
+
+/// Reporters printing one marked line per finished test, so results stream like libtest's
+const JEST_REPORTER: &str = include_str!("reporters/jest-reporter.cjs");
+const VITEST_REPORTER: &str = include_str!("reporters/vitest-reporter.mjs");
+
+/// Whole files run as one batch and single tests as another, since `-t` would also filter the whole files
+async fn run_js(
+    project_root: &str,
+    run_id: &str,
+    plan: &SuitePlan,
+    cancel: watch::Receiver<bool>,
+    mut reporter: impl FnMut(TestResult),
+) -> Result<bool, String> {
+    let suite = &plan.suite;
+    let name = suite.framework.name();
+    let program = diagnostics::node_bin(Path::new(&suite.cwd), Path::new(project_root), name)
+        .ok_or_else(|| format!("{} is not installed", name))?;
+
+    let (script, extension) = match suite.framework {
+        TestFramework::Jest => (JEST_REPORTER, "cjs"),
+        _ => (VITEST_REPORTER, "mjs"),
+    };
+    let reporter_path = report_path(run_id, 0, extension);
+    std::fs::write(&reporter_path, script).map_err(|e| format!("Failed to write test reporter: {}", e))?;
+    let result = run_js_batches(Path::new(&program), &reporter_path, plan, cancel, &mut reporter).await;
+    let _ = std::fs::remove_file(&reporter_path);
+    result
+}
+
+async fn run_js_batches(
+    program: &Path,
+    reporter_path: &Path,
+    plan: &SuitePlan,
+    cancel: watch::Receiver<bool>,
+    reporter: &mut impl FnMut(TestResult),
+) -> Result<bool, String> {
+    let suite = &plan.suite;
+    let mut batches: Vec<(Vec<String>, Vec<String>)> = Vec::new();
+    if !plan.whole_units.is_empty() {
+        batches.push((plan.whole_units.clone(), Vec::new()));
+    }
+    if !plan.tests.is_empty() {
+        let mut files: Vec<String> = plan.tests.iter().map(|t| t.unit.clone()).collect();
+        files.sort();
+        files.dedup();
+        batches.push((files, plan.tests.iter().map(|t| t.selector.clone()).collect()));
+    }
+
+    for (files, selectors) in batches {
+        let mut command = tokio::process::Command::new(program);
+        match suite.framework {
+            TestFramework::Jest => {
+                command.arg("--ci").arg(format!("--reporters={}", reporter_path.display())).args(&files);
+            }
+            _ => {
+                command
+                    .args(["run", "--passWithNoTests"])
+                    .arg(format!("--reporter={}", reporter_path.display()))
+                    .args(&files);
+            }
+        }
+        if !selectors.is_empty() {
+            let pattern: Vec<String> = selectors.iter().map(|s| regex::escape(s)).collect();
+            // Full names may carry a leading space from an unnamed outer suite, so anchor on a word boundary
+            command.arg("-t").arg(format!("(?:^|\\s)(?:{})$", pattern.join("|")));
+        }
+        command.current_dir(&suite.cwd);
+
+        let mut reported = 0usize;
+        let outcome = run_streaming(command, cancel.clone(), |line| {
+            if let Some(test) = reports::parse_js_reporter_line(line) {
+                reported += 1;
+                reporter(js_result(suite, test));
+            }
+        })
+        .await?;
+        let Some((status, stderr)) = outcome else {
+            return Ok(false);
+        };
+        if reported == 0 && !status.success() {
+            return Err(format!("{} exited with {}: {}", suite.framework.name(), status, last_line(&stderr)));
+        }
+    }
+    Ok(true)
+}
+
+/// Match a reported Jest or Vitest test to its place in the suite's tree
+fn js_result(suite: &TestSuite, test: ReportedTest) -> TestResult {
+    let cwd = Path::new(&suite.cwd);
+    let file = cwd.join(&test.group);
+    let unit = file.strip_prefix(cwd).map(|p| p.to_string_lossy().replace('\\', "/")).unwrap_or_else(|_| test.group.clone());
+    // Names without describe blocks may still be written as `describe > test`
+    let mut path: Vec<String> = if test.ancestors.is_empty() {
+        test.name.split(" > ").map(str::to_string).collect()
+    } else {
+        test.ancestors.iter().cloned().chain(std::iter::once(test.name.clone())).collect()
+    };
+    let selector = path.join(" ");
+    let leaf = path.pop().unwrap_or_default();
+    let case = test_case(&suite.id, &unit, path, leaf, selector);
+    result_for(suite, case, test)
+}
+
+// ================================
+// PYTEST
+// ================================
+
+/// Node ids select single tests precisely, so one invocation covers the whole plan; results come from a JUnit report
+async fn run_pytest(
+    project_root: &str,
+    run_id: &str,
+    plan: &SuitePlan,
+    cancel: watch::Receiver<bool>,
+    mut reporter: impl FnMut(TestResult),
+) -> Result<bool, String> {
+    let suite = &plan.suite;
+    let cwd = Path::new(&suite.cwd);
+    let report = report_path(run_id, 0, "xml");
+    let mut command = tokio::process::Command::new(discovery::python_for(Path::new(project_root), cwd));
+    command
+        .args(["-m", "pytest", "-q"])
+        .arg(format!("--junitxml={}", report.display()))
+        .args(&plan.whole_units)
+        .args(plan.tests.iter().map(|t| &t.selector))
+        .current_dir(cwd);
+
+    let outcome = run_streaming(command, cancel, |_| {}).await?;
+    let Some((status, stderr)) = outcome else {
+        let _ = std::fs::remove_file(&report);
+        return Ok(false);
+    };
+    let text = std::fs::read_to_string(&report).unwrap_or_default();
+    let _ = std::fs::remove_file(&report);
+    let reported = reports::parse_junit(&text);
+    // Exit code 1 means tests failed, 5 that none were collected
+    if reported.is_empty() && !matches!(status.code(), Some(0 | 1 | 5)) {
+        return Err(format!("pytest exited with {}: {}", status, last_line(&stderr)));
+    }
+    for test in reported {
+        let node_id = pytest_node_id(cwd, &test.group, &test.name);
+        reporter(result_for(suite, pytest_case(&suite.id, &node_id), test));
+    }
+    Ok(true)
+}
+
+/// Rebuild a node id from a JUnit `classname` such as `tests.test_a.TestX`: the longest module prefix that is a file
+fn pytest_node_id(cwd: &Path, classname: &str, name: &str) -> String {
+    let parts: Vec<&str> = classname.split('.').collect();
+    for split in (1..=parts.len()).rev() {
+        let file = format!("{}.py", parts[..split].join("/"));
+        if cwd.join(&file).is_file() {
+            let mut node_id = file;
+            for class in &parts[split..] {
+                node_id.push_str("::");
+                node_id.push_str(class);
+            }
+            node_id.push_str("::");
+            node_id.push_str(name);
+            return node_id;
+        }
+    }
+    format!("{}::{}", classname, name)
+}
@@ -0,0 +1,154 @@
+// Syntari AI IDE - Test Explorer Types
+// Discovered test suites, the explorer tree and streamed test results
+
+use serde::{Deserialize, Serialize};
+
+// ================================
+// DISCOVERY
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestFramework {
+    /// libtest harnesses built by `cargo test`
+    Cargo,
+    Vitest,
+    Jest,
+    Pytest,
+}
+
+impl TestFramework {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Vitest => "vitest",
+            Self::Jest => "jest",
+            Self::Pytest => "pytest",
+        }
+    }
+}
+
+/// What one framework runs as a whole: a Cargo test executable or a test file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestUnit {
+    /// Cargo: `package/kind/target`; others: path relative to the suite directory
+    pub key: String,
+    pub label: String,
+    /// Absolute source file: the target's root module for Cargo
+    pub file: String,
+    /// Directory the unit runs in
+    pub cwd: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCase {
+    /// `<suite id>::<unit key>::<path...>::<name>`; every tree node id is a prefix of the ids below it
+    pub id: String,
+    pub unit: String,
+    /// Modules, classes or describe blocks between the file and the test
+    pub path: Vec<String>,
+    pub name: String,
+    /// How the framework selects the test: libtest name, full test name or pytest node id
+    pub selector: String,
+}
+
+/// One framework in one directory of the project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestSuite {
+    /// `<framework>@<directory relative to the project root>`
+    pub id: String,
+    pub framework: TestFramework,
+    pub cwd: String,
+    pub units: Vec<TestUnit>,
+    pub tests: Vec<TestCase>,
+    /// Set when listing failed, e.g. the project does not compile; the suite is still shown
+    pub error: Option<String>,
+}
+
+// ================================
+// TREE
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestNodeKind {
+    Suite,
+    File,
+    Module,
+    Test,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestNode {
+    pub id: String,
+    pub label: String,
+    pub kind: TestNodeKind,
+    pub framework: TestFramework,
+    pub file: Option<String>,
+    pub children: Vec<TestNode>,
+    pub test_count: usize,
+    /// Outcome of the last run: of the test itself, or the worst below a group
+    pub outcome: Option<TestOutcome>,
+    pub error: Option<String>,
+}
+
+// ================================
+// RESULTS AND RUNS
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestOutcome {
+    Passed,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestResult {
+    pub test_id: String,
+    pub suite_id: String,
+    pub unit: String,
+    pub selector: String,
+    pub name: String,
+    pub outcome: TestOutcome,
+    pub duration_ms: Option<u64>,
+    /// Failure message and captured output
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestRunStatus {
+    Running,
+    Passed,
+    Failed,
+    Cancelled,
+    /// The tests could not be built or started
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestRun {
+    pub run_id: String,
+    pub project_path: String,
+    pub node_ids: Vec<String>,
+    pub only_failed: bool,
+    pub status: TestRunStatus,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub duration_ms: Option<u64>,
+    pub results: Vec<TestResult>,
+    pub error: Option<String>,
+}
+
+/// Payload of the `test-result` event, sent as each test finishes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestResultEvent {
+    pub run_id: String,
+    pub project_path: String,
+    pub result: TestResult,
+}
@@ -13,9 +13,16 @@ pub struct ProjectContext {
     pub root_path: String,
     pub project_type: String,
     pub open_files: Vec<FileInfo>,
-    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
     pub git_branch: Option<String>,
     pub active_framework: Option<String>,
+    /// Sub-projects found under the root; the root itself is the tree's top node
+    #[serde(default)]
+    pub project_tree: Option<SubProject>,
+    /// Root path of the sub-project the active file belongs to
+    #[serde(default)]
+    pub active_sub_project: Option<String>,
 }
 
 impl ProjectContext {
@@ -27,6 +34,8 @@ impl ProjectContext {
             dependencies: Vec::new(),
             git_branch: None,
             active_framework: None,
+            project_tree: None,
+            active_sub_project: None,
         }
     }
     
@@ -35,7 +44,7 @@ impl ProjectContext {
         self
     }
     
-    pub fn with_dependencies(mut self, dependencies: Vec<String>) -> Self {
+    pub fn with_dependencies(mut self, dependencies: Vec<Dependency>) -> Self {
         self.dependencies = dependencies;
         self
     }
@@ -61,6 +70,147 @@ impl ProjectContext {
     pub fn is_git_repository(&self) -> bool {
         self.git_branch.is_some()
     }
+    
+    pub fn with_project_tree(mut self, tree: SubProject) -> Self {
+        self.project_tree = Some(tree);
+        self
+    }
+    
+    /// The innermost sub-project containing `path`
+    pub fn sub_project_for(&self, path: &str) -> Option<&SubProject> {
+        self.project_tree.as_ref()?.find_for_path(std::path::Path::new(path))
+    }
+    
+    /// Track the sub-project of a newly focused file
+    pub fn set_active_file(&mut self, path: &str) -> Option<&SubProject> {
+        self.active_sub_project = self.sub_project_for(path).map(|project| project.root_path.clone());
+        self.active_sub_project()
+    }
+    
+    pub fn active_sub_project(&self) -> Option<&SubProject> {
+        let root = self.active_sub_project.as_ref()?;
+        self.project_tree.as_ref()?.flatten().into_iter().find(|project| &project.root_path == root)
+    }
+}
+
+// ================================
+// SUB-PROJECT TYPES
+// ================================
+
+/// Tool that declared a set of member projects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceKind {
+    Npm,
+    Yarn,
+    Pnpm,
+    Cargo,
+    Go,
+    Uv,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubProject {
+    pub name: String,
+    pub root_path: String,
+    /// Path from the opened root; `.` for the root itself
+    pub relative_path: String,
+    pub project_type: String,
+    pub framework: Option<String>,
+    pub dependencies: Vec<Dependency>,
+    /// Manifest files found in this directory
+    pub manifests: Vec<String>,
+    /// Set when this project declares workspace members
+    pub workspace: Option<WorkspaceKind>,
+    /// Relative path of the workspace that lists this project as a member
+    pub workspace_root: Option<String>,
+    pub children: Vec<SubProject>,
+}
+
+impl SubProject {
+    /// The deepest project in this tree whose directory contains `path`
+    pub fn find_for_path(&self, path: &std::path::Path) -> Option<&SubProject> {
+        if !path.starts_with(&self.root_path) {
+            return None;
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.find_for_path(path))
+            .or(Some(self))
+    }
+    
+    /// This project and every descendant, depth first
+    pub fn flatten(&self) -> Vec<&SubProject> {
+        let mut projects = vec![self];
+        for child in &self.children {
+            projects.extend(child.flatten());
+        }
+        projects
+    }
+}
+
+// ================================
+// DEPENDENCY TYPES
+// ================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyScope {
+    Runtime,
+    Dev,
+    Build,
+}
+
+/// A dependency declared in a project manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "DependencyRepr")]
+pub struct Dependency {
+    pub name: String,
+    /// Requirement as written in the manifest, e.g. `^1.2` or `>=2.0,<3`; None when unconstrained
+    pub version_req: Option<String>,
+    pub scope: DependencyScope,
+    /// Manifest file the dependency was read from
+    pub manifest: String,
+}
+
+impl Dependency {
+    pub fn new(name: impl Into<String>, version_req: Option<String>, scope: DependencyScope, manifest: &str) -> Self {
+        Self {
+            name: name.into(),
+            version_req: version_req.filter(|v| !v.trim().is_empty() && v.trim() != "*"),
+            scope,
+            manifest: manifest.to_string(),
+        }
+    }
+}
+
+/// Accepts both the structured form and the bare names older contexts stored
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DependencyRepr {
+    Legacy(String),
+    Structured {
+        name: String,
+        #[serde(default)]
+        version_req: Option<String>,
+        scope: DependencyScope,
+        #[serde(default)]
+        manifest: String,
+    },
+}
+
+impl From<DependencyRepr> for Dependency {
+    fn from(repr: DependencyRepr) -> Self {
+        match repr {
+            DependencyRepr::Legacy(name) => Self {
+                name,
+                version_req: None,
+                scope: DependencyScope::Runtime,
+                manifest: String::new(),
+            },
+            DependencyRepr::Structured { name, version_req, scope, manifest } => Self { name, version_req, scope, manifest },
+        }
+    }
 }
 
 // ================================
@@ -70,10 +220,28 @@ impl ProjectContext {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectAnalysis {
     pub context: ProjectContext,
+    /// 0-100, from decision density, nesting depth and code size
     pub complexity_score: f64,
+    /// Lines of code per language
     pub language_distribution: std::collections::HashMap<String, u32>,
+    /// 0-1 confidence in `framework`
     pub framework_confidence: f64,
     pub suggested_improvements: Vec<String>,
+    #[serde(default)]
+    pub framework: Option<String>,
+    /// Evidence behind the framework choice, e.g. "next in package.json"
+    #[serde(default)]
+    pub framework_signals: Vec<String>,
+    /// Per-language statistics, largest first
+    #[serde(default)]
+    pub languages: Vec<LanguageStats>,
+    #[serde(default)]
+    pub file_count: u32,
+    #[serde(default)]
+    pub total_lines: u32,
+    /// Files contributing most to the complexity score
+    #[serde(default)]
+    pub complexity_hotspots: Vec<ComplexityHotspot>,
 }
 
 impl ProjectAnalysis {
@@ -84,10 +252,33 @@ impl ProjectAnalysis {
             language_distribution: std::collections::HashMap::new(),
             framework_confidence: 0.0,
             suggested_improvements: Vec::new(),
+            framework: None,
+            framework_signals: Vec::new(),
+            languages: Vec::new(),
+            file_count: 0,
+            total_lines: 0,
+            complexity_hotspots: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageStats {
+    pub language: String,
+    pub files: u32,
+    pub lines: u32,
+    /// Lines that are neither blank nor comments
+    pub code_lines: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityHotspot {
+    pub path: String,
+    pub code_lines: u32,
+    pub decision_points: u32,
+    pub max_nesting: u32,
+}
+
 // ================================
 // PROJECT TYPE DETECTION
 // ================================
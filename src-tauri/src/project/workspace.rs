@@ -0,0 +1,256 @@
+// Syntari AI IDE - Workspace Persistence
+// Most-recently-used projects and per-project workspace state, kept under the app data dir
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use crate::core::{AppError, AppResult, content_hash, current_timestamp, write_file_atomic};
+use crate::project::types::ProjectContext;
+
+const RECENT_FILE: &str = "recent-projects.json";
+const STATES_DIR: &str = "states";
+/// Older entries fall off the end of the list
+const MAX_RECENT_PROJECTS: usize = 30;
+
+// ================================
+// TYPES
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentProject {
+    pub path: String,
+    pub name: String,
+    pub project_type: String,
+    pub last_opened: u64,
+    pub open_count: u32,
+    /// Computed when listing; false once the directory was moved or deleted
+    #[serde(default = "default_exists")]
+    pub exists: bool,
+}
+
+fn default_exists() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CursorPosition {
+    pub line: u32,
+    pub column: u32,
+    pub scroll_top: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenTab {
+    pub path: String,
+    pub pinned: bool,
+    /// Preview tabs are replaced by the next file opened
+    pub preview: bool,
+}
+
+/// Enough to recreate a terminal; the PTY itself does not survive a restart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TerminalConfig {
+    pub name: Option<String>,
+    pub working_directory: String,
+    pub cols: Option<u16>,
+    pub rows: Option<u16>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceState {
+    pub project_path: String,
+    pub open_tabs: Vec<OpenTab>,
+    pub active_file: Option<String>,
+    /// Keyed by file path
+    pub cursors: BTreeMap<String, CursorPosition>,
+    pub expanded_folders: Vec<String>,
+    pub terminals: Vec<TerminalConfig>,
+    pub last_chat_session: Option<String>,
+    pub updated_at: u64,
+}
+
+impl WorkspaceState {
+    pub fn empty(project_path: &str) -> Self {
+        Self {
+            project_path: project_path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Drop files, folders and terminal directories that no longer exist; returns what was dropped
+    pub fn prune_missing(&mut self) -> Vec<String> {
+        let root = PathBuf::from(&self.project_path);
+        let mut missing = Vec::new();
+        // Paths may be absolute or relative to the project root
+        let mut keep = |path: &str, is_dir: bool| {
+            let resolved = root.join(path);
+            let exists = if is_dir { resolved.is_dir() } else { resolved.is_file() };
+            if !exists && !missing.iter().any(|m| m == path) {
+                missing.push(path.to_string());
+            }
+            exists
+        };
+        self.open_tabs.retain(|tab| keep(&tab.path, false));
+        self.expanded_folders.retain(|folder| keep(folder, true));
+        self.terminals.retain(|terminal| keep(&terminal.working_directory, true));
+        if self.active_file.as_deref().is_some_and(|file| !keep(file, false)) {
+            self.active_file = self.open_tabs.first().map(|tab| tab.path.clone());
+        }
+        let open: Vec<&String> = self.open_tabs.iter().map(|tab| &tab.path).collect();
+        self.cursors.retain(|path, _| open.contains(&path));
+        missing
+    }
+}
+
+/// Everything the frontend needs to put a project back the way it was left
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoredWorkspace {
+    pub project: ProjectContext,
+    pub state: WorkspaceState,
+    /// Tabs, folders and terminal directories dropped because they no longer exist
+    pub missing_paths: Vec<String>,
+}
+
+// ================================
+// STORE
+// ================================
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecentIndex {
+    projects: Vec<RecentProject>,
+}
+
+/// Registered as Tauri state; the recent list is read at startup, workspace states load on demand
+pub struct WorkspaceStore {
+    root: PathBuf,
+    recent: Mutex<RecentIndex>,
+}
+
+impl WorkspaceStore {
+    pub fn open(root: PathBuf) -> Self {
+        if let Err(e) = std::fs::create_dir_all(root.join(STATES_DIR)) {
+            tracing::error!("Failed to create workspace store at {}: {}", root.display(), e);
+        }
+
+        let recent = std::fs::read(root.join(RECENT_FILE))
+            .ok()
+            .and_then(|bytes| match serde_json::from_slice::<RecentIndex>(&bytes) {
+                Ok(index) => Some(index),
+                Err(e) => {
+                    tracing::warn!("Ignoring unreadable recent projects list: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        tracing::info!("🗂️ Workspace store opened with {} recent projects", recent.projects.len());
+        Self {
+            root,
+            recent: Mutex::new(recent),
+        }
+    }
+
+    /// Open the store under the app data directory, falling back to the temp dir
+    pub fn open_for_app(app_handle: &tauri::AppHandle) -> Self {
+        let root = crate::core::app_data_subdir(app_handle, "workspaces").unwrap_or_else(|e| {
+            e.log_error();
+            std::env::temp_dir().join("syntari-workspaces")
+        });
+        Self::open(root)
+    }
+
+    fn lock_recent(&self) -> AppResult<std::sync::MutexGuard<'_, RecentIndex>> {
+        self.recent
+            .lock()
+            .map_err(|_| AppError::internal("WORKSPACE_STORE_POISONED", "Workspace store is poisoned"))
+    }
+
+    fn state_path(&self, project_path: &str) -> PathBuf {
+        self.root.join(STATES_DIR).join(format!("{}.json", content_hash(project_path.as_bytes())))
+    }
+
+    // ================================
+    // RECENT PROJECTS
+    // ================================
+
+    /// Move the project to the front of the recent list
+    pub fn record_opened(&self, project: &ProjectContext) -> AppResult<()> {
+        let mut recent = self.lock_recent()?;
+        let previous = recent.projects.iter().position(|p| p.path == project.root_path).map(|i| recent.projects.remove(i));
+        recent.projects.insert(0, RecentProject {
+            path: project.root_path.clone(),
+            name: Path::new(&project.root_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| project.root_path.clone()),
+            project_type: project.project_type.clone(),
+            last_opened: current_timestamp(),
+            open_count: previous.map(|p| p.open_count).unwrap_or(0).saturating_add(1),
+            exists: true,
+        });
+        recent.projects.truncate(MAX_RECENT_PROJECTS);
+        self.save_recent(&recent)
+    }
+
+    /// Most recent first, with `exists` refreshed from disk
+    pub fn recent_projects(&self, limit: Option<usize>) -> Vec<RecentProject> {
+        let projects = match self.recent.lock() {
+            Ok(recent) => recent.projects.clone(),
+            Err(_) => Vec::new(),
+        };
+        projects
+            .into_iter()
+            .take(limit.unwrap_or(MAX_RECENT_PROJECTS))
+            .map(|mut project| {
+                project.exists = Path::new(&project.path).is_dir();
+                project
+            })
+            .collect()
+    }
+
+    /// Forget a project: its recent entry and its saved workspace state
+    pub fn remove(&self, project_path: &str) -> AppResult<()> {
+        let mut recent = self.lock_recent()?;
+        recent.projects.retain(|p| p.path != project_path);
+        match std::fs::remove_file(self.state_path(project_path)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        self.save_recent(&recent)
+    }
+
+    fn save_recent(&self, recent: &RecentIndex) -> AppResult<()> {
+        let bytes = serde_json::to_vec_pretty(recent)?;
+        write_file_atomic(&self.root.join(RECENT_FILE), &bytes)
+    }
+
+    // ================================
+    // WORKSPACE STATE
+    // ================================
+
+    pub fn save_state(&self, state: &WorkspaceState) -> AppResult<()> {
+        let mut state = state.clone();
+        state.updated_at = current_timestamp();
+        let bytes = serde_json::to_vec_pretty(&state)?;
+        write_file_atomic(&self.state_path(&state.project_path), &bytes)
+    }
+
+    /// The saved state, or an empty one for a project never saved
+    pub fn load_state(&self, project_path: &str) -> WorkspaceState {
+        let Ok(bytes) = std::fs::read(self.state_path(project_path)) else {
+            return WorkspaceState::empty(project_path);
+        };
+        match serde_json::from_slice::<WorkspaceState>(&bytes) {
+            Ok(state) => state,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable workspace state for {}: {}", project_path, e);
+                WorkspaceState::empty(project_path)
+            }
+        }
+    }
+}
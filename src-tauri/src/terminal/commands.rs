@@ -64,6 +64,47 @@ impl TerminalManager {
     }
 
     pub fn create_session(&self, working_dir: &str, cols: u16, rows: u16) -> Result<String, String> {
+        // Determine shell based on platform
+        let shell = default_shell();
+        let (session_id, _child) = self.spawn_session(working_dir, CommandBuilder::new(&shell), &shell, cols, rows)?;
+
+        // Give the shell a moment to initialize and send its prompt
+        thread::sleep(Duration::from_millis(100));
+
+        Ok(session_id)
+    }
+
+    /// Run one command line through the shell in its own PTY; the child is returned so callers can wait for its exit code
+    pub fn create_command_session(
+        &self,
+        working_dir: &str,
+        command_line: &str,
+        env: &[(String, String)],
+        cols: u16,
+        rows: u16,
+    ) -> Result<(String, Box<dyn portable_pty::Child + Send + Sync>), String> {
+        let shell = default_shell();
+        let mut cmd = CommandBuilder::new(&shell);
+        if cfg!(target_os = "windows") {
+            cmd.arg("/C");
+        } else {
+            cmd.arg("-c");
+        }
+        cmd.arg(command_line);
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
+        self.spawn_session(working_dir, cmd, &shell, cols, rows)
+    }
+
+    fn spawn_session(
+        &self,
+        working_dir: &str,
+        mut cmd: CommandBuilder,
+        shell: &str,
+        cols: u16,
+        rows: u16,
+    ) -> Result<(String, Box<dyn portable_pty::Child + Send + Sync>), String> {
         let pty_system = portable_pty::native_pty_system();
         
         // Create pty with specified dimensions from frontend
@@ -76,22 +117,11 @@ impl TerminalManager {
             })
             .map_err(|e| format!("Failed to create pty: {}", e))?;
 
-        // Determine shell based on platform
-        let shell = if cfg!(target_os = "windows") {
-            std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string())
-        } else {
-            std::env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string())
-        };
-
-        // Create command builder
-        let cmd = CommandBuilder::new(&shell);
-        let mut cmd = cmd;
-
         // Set working directory
         cmd.cwd(working_dir);
 
         // Spawn the shell process in the pty
-        let _child = pty_pair
+        let child = pty_pair
             .slave
             .spawn_command(cmd)
             .map_err(|e| format!("Failed to spawn shell: {}", e))?;
@@ -187,7 +217,7 @@ impl TerminalManager {
             created_at: current_time,
             last_activity: Arc::new(Mutex::new(current_time)),
             working_directory: working_dir.to_string(),
-            shell: shell.clone(),
+            shell: shell.to_string(),
         };
 
         // Store session
@@ -195,10 +225,7 @@ impl TerminalManager {
             sessions.insert(session_id.clone(), session);
         }
 
-        // Give the shell a moment to initialize and send its prompt
-        thread::sleep(Duration::from_millis(100));
-
-        Ok(session_id)
+        Ok((session_id, child))
     }
 
     pub fn send_input(&self, session_id: &str, input: &str) -> Result<(), String> {
@@ -257,6 +284,10 @@ impl TerminalManager {
                     thread::sleep(Duration::from_millis(10));
                 }
                 Err(TryRecvError::Disconnected) => {
+                    // The process exited; hand over its last output before reporting the disconnect
+                    if !accumulated_output.is_empty() {
+                        break;
+                    }
                     return Err("Terminal session disconnected".to_string());
                 }
             }
@@ -346,6 +377,14 @@ impl TerminalManager {
     }
 }
 
+fn default_shell() -> String {
+    if cfg!(target_os = "windows") {
+        std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string())
+    } else {
+        std::env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string())
+    }
+}
+
 /// Create terminal session
 #[command]
 pub async fn create_terminal_session(
@@ -414,7 +414,12 @@ const App: React.FC = () => {
             content: file.content,
             language: file.language,
           })),
-          dependencies: projectResult.data.dependencies,
+          dependencies: projectResult.data.dependencies.map((dep: any) => ({
+            name: dep.name,
+            versionReq: dep.version_req,
+            scope: dep.scope,
+            manifest: dep.manifest,
+          })),
           gitBranch: projectResult.data.git_branch,
           activeFramework: projectResult.data.active_framework,
           lastAnalyzed: Date.now(),
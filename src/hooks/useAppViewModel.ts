@@ -37,11 +37,18 @@ interface BackendFileInfo {
   language?: string;
 }
 
+interface BackendDependency {
+  name: string;
+  version_req?: string;
+  scope: 'runtime' | 'dev' | 'build';
+  manifest: string;
+}
+
 interface BackendProjectContext {
   root_path: string;
   project_type: string;
   open_files: BackendFileInfo[];
-  dependencies: string[];
+  dependencies: BackendDependency[];
   git_branch?: string;
   active_framework?: string;
 }
@@ -426,7 +433,12 @@ export const useAppViewModel = (): UseAppViewModelReturn => {
       content: file.content,
       language: file.language,
     })),
-    dependencies: backendProject.dependencies,
+    dependencies: backendProject.dependencies.map(dep => ({
+      name: dep.name,
+      versionReq: dep.version_req,
+      scope: dep.scope,
+      manifest: dep.manifest,
+    })),
     gitBranch: backendProject.git_branch,
     activeFramework: backendProject.active_framework,
     lastAnalyzed: Date.now(),
@@ -30,11 +30,18 @@ export interface FileInfo {
   readonly checksum?: string;
 }
 
+export interface ProjectDependency {
+  readonly name: string;
+  readonly versionReq?: string;
+  readonly scope: 'runtime' | 'dev' | 'build';
+  readonly manifest: string;
+}
+
 export interface ProjectContext {
   readonly rootPath: string;
   readonly projectType: 'rust' | 'typescript' | 'python' | 'mixed' | 'unknown';
   readonly openFiles: readonly FileInfo[];
-  readonly dependencies: readonly string[];
+  readonly dependencies: readonly ProjectDependency[];
   readonly gitBranch?: string;
   readonly activeFramework?: string;
   readonly securityScan?: SecurityScanResult;